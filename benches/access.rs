@@ -0,0 +1,93 @@
+//! 変数と関数の参照にかかる時間を、スロットによる参照と名前による参照で比べるベンチマーク
+//!
+//! スタックマシンはコンパイル時に解決されたスロットで、Evaluatorは `VariableMapper` と `FunctionMapper` から名前で参照します。
+//! プロセスの起動、解析、コンパイルを含めないよう、同じプロセスで評価だけの時間を計ります。
+//! 同じ回数だけ繰り返し、参照の代わりにリテラルを使うプログラムの時間を引いて、1回の参照にかかった時間を求めます。
+//! 実行時間は、繰り返し実行したうちの最短の時間とします。
+//!
+//! ```sh
+//! cargo bench --bench access
+//! ```
+use shot::virtual_machine::benchmark::Program;
+use std::time::{Duration, Instant};
+
+/// プログラムごとの実行回数
+const ITERATIONS: u32 = 20;
+
+/// プログラムの中で繰り返す回数
+const LOOPS: usize = 300;
+
+/// 1回の繰り返しで参照する回数
+const ACCESSES: usize = 256;
+
+/// 外側の関数の変数の名前
+const VARIABLES: [&str; 8] = ["a", "b", "c", "d", "e", "f", "g", "h"];
+
+fn main() {
+    let baseline: Program = Program::compile(&program(&vec!["1"; ACCESSES]));
+    let variables: Program = Program::compile(&program(
+        &VARIABLES
+            .iter()
+            .copied()
+            .cycle()
+            .take(ACCESSES)
+            .collect::<Vec<&str>>(),
+    ));
+    let functions: Program = Program::compile(&program(&vec!["one()"; ACCESSES]));
+
+    let baseline_times: (Duration, Duration) = measure(&baseline);
+    // リテラルを読む場合と比べて、1回の参照で増えた時間
+    println!("{:<16} {:>16} {:>16}", "access", "slot", "name");
+    for (name, program) in [("variables", &variables), ("functions", &functions)] {
+        let (slot, by_name) = measure(program);
+        // 参照以外にかかった時間を引き、1回の参照あたりの時間にする
+        let per_access = |time: Duration, baseline: Duration| {
+            time.saturating_sub(baseline).as_secs_f64() * 1e9 / (LOOPS * ACCESSES) as f64
+        };
+        let slot: f64 = per_access(slot, baseline_times.0);
+        let by_name: f64 = per_access(by_name, baseline_times.1);
+        println!("{:<16} {:>13.1} ns {:>13.1} ns", name, slot, by_name);
+    }
+}
+
+/// 外側の関数の変数と関数を、1回の繰り返しごとに `terms` の順に参照して足し合わせるプログラムを作る
+///
+/// 繰り返しの終わりは、`k` が0の場合だけ19桁の0を連結した文字列を整数にキャストできることで判定します。
+fn program(terms: &[&str]) -> String {
+    let declarations: String = VARIABLES
+        .iter()
+        .map(|name| format!("    let {}: int = 1;\n", name))
+        .collect();
+    format!(
+        r#"let run: fn = (n: int): int {{
+{declarations}    let one: fn = (): int {{ return 1; }};
+    let count: fn = (k: int, acc: int): int {{
+        let is_zero: bool = ((k as string) + "0000000000000000000") as? int is int;
+        let sum: int = {sum};
+        return if is_zero {{ acc }} else {{ count(k: k - 1, acc: acc + sum) }};
+    }};
+    return count(k: n, acc: 0);
+}};
+return run(n: {loops});
+"#,
+        sum = terms.join(" + "),
+        loops = LOOPS
+    )
+}
+
+/// スタックマシンとEvaluatorで繰り返し実行し、それぞれの最短の時間を返す
+fn measure(program: &Program) -> (Duration, Duration) {
+    let expected = program.run_on_evaluator();
+    let mut stack_machine: Duration = Duration::MAX;
+    let mut evaluator: Duration = Duration::MAX;
+    for _ in 0..ITERATIONS {
+        let started_at: Instant = Instant::now();
+        assert_eq!(program.run_on_stack_machine(), expected);
+        stack_machine = stack_machine.min(started_at.elapsed());
+
+        let started_at: Instant = Instant::now();
+        assert_eq!(program.run_on_evaluator(), expected);
+        evaluator = evaluator.min(started_at.elapsed());
+    }
+    (stack_machine, evaluator)
+}
@@ -0,0 +1,76 @@
+//! 代表的なスクリプトの実行時間を、スタックマシンとEvaluatorで比べるベンチマーク
+//!
+//! `benches/scripts/*.shot` を `shot` コマンドで繰り返し実行し、実行時間の中央値を表示します。
+//! 実行ごとのばらつきを抑えるため、平均ではなく中央値を用い、スタックマシンとEvaluatorを交互に実行します。
+//! Evaluatorは `--tree-walk` で実行します。
+//!
+//! ```sh
+//! cargo bench --bench scripts
+//! ```
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+/// スクリプトごとの実行回数
+const ITERATIONS: usize = 21;
+
+fn main() {
+    let scripts_dir: PathBuf = Path::new(env!("CARGO_MANIFEST_DIR")).join("benches/scripts");
+    let mut scripts: Vec<PathBuf> = fs::read_dir(&scripts_dir)
+        .expect("benches/scripts must exist")
+        .map(|entry| entry.expect("entry must be readable").path())
+        .filter(|path| {
+            path.extension()
+                .is_some_and(|extension| extension == "shot")
+        })
+        .collect();
+    scripts.sort();
+
+    println!(
+        "{:<16} {:>16} {:>16} {:>8}",
+        "script", "stack machine", "tree-walk", "speedup"
+    );
+    for script in scripts.iter() {
+        let (stack_machine, tree_walk) = measure(script);
+        println!(
+            "{:<16} {:>13.2} ms {:>13.2} ms {:>7.1}x",
+            script.file_stem().unwrap().to_string_lossy(),
+            stack_machine.as_secs_f64() * 1000.0,
+            tree_walk.as_secs_f64() * 1000.0,
+            tree_walk.as_secs_f64() / stack_machine.as_secs_f64(),
+        );
+    }
+}
+
+/// スクリプトをスタックマシンとEvaluatorで交互に繰り返し実行し、それぞれの実行時間の中央値を返す
+///
+/// 交互に実行することで、計測中のマシンの負荷の変化が両方に同じように表れます。
+fn measure(script: &Path) -> (Duration, Duration) {
+    let mut stack_machine: Vec<Duration> = vec![];
+    let mut tree_walk: Vec<Duration> = vec![];
+    for _ in 0..ITERATIONS {
+        stack_machine.push(run(script, &[]));
+        tree_walk.push(run(script, &["--tree-walk"]));
+    }
+    (median(stack_machine), median(tree_walk))
+}
+
+/// スクリプトを一度実行し、実行時間を返す
+fn run(script: &Path, flags: &[&str]) -> Duration {
+    let started_at: Instant = Instant::now();
+    let status = Command::new(env!("CARGO_BIN_EXE_shot"))
+        .args(flags)
+        .arg("-f")
+        .arg(script)
+        .stdout(Stdio::null())
+        .status()
+        .expect("shot must be executable");
+    assert!(status.success(), "{} failed", script.display());
+    started_at.elapsed()
+}
+
+fn median(mut durations: Vec<Duration>) -> Duration {
+    durations.sort();
+    durations[durations.len() / 2]
+}
@@ -6,6 +6,7 @@ use loader_error::LoaderError;
 pub struct Loader {
     pub source_code: String,
     pub source_code_vector: Vec<String>,
+    pub file_path: Option<String>,
 }
 
 impl Loader {
@@ -13,6 +14,7 @@ impl Loader {
         Loader {
             source_code: content.clone(),
             source_code_vector: content.split("\n").map(|s| s.to_string()).collect(),
+            file_path: None,
         }
     }
 
@@ -41,6 +43,7 @@ impl Loader {
         Loader {
             source_code: content.clone(),
             source_code_vector: content.split("\n").map(|s| s.to_string()).collect(),
+            file_path: None,
         }
     }
 
@@ -57,6 +60,7 @@ impl Loader {
         Ok(Loader {
             source_code: source_code.clone(),
             source_code_vector: source_code.split("\n").map(|s| s.to_string()).collect(),
+            file_path: Some(file_path),
         })
     }
 }
@@ -1,13 +1,24 @@
-mod loader;
-mod receiver;
-mod virtual_machine;
+use shot::loader::Loader;
+use shot::receiver::{ReceivedData, Receiver};
+use shot::virtual_machine::VirtualMachine;
 
-use crate::receiver::ReceivedData;
-use crate::virtual_machine::VirtualMachine;
-use loader::Loader;
-use receiver::Receiver;
+/// プログラムを実行するスレッドのスタックの大きさ
+///
+/// Evaluatorは関数の呼び出しごとにRustの関数を再帰的に呼び出すため、
+/// 呼び出しの深さが上限に達するまで再帰できる大きさにします。
+const STACK_SIZE: usize = 256 * 1024 * 1024;
 
 fn main() {
+    let runner = std::thread::Builder::new()
+        .stack_size(STACK_SIZE)
+        .spawn(run)
+        .expect("failed to spawn the thread to run the program");
+    if runner.join().is_err() {
+        std::process::exit(1);
+    }
+}
+
+fn run() {
     let receiver: Receiver = Receiver::new();
     let received_data: ReceivedData = receiver.receive();
 
@@ -19,10 +30,20 @@ fn main() {
         }
     };
 
-    let virtual_machine: VirtualMachine = VirtualMachine::new(
+    let mut virtual_machine: VirtualMachine = VirtualMachine::new(
         loader.source_code,
         loader.source_code_vector,
+        loader.file_path,
         received_data.debug_mode,
+        received_data.named_only,
+        received_data.infer_types,
+        received_data.warn_shadowing,
     );
+    if received_data.tree_walk {
+        virtual_machine = virtual_machine.with_tree_walking_evaluator();
+    }
+    if received_data.optimize {
+        virtual_machine = virtual_machine.with_optimization();
+    }
     virtual_machine.run();
 }
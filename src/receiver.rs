@@ -6,6 +6,11 @@ pub struct ReceivedData {
     pub source_code: Option<String>,
     pub file_path: Option<String>,
     pub debug_mode: bool,
+    pub named_only: bool,
+    pub infer_types: bool,
+    pub warn_shadowing: bool,
+    pub tree_walk: bool,
+    pub optimize: bool,
 }
 
 impl Default for Receiver {
@@ -43,11 +48,21 @@ impl Receiver {
         let source_code: Option<String> = matches.get_one::<String>("inline").cloned();
         let file_path: Option<String> = matches.get_one::<String>("file").cloned();
         let debug_mode: bool = matches.get_flag("debug");
+        let named_only: bool = matches.get_flag("named-only");
+        let infer_types: bool = matches.get_flag("infer-types");
+        let warn_shadowing: bool = matches.get_flag("warn-shadowing");
+        let tree_walk: bool = matches.get_flag("tree-walk");
+        let optimize: bool = matches.get_flag("optimize");
 
         ReceivedData {
             source_code,
             file_path,
             debug_mode,
+            named_only,
+            infer_types,
+            warn_shadowing,
+            tree_walk,
+            optimize,
         }
     }
 
@@ -56,7 +71,7 @@ impl Receiver {
             .about("kill your task in one shot")
             .author("shunsock")
             .version("0.1.0")
-            .help_template(&format!(
+            .help_template(format!(
                 "{}\n\n{{before-help}}{{about}}\n\nUSAGE:\n    {{usage}}\n\n{{all-args}}{{after-help}}\n",
                 ASCII_ART
             ))
@@ -79,5 +94,39 @@ impl Receiver {
                     .action(ArgAction::SetTrue)
                     .help("Enable debug mode"),
             )
+            .arg(
+                Arg::new("named-only")
+                    .short('n')
+                    .long("named-only")
+                    .action(ArgAction::SetTrue)
+                    .help("Reject positional arguments in function calls"),
+            )
+            .arg(
+                Arg::new("infer-types")
+                    .short('t')
+                    .long("infer-types")
+                    .action(ArgAction::SetTrue)
+                    .help("Allow omitted type annotations on let declarations and return types"),
+            )
+            .arg(
+                Arg::new("warn-shadowing")
+                    .short('w')
+                    .long("warn-shadowing")
+                    .action(ArgAction::SetTrue)
+                    .help("Warn when a declaration shadows one in an outer scope"),
+            )
+            .arg(
+                Arg::new("tree-walk")
+                    .long("tree-walk")
+                    .action(ArgAction::SetTrue)
+                    .help("Evaluate the AST directly instead of compiling it to bytecode"),
+            )
+            .arg(
+                Arg::new("optimize")
+                    .short('O')
+                    .long("optimize")
+                    .action(ArgAction::SetTrue)
+                    .help("Fold constant expressions and remove dead code before evaluation"),
+            )
     }
 }
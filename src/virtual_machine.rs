@@ -1,37 +1,95 @@
 pub mod ast;
+#[doc(hidden)]
+pub mod benchmark;
+mod bytecode;
 mod evaluator;
+mod expression_typer;
+mod module_loader;
+mod optimizer;
 pub mod parser;
+mod resolver;
 mod scanner;
+mod shadowing_checker;
 pub mod token;
+mod type_checker;
+mod type_inferrer;
 
+use crate::virtual_machine::ast::LiteralValue;
 use crate::virtual_machine::ast::AST;
+use crate::virtual_machine::evaluator::call_stack::Traceback;
+use crate::virtual_machine::evaluator::constant_folder::fold_constants;
+use crate::virtual_machine::evaluator::evaluation_error::EvaluationError;
 use crate::virtual_machine::evaluator::mapper::function_mapper::FunctionMapper;
 use crate::virtual_machine::evaluator::mapper::variable_mapper::VariableMapper;
 use crate::virtual_machine::evaluator::Evaluator;
 use crate::virtual_machine::parser::Parser;
 use crate::virtual_machine::token::Token;
+use bytecode::{compile, Chunk, StackMachine};
+use module_loader::ModuleLoader;
+use optimizer::optimize;
+use resolver::resolve;
 use scanner::Scanner;
+use shadowing_checker::find_shadowing;
+use std::path::Path;
 use std::process::exit;
+use type_checker::check_types;
+use type_inferrer::infer_types;
+
+/// 型推論を有効にするpragma
+const INFER_TYPES_PRAGMA: &str = "# pragma: infer-types";
 
 pub struct VirtualMachine {
     source_code: String,
     source_code_vector: Vec<String>,
+    file_path: Option<String>,
     debug: bool,
+    named_only: bool,
+    infer_types: bool,
+    warn_shadowing: bool,
+    tree_walk: bool,
+    optimize: bool,
 }
 
 impl VirtualMachine {
     pub fn new(
         source_code: String,
         source_code_vector: Vec<String>,
+        file_path: Option<String>,
         debug: bool,
+        named_only: bool,
+        infer_types: bool,
+        warn_shadowing: bool,
     ) -> VirtualMachine {
         VirtualMachine {
             source_code,
             source_code_vector,
+            file_path,
             debug,
+            named_only,
+            infer_types,
+            warn_shadowing,
+            tree_walk: false,
+            optimize: false,
         }
     }
 
+    /// バイトコードにコンパイルせず、ASTを直接評価するEvaluatorで実行する
+    ///
+    /// Evaluatorは、スタックマシンの結果と性能を比べるための参照実装です。
+    pub fn with_tree_walking_evaluator(mut self) -> Self {
+        self.tree_walk = true;
+        self
+    }
+
+    /// 評価の前にASTを最適化する
+    ///
+    /// 定数式の畳み込み、return文より後の文の除去、定数の変数の参照の置き換えを行います。
+    /// 最適化しない場合と同じ値とエラーになります。
+    pub fn with_optimization(mut self) -> Self {
+        self.optimize = true;
+        self
+    }
+
     pub fn run(self) {
         // 字句解析
         let scanner: Scanner =
@@ -51,6 +109,17 @@ impl VirtualMachine {
 
         // 構文解析
         let mut parser: Parser = Parser::new(tokens);
+        // named_only が有効な場合は、位置引数を禁止する
+        if self.named_only {
+            parser = parser.with_named_only();
+        }
+        // 型推論が有効な場合は、型注釈の省略を許可する
+        // CLIのフラグか、ファイル先頭の `# pragma: infer-types` で有効になる
+        let is_type_inference_enabled: bool =
+            self.infer_types || Self::has_infer_types_pragma(&self.source_code_vector);
+        if is_type_inference_enabled {
+            parser = parser.with_type_inference();
+        }
         let ast: AST = match parser.parse() {
             Ok(ast) => ast,
             Err(error) => {
@@ -59,25 +128,131 @@ impl VirtualMachine {
             }
         };
 
+        // 構文解析の直後のASTを表示する
+        // 検査やエラーで終了する場合も、Parserが作ったASTを確認できる
         if self.debug {
             Self::print_statements(ast.clone());
         }
 
+        // モジュールの読み込み
+        // importのパスは、ファイルのディレクトリからの相対パスとして解決する
+        // ソースコードを直接渡した場合は、カレントディレクトリを基準にする
+        let base_dir: &Path = match &self.file_path {
+            Some(file_path) => Path::new(file_path).parent().unwrap_or(Path::new(".")),
+            None => Path::new("."),
+        };
+        let mut module_loader: ModuleLoader =
+            ModuleLoader::new(self.named_only, is_type_inference_enabled);
+        if let Some(file_path) = &self.file_path {
+            module_loader = module_loader.with_entry_file(Path::new(file_path));
+        }
+        // モジュールも、実行するファイルと同じ方法で評価する
+        if self.tree_walk {
+            module_loader = module_loader.with_tree_walking_evaluator();
+        }
+        if self.optimize {
+            module_loader = module_loader.with_optimization();
+        }
+        let ast: AST = match module_loader.link(ast, base_dir) {
+            Ok(ast) => ast,
+            Err(error) => {
+                eprintln!("{:?}", error.to_string());
+                exit(1);
+            }
+        };
+
+        // シャドーイングの警告
+        if self.warn_shadowing {
+            for warning in find_shadowing(&ast) {
+                eprintln!("{}", warning);
+            }
+        }
+
+        // 名前の検査 (スロットはバイトコードへのコンパイルで割り当てる)
+        // 宣言されていない名前と重複した宣言を、全て表示してから終了する
+        if let Err(errors) = resolve(&ast) {
+            for error in errors {
+                eprintln!("{:?}", error.to_string());
+            }
+            exit(1);
+        }
+
+        // 型推論
+        let ast: AST = if is_type_inference_enabled {
+            match infer_types(ast) {
+                Ok(ast) => ast,
+                Err(error) => {
+                    eprintln!("{:?}", error.to_string());
+                    exit(1);
+                }
+            }
+        } else {
+            ast
+        };
+
+        // 型検査
+        // 見つかった全ての型エラーを表示してから終了する
+        if let Err(errors) = check_types(&ast) {
+            for error in errors {
+                eprintln!("{:?}", error.to_string());
+            }
+            exit(1);
+        }
+
+        // 定数の畳み込み
+        let ast: AST = match fold_constants(ast) {
+            Ok(ast) => ast,
+            Err(error) => {
+                eprintln!("{:?}", error.to_string());
+                exit(1);
+            }
+        };
+
+        // 最適化
+        let ast: AST = if self.optimize { optimize(ast) } else { ast };
+
         // 評価
-        let mut evaluator: Evaluator =
-            Evaluator::new(ast, FunctionMapper::new(), VariableMapper::new());
+        // エラーで終わった場合に表示するため、呼び出しの履歴も受け取る
+        let (result, traceback): (Result<LiteralValue, EvaluationError>, Traceback) =
+            if self.tree_walk {
+                let mut evaluator: Evaluator =
+                    Evaluator::new(ast, FunctionMapper::new(), VariableMapper::new());
+                (evaluator.evaluate(), evaluator.traceback())
+            } else {
+                // バイトコードへのコンパイル
+                let chunk: Chunk = compile(&ast);
+
+                if self.debug {
+                    println!("Bytecode is compiled:");
+                    print!("{}", chunk);
+                }
 
-        match evaluator.evaluate() {
+                let mut stack_machine: StackMachine = StackMachine::new();
+                (stack_machine.run(&chunk), stack_machine.traceback())
+            };
+        match result {
             Ok(result) => {
                 println!("{:?}", result);
             }
             Err(error) => {
+                // 関数の中で起きたエラーは、呼び出しの履歴とともに表示する
+                if traceback.is_in_function() {
+                    eprintln!("{}", traceback);
+                }
                 eprintln!("{:?}", error.to_string());
                 exit(1);
             }
         }
     }
 
+    /// ファイル先頭の行が型推論を有効にするpragmaか判定する
+    fn has_infer_types_pragma(source_code_vector: &[String]) -> bool {
+        match source_code_vector.first() {
+            Some(line) => line.trim() == INFER_TYPES_PRAGMA,
+            None => false,
+        }
+    }
+
     fn print_tokens(tokens: Vec<Token>) {
         println!("Scanned Tokens:");
         for token in tokens {
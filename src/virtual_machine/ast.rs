@@ -1,4 +1,5 @@
 use std::fmt;
+use std::rc::Rc;
 
 // ---------------------------------------------------------------------
 // Abstract Syntax Tree
@@ -31,10 +32,17 @@ impl AST {
 /// ```shot
 /// let f: fn  = add(a: int, b: int): int { };
 /// let a: int = 1;
+/// const B: int = 2;
+/// import "lib/strings.shot" as strings;
+/// export let pad: fn = (value: string): string { return value; };
+/// newtype UserId = int;
+/// interface Named { let name: fn = (self): string; }
+/// impl UserId { let name: fn = (self): string { return "user"; }; }
 /// return a;
 /// ```
 ///
 /// Statementは必ず文末にセミコロンがつきます。
+/// ただし、ブロック `{ ... }` は閉じ括弧で終わるため、セミコロンは不要です。
 /// 例えば、下記のようなコードはエラーとなります。
 ///
 /// ```shot
@@ -43,9 +51,136 @@ impl AST {
 #[derive(Debug, PartialEq, Clone)]
 pub enum Statement {
     Expression(ExpressionNode),
-    DeclarationOfFunction(Box<FunctionDeclarationNode>), // 関数宣言
+    DeclarationOfFunction(Rc<FunctionDeclarationNode>), // 関数宣言
     DeclarationOfVariable(Box<VariableDeclarationNode>), // 変数宣言
-    Return(Box<ExpressionNode>),                         // return文
+    DeclarationOfConstant(Box<VariableDeclarationNode>), // 定数宣言
+    Return(Box<ExpressionNode>),                        // return文
+    Import(Box<ImportNode>),                            // import文
+    Export(Box<Statement>),                             // export付きの宣言
+    Block(Box<BlockNode>),                              // ブロック
+    DeclarationOfNewtype(Box<NewtypeDeclarationNode>),  // newtype宣言
+    DeclarationOfInterface(Box<InterfaceDeclarationNode>), // interface宣言
+    Impl(Box<ImplNode>),                                // メソッドの実装
+}
+
+/// ## ブロックノード
+///
+/// ブロックノードは、`{ ... }` で囲まれた文の並びを表すノードです。
+/// ブロックは独自のスコープを持ち、ブロックの中で宣言した変数はブロックの外から参照できません。
+///
+/// ブロックの中では、外側のスコープの変数と同じ名前の変数を宣言できます (シャドーイング)。
+/// 同じブロックの中で同じ名前の変数を宣言した場合はエラーとなります。
+///
+/// ```shot
+/// let x: int = 1;
+/// {
+///     let x: string = "inner";  # 外側の x をシャドーイングする
+///     let x: int = 2;           # Error: 同じブロックで再宣言している
+/// }
+/// ```
+///
+/// ブロックの中の文は、ASTと同じく行番号とともに保持します。
+#[derive(Debug, PartialEq, Clone)]
+pub struct BlockNode {
+    pub statements: Vec<(usize, Statement)>, // (行番号, 文) のリスト
+}
+
+/// ## newtype宣言ノード
+///
+/// newtype宣言ノードは、既存の型をもとに新しい型を宣言するnewtype文を表すノードです。
+/// 例えば、`newtype UserId = int;` の場合、型名は `UserId`、元の型は `int` になります。
+///
+/// newtypeで宣言した型は、元の型とは別の型として扱われます。
+/// `UserId` 型のパラメータに `int` の値を渡すことはできず、`int` 型のパラメータに `UserId` の値を渡すこともできません。
+/// 元の型の値との変換は、`as` による型キャストで明示的に行います。
+///
+/// ```shot
+/// newtype UserId = int;
+/// let id: UserId = 42 as UserId;  # int の値を UserId で包む
+/// let raw: int = id as int;       # UserId の値から int の値を取り出す
+/// ```
+///
+/// 型名は構文解析の時点で解決されるため、newtype宣言は型名を使う前に書く必要があります。
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewtypeDeclarationNode {
+    pub name: String,          // 型名
+    pub underlying_type: Type, // 元の型
+}
+
+/// ## interface宣言ノード
+///
+/// interface宣言ノードは、型が持つべきメソッドの一覧を宣言するinterface文を表すノードです。
+/// 例えば、下記の場合、型名は `Addressable`、メソッドは `url` になります。
+///
+/// ```shot
+/// interface Addressable {
+///     let url: fn = (self): string;
+/// }
+/// ```
+///
+/// interfaceで宣言した型は、パラメータなどの型注釈に使うことができます。
+/// interface型には、全てのメソッドを同じシグネチャで実装した型の値が当てはまります。
+/// 型が interface を実装していることを宣言する必要はありません。
+#[derive(Debug, Clone, PartialEq)]
+pub struct InterfaceDeclarationNode {
+    pub name: String,                      // 型名
+    pub methods: Vec<MethodSignatureNode>, // メソッドのシグネチャ
+}
+
+/// ## メソッドシグネチャノード
+///
+/// メソッドシグネチャノードは、interfaceで宣言されたメソッドの名前、パラメータ、戻り値の型を表すノードです。
+/// パラメータには、先頭の `self` を含みません。
+#[derive(Debug, Clone, PartialEq)]
+pub struct MethodSignatureNode {
+    pub name: String,               // メソッド名
+    pub params: Vec<ParameterNode>, // selfを除くパラメータのリスト
+    pub return_type: Type,          // 戻り値の型
+}
+
+/// ## implノード
+///
+/// implノードは、型にメソッドを実装するimpl文を表すノードです。
+/// 例えば、下記の場合、型は `Host`、メソッドは `url` になります。
+///
+/// ```shot
+/// newtype Host = string;
+/// impl Host {
+///     let url: fn = (self): string { return "https://" + (self as string); };
+/// }
+/// let h: Host = "example.com" as Host;
+/// h.url();  # "https://example.com"
+/// ```
+///
+/// メソッドは、先頭のパラメータ `self` で呼び出し元の値を受け取る関数です。
+/// `self` の型は、implの対象の型になります。
+/// メソッドの本体は、グローバルに宣言された関数と同じく、パラメータと本体で宣言された変数と関数のみ参照できます。
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImplNode {
+    pub target: Type,                              // メソッドを実装する型
+    pub methods: Vec<Rc<FunctionDeclarationNode>>, // メソッドのリスト
+}
+
+/// ## importノード
+///
+/// importノードは、他のファイルのモジュールを読み込むimport文を表すノードです。
+/// 例えば、`import "lib/strings.shot" as strings;` の場合、
+/// パスは `lib/strings.shot`、別名は `strings` になります。
+///
+/// パスは、import文を書いたファイルのディレクトリからの相対パスです。
+/// モジュールで `export` が付けられた宣言は、`strings.pad` のように別名で修飾して参照します。
+///
+/// ```shot
+/// import "lib/strings.shot" as strings;
+/// let padded: string = strings.pad(value: "a");
+/// ```
+///
+/// 同じモジュールを複数回importしても、モジュールは一度だけ評価されます。
+/// モジュール同士が循環してimportしている場合はエラーとなります。
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportNode {
+    pub path: String,  // モジュールのファイルパス
+    pub alias: String, // モジュールの別名
 }
 
 /// ## 変数宣言ノード
@@ -54,8 +189,11 @@ pub enum Statement {
 /// 例えば、`let a: int = 1;` のような変数宣言が VariableDeclarationNode に含まれます。
 ///
 /// 全ての変数宣言はletで始まります。
-/// 例えば、`const a: int = 1;` のようなconstで始まる変数宣言はエラーとなります。
-/// また、letがない場合は、変数として認識されません。
+/// letがない場合は、変数として認識されません。
+///
+/// `const MAX_RETRIES: int = 5;` のようにconstで始まる宣言は定数宣言となり、同じノードで表されます。
+/// 定数の初期化式は定数式 (リテラル、四則演算、型キャスト、他の定数の参照) でなければならず、
+/// 評価の前に値へ畳み込まれます。
 ///
 /// 変数宣言ノードは、変数名、型、初期化式を持ちます。
 /// 例えば、`let a: int = 1;` の場合、変数名は `a`、型は `int`、初期化式は `1` になります。
@@ -73,7 +211,7 @@ pub struct VariableDeclarationNode {
 ///
 /// 関数宣言ノードは、関数名、パラメータリスト、戻り値の型、関数の本体を持ちます。
 /// 例えば、`let add: fn = (a: int, b: int): int { return a + b; };` の場合、
-/// 関数名は `add`、パラメータリストは `[(a, int), (b, int)]`、戻り値の型は `int`、関数の本体は `return a + b;` になります。
+/// 関数名は `add`、パラメータリストは `[a: int, b: int]`、戻り値の型は `int`、関数の本体は `return a + b;` になります。
 /// また、 `let hello: fn = (): void { print("Hello, World!"); return none };` の場合、
 /// 関数名は `hello`、パラメータリストは `[]`、戻り値の型は `void`、関数の本体は `print("Hello, World!");` になります。
 ///
@@ -94,13 +232,66 @@ pub struct VariableDeclarationNode {
 /// let add: fn = (a: int, b: int): int {
 ///   a + b;
 /// };
+/// ```
 ///
+/// 関数宣言ノードは、宣言された後も関数のMapperや関数の値から参照されるため、`Rc` で共有します。
+/// 関数を呼び出すたびに本体を複製せず、共有したノードを借用して評価します。
+/// `Rc` で共有するのは関数宣言ノードだけで、他のノードはASTが所有します。
+///
+/// 本体の文は、ASTと同じく行番号とともに保持します。
+/// 実行時のエラーは、関数の本体で評価中の文の行番号で報告されます。
 #[derive(Debug, Clone, PartialEq)]
 pub struct FunctionDeclarationNode {
-    pub name: String,                // 関数名
-    pub params: Vec<(String, Type)>, // パラメータのリスト (名前と型)
-    pub return_type: Type,           // 戻り値の型
-    pub body: Vec<Statement>,        // 関数の本体 (ステートメントのリスト)
+    pub name: String,                  // 関数名
+    pub params: Vec<ParameterNode>,    // パラメータのリスト
+    pub return_type: Type,             // 戻り値の型
+    pub body: Vec<(usize, Statement)>, // 関数の本体 ((行番号, 文) のリスト)
+}
+
+/// ## パラメータノード
+///
+/// パラメータノードは、関数宣言のパラメータを表すノードです。
+///
+/// パラメータノードは、パラメータ名、型、デフォルト値を持ちます。
+/// 例えば、`(retries: int = 3, verbose: bool = false)` の場合、
+/// `retries` の型は `int` でデフォルト値は `3`、`verbose` の型は `bool` でデフォルト値は `false` になります。
+///
+/// デフォルト値を持つパラメータは、関数呼び出し時に引数を省略できます。
+/// 例えば、下記のようなコードは正しいコードです。
+///
+/// ```shot
+/// let retry: fn = (command: string, retries: int = 3): void { return none; };
+/// retry(command: "ls");
+/// retry(retries: 5, command: "ls");
+/// ```
+///
+/// デフォルト値は、関数が呼び出される度に評価されます。
+///
+/// パラメータ名の前に `...` をつけると、可変長パラメータになります。
+/// 可変長パラメータは最後のパラメータにのみ指定でき、デフォルト値を持つことはできません。
+/// 例えば、`(prefix: string, ...parts: string)` の場合、
+/// `parts` に渡された引数は `[string]` 型のリストにまとめられます。
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParameterNode {
+    pub name: String,                               // パラメータ名
+    pub param_type: Type,                           // 型 (可変長パラメータの場合は要素の型)
+    pub default_value: Option<Box<ExpressionNode>>, // デフォルト値 (省略可能)
+    pub is_variadic: bool,                          // 可変長パラメータか
+}
+
+/// ## 関数の値
+///
+/// 関数の値は、部分適用で作られる関数を表します。
+/// 例えば、`add(x: 1, ...)` の値は、`add` の宣言と束縛された引数 `x: int = 1` を持ちます。
+///
+/// 元の関数の宣言は複製せず、`Rc` で共有します。
+/// 束縛された引数は、関数の本体の前に変数として宣言されるプロローグとして保持します。
+/// 関数の値をさらに部分適用した場合も、元の関数の宣言を共有し、プロローグに引数を加えます。
+#[derive(Debug, Clone, PartialEq)]
+pub struct FunctionValue {
+    pub declaration: Rc<FunctionDeclarationNode>, // 元の関数の宣言
+    pub params: Vec<ParameterNode>,               // 束縛されていないパラメータのリスト
+    pub prologue: Vec<(String, Type, LiteralValue)>, // 束縛された引数 ((名前, 型, 値) のリスト)
 }
 
 /// ## Returnノード
@@ -151,10 +342,13 @@ pub struct ReturnNode {
 ///
 /// ```shot
 /// 1 + 2;              // 1 + 2 の部分
-/// add(1, 2);          // add(1, 2) の部分
+/// add(x: 1, y: 2);    // add(x: 1, y: 2) の部分
 /// a;                  // a の部分
 /// 1;                  // 1 の部分
 /// 1 as int -> float;  // 1 as int -> float の部分
+/// x is int;           // x is int の部分
+/// if x is int { 1 } else { 2 };  // if から閉じ括弧までの部分
+/// h.url();            // h.url() の部分
 /// ```
 ///
 /// 注意点として、式は文の一部として使われることがあります。
@@ -163,9 +357,13 @@ pub struct ReturnNode {
 pub enum ExpressionNode {
     BinaryOperation(Box<BinaryOperationNode>), // 二項演算
     CallOfFunction(Box<FunctionCallNode>),     // 関数呼び出し
+    PartialApplication(Box<FunctionCallNode>), // 関数の部分適用
     CallOfVariable(Box<VariableCallNode>),     // 識別子
     Literal(Box<LiteralNode>),                 // リテラル
     TypeCast(Box<TypeCastNode>),               // 型キャスト
+    TypeTest(Box<TypeTestNode>),               // 型の検査
+    If(Box<IfNode>),                           // 条件分岐
+    CallOfMethod(Box<MethodCallNode>),         // メソッド呼び出し
 }
 
 /// ## 二項演算ノード
@@ -195,16 +393,15 @@ pub struct BinaryOperationNode {
 /// 関数呼び出しノードは、関数の呼び出しを表すノードです。
 ///
 /// 関数呼び出しノードは、関数名、引数リストを持ちます。
-/// 例えば、`add(1, 2)` の場合、関数名は `add`、引数リストは `[1, 2]` になります。
+/// 例えば、`add(x: 1, y: 2)` の場合、関数名は `add`、引数リストは `[x: 1, y: 2]` になります。
 ///
 /// 引数リストは、関数に渡す引数のリストです。
-/// 例えば、`add(1, 2)` の場合、引数リストは `[1, 2]` になります。
-/// 引数リストは、式のリストです。`add(1 + 2, 3 * 4)` の場合、引数リストは `[1 + 2, 3 * 4]` になります。
+/// 引数リストは、式のリストです。`add(x: 1 + 2, y: 3 * 4)` の場合、引数リストは `[x: 1 + 2, y: 3 * 4]` になります。
 /// つまり、下記のようなコードは正しいコードです。
 ///
 /// ```shot
-/// add(1, 2);
-/// add((1 + 2) as int -> float, 3 * 4);
+/// add(x: 1, y: 2);
+/// add(x: (1 + 2) as int -> float, y: 3 * 4);
 /// ```
 ///
 /// また、引数が無い場合、引数リストは空リストになります。
@@ -213,12 +410,68 @@ pub struct BinaryOperationNode {
 /// 一方で、下記のようなコードはエラーとなります。
 ///
 /// ```shot
-/// add(1, let n: int = 2;);  // let n: int = 2; はStatementであり、式ではないためエラー
+/// add(x: 1, y: let n: int = 2;);  // let n: int = 2; はStatementであり、式ではないためエラー
+/// ```
+///
+/// 引数リストの最後に `...` を置くと、関数の部分適用となります。
+/// 部分適用は関数を呼び出さず、渡されなかったパラメータを受け取る新しい関数を返します。
+/// 部分適用も関数呼び出しノードで表し、`ExpressionNode::PartialApplication` に格納します。
+///
+/// ```shot
+/// let inc: fn = add(x: 1, ...);  // (y: int): int を受け取る関数
+/// inc(y: 2);                     // 3
 /// ```
 #[derive(Debug, Clone, PartialEq)]
 pub struct FunctionCallNode {
-    pub name: String,                             // 呼び出される関数名
-    pub arguments: Vec<(String, ExpressionNode)>, // 関数に渡す引数リスト
+    pub name: String,                 // 呼び出される関数名
+    pub arguments: Vec<ArgumentNode>, // 関数に渡す引数リスト
+}
+
+/// ## メソッド呼び出しノード
+///
+/// メソッド呼び出しノードは、値に対するメソッドの呼び出しを表すノードです。
+/// 例えば、`h.url(scheme: "https")` の場合、呼び出し元の式は `h`、メソッド名は `url`、
+/// 引数リストは `[scheme: "https"]` になります。
+///
+/// 呼び出し元の値は、メソッドの `self` パラメータに渡されます。
+/// どのメソッドを呼び出すかは、呼び出し元の値の型から実行時に決まります。
+///
+/// ```shot
+/// h.url();
+/// (name as Host).url();
+/// h.url().len();  # メソッド呼び出しは続けて書くことができる
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct MethodCallNode {
+    pub receiver: Box<ExpressionNode>, // 呼び出し元の式
+    pub name: String,                  // メソッド名
+    pub arguments: Vec<ArgumentNode>,  // selfを除く引数リスト
+}
+
+/// ## 引数ノード
+///
+/// 引数ノードは、関数呼び出しに渡す引数を表すノードです。
+///
+/// 引数ノードは、引数名、式、展開の有無を持ちます。
+/// 例えば、`f(x: 1)` の場合、引数名は `x`、式は `1` になります。
+///
+/// 引数名を省略した位置引数は、パラメータの宣言順に束縛されます。
+/// 位置引数は名前付き引数よりも前に置く必要があります。
+/// 例えば、`f(1, y: 2)` の場合、`1` は最初のパラメータに束縛され、引数名は `None` になります。
+///
+/// 可変長パラメータには、同じ名前の引数を複数回渡すことができます。
+/// また、`...` を式の前につけると、リストの要素を展開して渡すことができます。
+///
+/// ```shot
+/// join(prefix: "-", parts: "a", parts: "b");
+/// join(prefix: "-", parts: ...parts);
+/// join("-", "a", "b");
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArgumentNode {
+    pub name: Option<String>,  // 引数名 (位置引数の場合はNone)
+    pub value: ExpressionNode, // 引数の式
+    pub is_spread: bool,       // リストを展開して渡すか
 }
 
 /// ## 変数呼び出しノード
@@ -255,13 +508,65 @@ pub struct LiteralNode {
 /// 型キャストの構文は、`式 as 型1 -> 型2` です。
 /// 型キャストは式なので、値を返します。具体的には、キャスト先の型にキャストされた値を返します。
 /// 例えば、`1 as int -> float` の場合、`1` は `float` 型にキャストされた値 `1.0` を返します。
+///
+/// キャスト元の型を省略した `式 as 型` と書くこともできます。
+/// この場合、キャスト元の型は式の値から決まります。
+/// キャスト元の型を書いた場合は、式の値がその型であるかが実行時に検査されます。
+///
+/// `式 as? 型` は失敗しうるキャストです。
+/// `"a" as? int` のように値を変換できない場合、エラーではなく `none` を返します。
+/// そのため、`as?` の結果は `int?` のようなOptional型になります。
 #[derive(Debug, Clone, PartialEq)]
 pub struct TypeCastNode {
-    pub from_type: Type,                 // キャスト元の型
-    pub to_type: Type,                   // キャスト先の型
+    pub from_type: Option<Type>, // キャスト元の型 (省略した場合はNone)
+    pub to_type: Type,           // キャスト先の型
+    pub is_checked: bool,        // as? による失敗しうるキャストか
     pub expression: Box<ExpressionNode>, // キャスト対象の式
 }
 
+/// ## 型検査ノード
+///
+/// 型検査ノードは、式の値が特定の型であるかを実行時に検査するノードです。
+/// 例えば、`x is int` の場合、式は `x`、検査する型は `int` になります。
+///
+/// 型検査は式なので、値を返します。値が型に含まれる場合は `true`、含まれない場合は `false` を返します。
+/// 検査する型には、`int | string` のようなUnion型や `any` 型も書くことができます。
+///
+/// ```shot
+/// let value: int | string = "shot";
+/// value is string;         // true
+/// value is int | float;    // false
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeTestNode {
+    pub expression: Box<ExpressionNode>, // 検査対象の式
+    pub tested_type: Type,               // 検査する型
+}
+
+/// ## 条件分岐ノード
+///
+/// 条件分岐ノードは、条件によって評価する式を選ぶノードです。
+/// 例えば、`if x is int { x + 1 } else { 0 }` の場合、
+/// 条件は `x is int`、条件が真の場合の式は `x + 1`、偽の場合の式は `0` になります。
+///
+/// 条件分岐は式なので、選ばれた分岐の値を返します。そのため、`else` は省略できません。
+/// 条件は `bool` 型でなければならず、それ以外の値の場合はエラーとなります。
+///
+/// `else if` と書くことで、条件分岐を続けることができます。
+///
+/// ```shot
+/// let label: string = if n is int { "int" } else if n is float { "float" } else { "other" };
+/// ```
+///
+/// 型推論を有効にした場合、条件が `変数 is 型` であれば、
+/// 真の分岐ではその変数を検査した型として、偽の分岐では残りの型として扱います (型の絞り込み)。
+#[derive(Debug, Clone, PartialEq)]
+pub struct IfNode {
+    pub condition: Box<ExpressionNode>,   // 条件式
+    pub then_branch: Box<ExpressionNode>, // 条件が真の場合に評価する式
+    pub else_branch: Box<ExpressionNode>, // 条件が偽の場合に評価する式
+}
+
 // ---------------------------------------------------------------------
 // Operators
 // ---------------------------------------------------------------------
@@ -287,27 +592,85 @@ pub enum BinaryOperator {
     Divide,   // 除算
 }
 
+impl fmt::Display for BinaryOperator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let operator_str: &str = match self {
+            BinaryOperator::Add => "+",
+            BinaryOperator::Subtract => "-",
+            BinaryOperator::Multiply => "*",
+            BinaryOperator::Divide => "/",
+        };
+        write!(f, "{}", operator_str)
+    }
+}
+
 // ---------------------------------------------------------------------
 // Types and Literals
 // ---------------------------------------------------------------------
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum Type {
-    Integer,  // 整数型
-    Float,    // 浮動小数点型
-    String,   // 文字列型
-    Void,     // Void 型 (戻り値がない)
-    Function, // 関数型
+    Integer,                                     // 整数型
+    Float,                                       // 浮動小数点型
+    String,                                      // 文字列型
+    Boolean,                                     // 真偽値型
+    Void,                                        // Void 型 (戻り値がない)
+    Function,                                    // 関数型
+    List(Box<Type>),                             // リスト型 (可変長パラメータ)
+    Optional(Box<Type>),                         // Optional型 (値またはnone)
+    Union(Vec<Type>),                            // Union型 (いずれかの型の値)
+    Newtype(String, Box<Type>),                  // newtypeで宣言された型 (型名, 元の型)
+    Interface(String, Vec<MethodSignatureNode>), // interfaceで宣言された型 (型名, メソッド)
+    Any,                                         // 全ての値を含む型
+    Infer,                                       // 型推論で決まる型 (型推論の前のみ現れる)
+}
+
+impl Type {
+    /// 型のリストからUnion型を作る
+    ///
+    /// 入れ子のUnion型は平坦にし、重複した型は取り除きます。
+    /// 型が1つだけになった場合は、その型をそのまま返します。
+    pub fn union(types: Vec<Type>) -> Type {
+        let mut members: Vec<Type> = vec![];
+        for member in types {
+            let flattened: Vec<Type> = match member {
+                Type::Union(inner) => inner,
+                member => vec![member],
+            };
+            for member in flattened {
+                if !members.contains(&member) {
+                    members.push(member);
+                }
+            }
+        }
+        if members.len() == 1 {
+            members.remove(0)
+        } else {
+            Type::Union(members)
+        }
+    }
 }
 
 impl fmt::Display for Type {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let type_str = match self {
-            Type::Integer => "int",
-            Type::Float => "float",
-            Type::String => "string",
-            Type::Void => "void",
-            Type::Function => "fn",
+        let type_str: String = match self {
+            Type::Integer => "int".to_string(),
+            Type::Float => "float".to_string(),
+            Type::String => "string".to_string(),
+            Type::Boolean => "bool".to_string(),
+            Type::Void => "void".to_string(),
+            Type::Function => "fn".to_string(),
+            Type::List(element_type) => format!("[{}]", element_type),
+            Type::Optional(inner_type) => format!("{}?", inner_type),
+            Type::Union(member_types) => member_types
+                .iter()
+                .map(|member_type| member_type.to_string())
+                .collect::<Vec<String>>()
+                .join(" | "),
+            Type::Any => "any".to_string(),
+            Type::Newtype(name, _) => name.clone(),
+            Type::Interface(name, _) => name.clone(),
+            Type::Infer => "infer".to_string(),
         };
         write!(f, "{}", type_str)
     }
@@ -316,10 +679,14 @@ impl fmt::Display for Type {
 // リテラルの値の種類
 #[derive(Debug, Clone, PartialEq)]
 pub enum LiteralValue {
-    Integer(i64),   // 整数リテラル
-    Float(f64),     // 浮動小数点リテラル
-    String(String), // 文字列リテラル
-    None,           // Noneリテラル
+    Integer(i64),                       // 整数リテラル
+    Float(f64),                         // 浮動小数点リテラル
+    String(String),                     // 文字列リテラル
+    Boolean(bool),                      // 真偽値リテラル
+    None,                               // Noneリテラル
+    List(Vec<LiteralValue>),            // リスト (可変長パラメータに渡された値)
+    Function(Rc<FunctionValue>),        // 関数 (部分適用で作られた関数)
+    Newtype(String, Box<LiteralValue>), // newtypeの値 (型名, 元の型の値)
 }
 
 impl fmt::Display for LiteralValue {
@@ -328,7 +695,18 @@ impl fmt::Display for LiteralValue {
             LiteralValue::Integer(value) => value.to_string(),
             LiteralValue::Float(value) => value.to_string(),
             LiteralValue::String(value) => value.clone(),
+            LiteralValue::Boolean(value) => value.to_string(),
             LiteralValue::None => "none".to_string(),
+            LiteralValue::List(values) => format!(
+                "[{}]",
+                values
+                    .iter()
+                    .map(|value| value.to_string())
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ),
+            LiteralValue::Function(value) => format!("<fn {}>", value.declaration.name),
+            LiteralValue::Newtype(name, value) => format!("{}({})", name, value),
         };
         write!(f, "{}", value)
     }
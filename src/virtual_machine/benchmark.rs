@@ -0,0 +1,54 @@
+use crate::virtual_machine::ast::{LiteralValue, AST};
+use crate::virtual_machine::bytecode::{compile, Chunk, StackMachine};
+use crate::virtual_machine::evaluator::mapper::function_mapper::FunctionMapper;
+use crate::virtual_machine::evaluator::mapper::variable_mapper::VariableMapper;
+use crate::virtual_machine::evaluator::Evaluator;
+use crate::virtual_machine::parser::Parser;
+use crate::virtual_machine::scanner::Scanner;
+
+/// ベンチマークのために、解析とコンパイルを済ませたプログラム
+///
+/// プロセスの起動、解析、コンパイルを含めずに、評価だけの時間を計るために用います。
+/// スタックマシンは変数と関数をスロットで参照し、Evaluatorは `VariableMapper` と `FunctionMapper` から名前で探します。
+pub struct Program {
+    ast: AST,
+    chunk: Chunk,
+}
+
+impl Program {
+    /// ソースコードを解析し、バイトコードにコンパイルする
+    ///
+    /// ## Panics
+    ///
+    /// 字句解析または構文解析に失敗した場合
+    pub fn compile(source_code: &str) -> Self {
+        let source_code_vector: Vec<String> =
+            source_code.lines().map(|line| line.to_string()).collect();
+        let tokens = Scanner::new(source_code.to_string(), source_code_vector)
+            .scan()
+            .expect("benchmark program must be scanned");
+        let ast: AST = Parser::new(tokens)
+            .parse()
+            .expect("benchmark program must be parsed");
+        let chunk: Chunk = compile(&ast);
+        Program { ast, chunk }
+    }
+
+    /// スタックマシンで実行し、プログラムの結果を返す
+    pub fn run_on_stack_machine(&self) -> LiteralValue {
+        StackMachine::new()
+            .run(&self.chunk)
+            .expect("benchmark program must run on the stack machine")
+    }
+
+    /// Evaluatorで評価し、プログラムの結果を返す
+    pub fn run_on_evaluator(&self) -> LiteralValue {
+        Evaluator::new(
+            self.ast.clone(),
+            FunctionMapper::new(),
+            VariableMapper::new(),
+        )
+        .evaluate()
+        .expect("benchmark program must run on the evaluator")
+    }
+}
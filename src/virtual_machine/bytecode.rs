@@ -0,0 +1,325 @@
+mod compiler;
+mod instruction;
+mod stack_machine;
+
+pub(crate) use compiler::compile;
+pub(crate) use instruction::Chunk;
+pub(crate) use stack_machine::StackMachine;
+
+#[cfg(test)]
+mod tests {
+    use crate::virtual_machine::ast::{BinaryOperator, LiteralValue, AST};
+    use crate::virtual_machine::bytecode::instruction::{Instruction, Slot};
+    use crate::virtual_machine::bytecode::{compile, Chunk, StackMachine};
+    use crate::virtual_machine::evaluator::call_stack::Traceback;
+    use crate::virtual_machine::evaluator::core::parse_source;
+    use crate::virtual_machine::evaluator::evaluation_error::EvaluationError;
+    use crate::virtual_machine::evaluator::mapper::function_mapper::FunctionMapper;
+    use crate::virtual_machine::evaluator::mapper::variable_mapper::VariableMapper;
+    use crate::virtual_machine::evaluator::Evaluator;
+
+    /// EvaluatorとStackMachineで同じ結果になることを確認し、StackMachineの結果を返す
+    fn run_both(source_code: &str) -> Result<LiteralValue, EvaluationError> {
+        run_both_with_traceback(source_code).0
+    }
+
+    /// EvaluatorとStackMachineで同じ結果と呼び出しの履歴になることを確認し、StackMachineのものを返す
+    fn run_both_with_traceback(
+        source_code: &str,
+    ) -> (Result<LiteralValue, EvaluationError>, Traceback) {
+        let ast: AST = parse_source(source_code);
+        let mut evaluator: Evaluator =
+            Evaluator::new(ast.clone(), FunctionMapper::new(), VariableMapper::new());
+        let expected: Result<LiteralValue, EvaluationError> = evaluator.evaluate();
+        let chunk: Chunk = compile(&ast);
+        let mut stack_machine: StackMachine = StackMachine::new();
+        let actual: Result<LiteralValue, EvaluationError> = stack_machine.run(&chunk);
+        assert_eq!(actual, expected, "source code:{}", source_code);
+        assert_eq!(
+            stack_machine.traceback(),
+            evaluator.traceback(),
+            "source code:{}",
+            source_code
+        );
+        (actual, stack_machine.traceback())
+    }
+
+    /// 式文はスタックに積んだ値を捨てる命令にコンパイルされることを確認するテスト
+    ///
+    /// 1 + 2;
+    #[test]
+    fn test_compile_expression_statement() {
+        let chunk: Chunk = compile(&parse_source("1 + 2;"));
+        assert_eq!(
+            chunk.instructions,
+            vec![
+                Instruction::SetLine(1),
+                Instruction::Constant(0),
+                Instruction::Constant(1),
+                Instruction::BinaryOperation(BinaryOperator::Add),
+                Instruction::Pop,
+            ]
+        );
+        assert_eq!(
+            chunk.constants,
+            vec![LiteralValue::Integer(1), LiteralValue::Integer(2)]
+        );
+    }
+
+    /// 変数がスコープに応じたスロットにコンパイルされることを確認するテスト
+    ///
+    /// ブロックの変数もフレームのスロットに置かれ、同じスコープで宣言済みの変数にはスロットが割り当てられません。
+    #[test]
+    fn test_compile_variables_to_slots() {
+        let chunk: Chunk = compile(&parse_source(
+            "let a: int = 1;\n{ let b: int = a; }\nlet a: int = 2;\nlet c: int = d;",
+        ));
+        assert!(chunk
+            .instructions
+            .contains(&Instruction::LoadVariable(Slot { hops: 0, index: 0 })));
+        assert!(chunk.instructions.contains(&Instruction::LoadUndefined(0)));
+        assert_eq!(chunk.names, vec!["d".to_string()]);
+        assert_eq!(
+            chunk
+                .declarations
+                .iter()
+                .map(|declaration| declaration.slot)
+                .collect::<Vec<Option<usize>>>(),
+            vec![Some(0), Some(1), None, Some(2)]
+        );
+        assert_eq!(chunk.variable_slots, 3);
+    }
+
+    /// 外側の関数の変数が、たどるフレームの数とスロットにコンパイルされることを確認するテスト
+    #[test]
+    fn test_compile_enclosing_variables_to_slots() {
+        let chunk: Chunk = compile(&parse_source(
+            r#"
+let f: fn = (x: int): int {
+    let g: fn = (y: int): int { return x + y; };
+    return g(y: 1);
+};
+"#,
+        ));
+        let g: &Chunk = &chunk.functions[0].function.body.functions[0].function.body;
+        assert_eq!(
+            g.instructions[..3],
+            [
+                Instruction::SetLine(3),
+                Instruction::LoadVariable(Slot { hops: 1, index: 0 }),
+                Instruction::LoadVariable(Slot { hops: 0, index: 0 }),
+            ]
+        );
+    }
+
+    /// 変数、ブロック、条件分岐、型キャスト、型の検査の結果がEvaluatorと一致することを確認するテスト
+    #[test]
+    fn test_run_expressions_same_as_evaluator() {
+        let source_code: &str = r#"
+let a: int = (1 + 2) * 3;
+const b: float = 0.5;
+{
+    let a: string = "inner";
+}
+let c: float = a as int -> float + b;
+let d: int | string = "x";
+let e: string = if d is int { "int" } else if d is string { "string" } else { "other" };
+let f: int? = "x" as? int;
+return e + (a as string) + (c as string);
+"#;
+        assert_eq!(
+            run_both(source_code),
+            Ok(LiteralValue::String("string99.5".to_string()))
+        );
+    }
+
+    /// 関数呼び出し、オーバーロード、入れ子の関数、部分適用の結果がEvaluatorと一致することを確認するテスト
+    #[test]
+    fn test_run_functions_same_as_evaluator() {
+        let source_code: &str = r#"
+let add: fn = (x: int, y: int = 10): int { return x + y; };
+let sum: fn = (...xs: int): int { return 0; };
+let open: fn = (path: string): int { return 0; };
+let open: fn = (fd: int): int { return fd; };
+let outer: fn = (x: int): int {
+    let offset: int = 100;
+    let inner: fn = (y: int): int { return x + y + offset; };
+    return inner(y: 1);
+};
+let inc: fn = add(y: 1, ...);
+let a: int = add(1, 2) + add(x: 1) + sum(xs: 1, xs: 2) + open(fd: 3) + open(path: "a");
+return a + outer(x: 1000) + inc(x: 5);
+"#;
+        assert_eq!(run_both(source_code), Ok(LiteralValue::Integer(1124)));
+    }
+
+    /// 再帰呼び出し、外側のフレームの参照、デフォルト値のスコープの結果がEvaluatorと一致することを確認するテスト
+    ///
+    /// `k` が0の場合だけ、19桁の0を連結した文字列を整数にキャストできます。
+    #[test]
+    fn test_run_scopes_same_as_evaluator() {
+        let source_code: &str = r#"
+let base: int = 1000;
+let top: fn = (x: int = base): int { return x; };
+let sum_to: fn = (n: int): int {
+    let step: int = 1;
+    let count: fn = (k: int, acc: int): int {
+        let is_zero: bool = ((k as string) + "0000000000000000000") as? int is int;
+        return if is_zero { acc } else { count(k: k - step, acc: acc + k) };
+    };
+    let offset: fn = (y: int = step * 10): int {
+        let inner: fn = (): int { return n + y; };
+        return inner();
+    };
+    let shifted: fn = offset(...);
+    let probe: fn = (): int {
+        let step: int = 100;
+        return shifted() + step;
+    };
+    return count(k: n, acc: 0) + probe();
+};
+return sum_to(n: 20) + top();
+"#;
+        assert_eq!(run_both(source_code), Ok(LiteralValue::Integer(1340)));
+    }
+
+    /// メソッド呼び出しとinterface型のパラメータの結果がEvaluatorと一致することを確認するテスト
+    #[test]
+    fn test_run_methods_same_as_evaluator() {
+        let source_code: &str = r#"
+newtype Host = string;
+interface Addressable {
+    let url: fn = (self): string;
+}
+impl Host {
+    let url: fn = (self): string { return "https://" + (self as string); };
+    let with_path: fn = (self, path: string): string { return self.url() + path; };
+}
+let open: fn = (target: Addressable): string { return target.url(); };
+let h: Host = "example.com" as Host;
+return open(target: h) + h.with_path(path: "/docs");
+"#;
+        assert_eq!(
+            run_both(source_code),
+            Ok(LiteralValue::String(
+                "https://example.comhttps://example.com/docs".to_string()
+            ))
+        );
+    }
+
+    /// 実行時のエラーとその行番号がEvaluatorと一致することを確認するテスト
+    ///
+    /// 関数の本体で起きたエラーは、本体の文の行番号となります。
+    /// ただし、引数や戻り値の型の検査のエラーは、呼び出し元の行番号となります。
+    #[test]
+    fn test_run_errors_same_as_evaluator() {
+        let cases: Vec<(&str, EvaluationError)> = vec![
+            (
+                "let a: int = 1;\nlet b: int = a / 0;",
+                EvaluationError::DivisionByZero { line: 2 },
+            ),
+            (
+                "let f: fn = (x: int): int {\n    return x / 0;\n};\nf(x: 1);",
+                EvaluationError::DivisionByZero { line: 2 },
+            ),
+            (
+                "let a: int = 1;\nlet b: int = if a { 1 } else { 2 };",
+                EvaluationError::InvalidCondition {
+                    actual: "int".to_string(),
+                    line: 2,
+                },
+            ),
+            (
+                "let f: fn = (x: int): int { return x; };\nf(x: 1.5);",
+                EvaluationError::ParameterTypeMismatch {
+                    function_name: "f".to_string(),
+                    param_name: "x".to_string(),
+                    line: 2,
+                    expected: "int".to_string(),
+                    actual: "float".to_string(),
+                },
+            ),
+            (
+                "let f: fn = (): int { return \"a\"; };\nf();",
+                EvaluationError::ReturnTypeMismatch {
+                    function_name: "f".to_string(),
+                    line: 2,
+                    expected: "int".to_string(),
+                    actual: "string".to_string(),
+                },
+            ),
+            (
+                "let open: fn = (path: string): int { return 0; };\nlet open: fn = (fd: int): int { return fd; };\n{\n    open(mode: \"r\");\n}",
+                EvaluationError::NoMatchingOverload {
+                    name: "open".to_string(),
+                    line: 4,
+                },
+            ),
+            (
+                "let a: int = 1;\n{\n    let b: int = 2;\n}\nlet a: int = b;",
+                EvaluationError::VariableNotFound {
+                    name: "b".to_string(),
+                    line: 5,
+                },
+            ),
+            (
+                "let a: int = 1;\nlet a: int = 2;",
+                EvaluationError::ReassignmentError {
+                    name: "a".to_string(),
+                    line: 2,
+                },
+            ),
+            (
+                "let a: int = 1;\nlet f: fn = (): int { return a; };\nf();",
+                EvaluationError::VariableNotFound {
+                    name: "a".to_string(),
+                    line: 2,
+                },
+            ),
+        ];
+        for (source_code, expected) in cases {
+            assert_eq!(run_both(source_code), Err(expected));
+        }
+    }
+
+    /// 入れ子の関数の本体で起きたエラーの行番号と呼び出しの履歴がEvaluatorと一致することを確認するテスト
+    ///
+    /// エラーが起きる前に終えた呼び出し `outer(x: 5)` は、履歴に残りません。
+    #[test]
+    fn test_traceback_same_as_evaluator() {
+        let source_code: &str = r#"let outer: fn = (x: int): int {
+    let inner: fn = (y: int): int {
+        let z: int = y - 1;
+        return 10 / z;
+    };
+    let w: int = x;
+    return inner(y: w);
+};
+let ok: int = outer(x: 5);
+return outer(x: 1);
+"#;
+        let (result, traceback) = run_both_with_traceback(source_code);
+        assert_eq!(result, Err(EvaluationError::DivisionByZero { line: 4 }));
+        assert!(traceback.is_in_function());
+        assert_eq!(
+            traceback.to_string(),
+            [
+                "Traceback (most recent call last):",
+                "  line 10, in <main>",
+                "  line 7, in outer (called at line 10)",
+                "  line 4, in inner (called at line 7)",
+            ]
+            .join("\n")
+        );
+    }
+
+    /// トップレベルで起きたエラーは、関数の呼び出しの履歴を持たないことを確認するテスト
+    #[test]
+    fn test_traceback_at_top_level() {
+        let source_code: &str =
+            "let f: fn = (x: int): int { return x; };\nlet a: int = f(x: 1);\nlet b: int = a / 0;";
+        let (result, traceback) = run_both_with_traceback(source_code);
+        assert_eq!(result, Err(EvaluationError::DivisionByZero { line: 3 }));
+        assert!(!traceback.is_in_function());
+    }
+}
@@ -0,0 +1,392 @@
+use crate::virtual_machine::ast::{
+    ArgumentNode, ExpressionNode, FunctionDeclarationNode, LiteralValue, Statement, AST,
+};
+use crate::virtual_machine::bytecode::instruction::{
+    ArgumentShape, Call, Callee, Chunk, CompiledFunction, CompiledImpl, FunctionDeclaration,
+    Instruction, Slot, TypeCast, VariableDeclaration,
+};
+use std::collections::HashMap;
+use std::rc::Rc;
+
+/// ASTをプログラム全体のチャンクにコンパイルする
+///
+/// トップレベルの文の前には、その文の行番号を設定する命令を置きます。
+/// トップレベルのreturn文は、評価を終えてその値をプログラムの結果とします。
+/// グローバルな変数と関数は、一番外側のフレームのスロットに置かれます。
+/// グローバルな変数のスロットは、名前とともにチャンクに残ります。
+pub(crate) fn compile(ast: &AST) -> Chunk {
+    let mut compiler: Compiler = Compiler::new(Environment::default(), false);
+    for (line, statement) in ast.statements.iter() {
+        compiler.emit(Instruction::SetLine(*line));
+        compiler.compile_top_level_statement(statement);
+    }
+    // モジュールからexportされた変数の値を、名前で求められるようにする
+    compiler.chunk.global_slots = std::mem::take(&mut compiler.scopes[0].variables);
+    compiler.chunk
+}
+
+/// 関数の本体とデフォルト値の式をコンパイルする
+///
+/// 関数の本体の文の前には、トップレベルの文と同じく、その文の行番号を設定する命令を置きます。
+/// `body_environment` は本体から参照できる外側のフレームのスコープ、
+/// `default_environment` は関数が宣言された位置から見えるスコープです。
+pub(crate) fn compile_function(
+    node: &Rc<FunctionDeclarationNode>,
+    body_environment: &Environment,
+    default_environment: &Environment,
+) -> CompiledFunction {
+    // パラメータは、本体で宣言された変数と同じスコープに置く
+    let mut body: Compiler = Compiler::new(body_environment.clone(), true);
+    let param_slots: Vec<Option<usize>> = node
+        .params
+        .iter()
+        .map(|param| body.declare_variable(&param.name))
+        .collect();
+    for (line, statement) in node.body.iter() {
+        body.emit(Instruction::SetLine(*line));
+        body.compile_top_level_statement(statement);
+    }
+
+    let default_values: Vec<Option<Chunk>> = node
+        .params
+        .iter()
+        .map(|param| {
+            param.default_value.as_ref().map(|default_value| {
+                let mut compiler: Compiler = Compiler::in_frame_of(default_environment);
+                compiler.compile_expression(default_value);
+                compiler.emit(Instruction::Return);
+                compiler.chunk
+            })
+        })
+        .collect();
+
+    CompiledFunction {
+        declaration: Rc::clone(node),
+        body: body.chunk,
+        param_slots,
+        default_values,
+    }
+}
+
+/// コンパイル時のスコープで宣言された変数と関数のスロット
+#[derive(Debug, Clone, Default, PartialEq)]
+struct Scope {
+    variables: HashMap<String, usize>,
+    functions: HashMap<String, usize>,
+}
+
+/// ある位置から見える、フレームごとのスコープ
+///
+/// 外側のフレームから順に並び、最後がその位置のフレームです。
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct Environment {
+    frames: Vec<Vec<Scope>>,
+    is_function_body: bool, // その位置が関数の本体の中か
+}
+
+struct Compiler {
+    chunk: Chunk,
+    enclosing: Environment, // 外側のフレームのスコープ
+    scopes: Vec<Scope>,     // コンパイル中のフレームのスコープ (ブロックごとに積まれる)
+    is_function_body: bool,
+}
+
+impl Compiler {
+    fn new(enclosing: Environment, is_function_body: bool) -> Self {
+        Compiler {
+            chunk: Chunk::default(),
+            enclosing,
+            scopes: vec![Scope::default()],
+            is_function_body,
+        }
+    }
+
+    /// 指定した位置のフレームで実行される式のコンパイラを作成する
+    ///
+    /// デフォルト値の式は、関数が宣言されたフレームで実行されます。
+    fn in_frame_of(environment: &Environment) -> Self {
+        let mut enclosing: Environment = environment.clone();
+        let scopes: Vec<Scope> = enclosing
+            .frames
+            .pop()
+            .unwrap_or_else(|| vec![Scope::default()]);
+        Compiler {
+            chunk: Chunk::default(),
+            enclosing,
+            scopes,
+            is_function_body: environment.is_function_body,
+        }
+    }
+
+    fn emit(&mut self, instruction: Instruction) -> usize {
+        self.chunk.instructions.push(instruction);
+        self.chunk.instructions.len() - 1
+    }
+
+    /// チャンクのトップレベルの文をコンパイルする
+    ///
+    /// return文はトップレベルでのみ評価でき、ブロックの中では `Unexpected` となります。
+    fn compile_top_level_statement(&mut self, statement: &Statement) {
+        match statement {
+            Statement::Return(expression) => {
+                self.compile_expression(expression);
+                self.emit(Instruction::Return);
+            }
+            statement => self.compile_statement(statement),
+        }
+    }
+
+    fn compile_statement(&mut self, statement: &Statement) {
+        match statement {
+            Statement::Expression(expression) => {
+                self.compile_expression(expression);
+                self.emit(Instruction::Pop);
+            }
+            Statement::DeclarationOfFunction(node) => {
+                // 再帰呼び出しのため、本体より先に関数名を宣言する
+                let slot: usize = self.declare_function(&node.name);
+                let default_environment: Environment = self.environment(self.scopes.len());
+                // グローバルに宣言された関数の本体は、外側のスコープを引き継がない
+                let body_environment: Environment = match self.is_function_body {
+                    true => default_environment.clone(),
+                    false => Environment::default(),
+                };
+                let function: CompiledFunction =
+                    compile_function(node, &body_environment, &default_environment);
+                self.chunk.functions.push(FunctionDeclaration {
+                    slot,
+                    function: Rc::new(function),
+                });
+                self.emit(Instruction::DeclareFunction(self.chunk.functions.len() - 1));
+            }
+            Statement::DeclarationOfVariable(node) | Statement::DeclarationOfConstant(node) => {
+                // 初期化式は、宣言する変数を含まないスコープで評価する
+                self.compile_expression(&node.value);
+                let slot: Option<usize> = self.declare_variable(&node.name);
+                self.chunk.declarations.push(VariableDeclaration {
+                    name: node.name.clone(),
+                    var_type: node.var_type.clone(),
+                    is_constant: matches!(statement, Statement::DeclarationOfConstant(_)),
+                    slot,
+                });
+                self.emit(Instruction::DeclareVariable(
+                    self.chunk.declarations.len() - 1,
+                ));
+            }
+            // exportはモジュールの外から参照するための印なので、中の宣言をそのままコンパイルする
+            Statement::Export(declaration) => self.compile_statement(declaration),
+            // newtypeとinterfaceの型名は構文解析で解決済み
+            Statement::DeclarationOfNewtype(_) | Statement::DeclarationOfInterface(_) => {}
+            // メソッドの本体とデフォルト値は、外側のスコープを引き継がない
+            Statement::Impl(node) => {
+                self.chunk.impls.push(CompiledImpl {
+                    type_name: node.target.to_string(),
+                    methods: node
+                        .methods
+                        .iter()
+                        .map(|method| {
+                            Rc::new(compile_function(
+                                method,
+                                &Environment::default(),
+                                &Environment::default(),
+                            ))
+                        })
+                        .collect(),
+                });
+                self.emit(Instruction::Impl(self.chunk.impls.len() - 1));
+            }
+            Statement::Block(block) => {
+                self.emit(Instruction::EnterBlock);
+                self.scopes.push(Scope::default());
+                for (line, statement) in block.statements.iter() {
+                    self.emit(Instruction::SetLine(*line));
+                    self.compile_statement(statement);
+                }
+                self.scopes.pop();
+                self.emit(Instruction::ExitBlock);
+            }
+            Statement::Return(_) | Statement::Import(_) => {
+                self.emit(Instruction::Unexpected);
+            }
+        }
+    }
+
+    fn compile_expression(&mut self, expression: &ExpressionNode) {
+        match expression {
+            ExpressionNode::Literal(node) => self.emit_constant(node.value.clone()),
+            // 左辺、右辺の順に評価する
+            ExpressionNode::BinaryOperation(node) => {
+                self.compile_expression(&node.left);
+                self.compile_expression(&node.right);
+                self.emit(Instruction::BinaryOperation(node.operator.clone()));
+            }
+            ExpressionNode::CallOfFunction(node) => {
+                let call: usize = self.compile_call(&node.name, &node.arguments, true);
+                self.emit(Instruction::Call(call));
+            }
+            ExpressionNode::PartialApplication(node) => {
+                let call: usize = self.compile_call(&node.name, &node.arguments, true);
+                self.emit(Instruction::PartialApplication(call));
+            }
+            ExpressionNode::CallOfVariable(node) => {
+                match self.resolve(&node.name, |scope| &scope.variables) {
+                    Some((slot, _)) => self.emit(Instruction::LoadVariable(slot)),
+                    None => {
+                        self.chunk.names.push(node.name.clone());
+                        self.emit(Instruction::LoadUndefined(self.chunk.names.len() - 1))
+                    }
+                };
+            }
+            ExpressionNode::TypeCast(node) => {
+                self.compile_expression(&node.expression);
+                self.chunk.type_casts.push(TypeCast {
+                    from_type: node.from_type.clone(),
+                    to_type: node.to_type.clone(),
+                    is_checked: node.is_checked,
+                });
+                self.emit(Instruction::TypeCast(self.chunk.type_casts.len() - 1));
+            }
+            ExpressionNode::TypeTest(node) => {
+                self.compile_expression(&node.expression);
+                self.chunk.types.push(node.tested_type.clone());
+                self.emit(Instruction::TypeTest(self.chunk.types.len() - 1));
+            }
+            // 選ばれなかった分岐は評価しない
+            ExpressionNode::If(node) => {
+                self.compile_expression(&node.condition);
+                let jump_to_else: usize = self.emit(Instruction::JumpIfFalse(0));
+                self.compile_expression(&node.then_branch);
+                let jump_to_end: usize = self.emit(Instruction::Jump(0));
+                self.chunk.instructions[jump_to_else] =
+                    Instruction::JumpIfFalse(self.chunk.instructions.len());
+                self.compile_expression(&node.else_branch);
+                self.chunk.instructions[jump_to_end] =
+                    Instruction::Jump(self.chunk.instructions.len());
+            }
+            // 呼び出し元の値、引数の順に評価する
+            ExpressionNode::CallOfMethod(node) => {
+                self.compile_expression(&node.receiver);
+                let call: usize = self.compile_call(&node.name, &node.arguments, false);
+                self.emit(Instruction::CallMethod(call));
+            }
+        }
+    }
+
+    /// 引数を左から順に評価する命令を置き、呼び出しの番号を返す
+    ///
+    /// 関数の呼び出しでは、呼び出される関数をスロットに解決します。
+    fn compile_call(&mut self, name: &str, arguments: &[ArgumentNode], is_function: bool) -> usize {
+        for argument in arguments.iter() {
+            self.compile_expression(&argument.value);
+        }
+        let callee: Callee = match is_function {
+            true => self.resolve_callee(name),
+            false => Callee::Undefined,
+        };
+        self.chunk.calls.push(Call {
+            name: name.to_string(),
+            arguments: arguments
+                .iter()
+                .map(|argument| ArgumentShape {
+                    name: argument.name.clone(),
+                    is_spread: argument.is_spread,
+                })
+                .collect(),
+            callee,
+        });
+        self.chunk.calls.len() - 1
+    }
+
+    /// 呼び出される関数を解決する
+    ///
+    /// 関数として宣言されていない場合は、関数を値に持つ変数を探します。
+    fn resolve_callee(&self, name: &str) -> Callee {
+        if let Some((slot, _)) = self.resolve(name, |scope| &scope.functions) {
+            return Callee::Function(slot);
+        }
+        match self.resolve(name, |scope| &scope.variables) {
+            Some((slot, depth)) => {
+                // 変数の値の関数は、変数が宣言された位置から見えるスコープを引き継ぐ
+                let mut default_environment: Environment = self.environment(self.scopes.len());
+                let frame: usize = default_environment.frames.len() - 1 - slot.hops;
+                default_environment.frames.truncate(frame + 1);
+                default_environment.frames[frame].truncate(depth);
+                let body_environment: Environment = match self.is_function_body {
+                    true => default_environment.clone(),
+                    false => Environment::default(),
+                };
+                Callee::Variable {
+                    slot,
+                    body_environment: Rc::new(body_environment),
+                    default_environment: Rc::new(default_environment),
+                }
+            }
+            None => Callee::Undefined,
+        }
+    }
+
+    /// 名前を内側のスコープから順に探し、スロットと名前が宣言されたスコープの深さを返す
+    fn resolve(
+        &self,
+        name: &str,
+        table: impl Fn(&Scope) -> &HashMap<String, usize>,
+    ) -> Option<(Slot, usize)> {
+        let frames = self
+            .enclosing
+            .frames
+            .iter()
+            .chain(std::iter::once(&self.scopes))
+            .rev();
+        for (hops, scopes) in frames.enumerate() {
+            if let Some(depth) = scopes
+                .iter()
+                .rposition(|scope| table(scope).contains_key(name))
+            {
+                let index: usize = table(&scopes[depth])[name];
+                return Some((Slot { hops, index }, depth + 1));
+            }
+        }
+        None
+    }
+
+    /// コンパイル中のフレームの、指定した深さまでのスコープから見える位置を返す
+    fn environment(&self, depth: usize) -> Environment {
+        let mut environment: Environment = self.enclosing.clone();
+        environment.frames.push(self.scopes[..depth].to_vec());
+        environment.is_function_body = self.is_function_body;
+        environment
+    }
+
+    /// 変数を一番内側のスコープに宣言し、スロットを返す
+    ///
+    /// 同じスコープで宣言済みの場合は、実行時に再代入のエラーとなるため `None` を返します。
+    fn declare_variable(&mut self, name: &str) -> Option<usize> {
+        let scope: &mut Scope = self.scopes.last_mut().expect("scope must exist");
+        if scope.variables.contains_key(name) {
+            return None;
+        }
+        scope
+            .variables
+            .insert(name.to_string(), self.chunk.variable_slots);
+        self.chunk.variable_slots += 1;
+        Some(self.chunk.variable_slots - 1)
+    }
+
+    /// 関数を一番内側のスコープに宣言し、オーバーロードを置くスロットを返す
+    fn declare_function(&mut self, name: &str) -> usize {
+        let scope: &mut Scope = self.scopes.last_mut().expect("scope must exist");
+        if let Some(slot) = scope.functions.get(name) {
+            return *slot;
+        }
+        scope
+            .functions
+            .insert(name.to_string(), self.chunk.function_slots);
+        self.chunk.function_slots += 1;
+        self.chunk.function_slots - 1
+    }
+
+    fn emit_constant(&mut self, value: LiteralValue) {
+        self.chunk.constants.push(value);
+        self.emit(Instruction::Constant(self.chunk.constants.len() - 1));
+    }
+}
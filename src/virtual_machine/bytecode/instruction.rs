@@ -0,0 +1,170 @@
+use crate::virtual_machine::ast::{BinaryOperator, FunctionDeclarationNode, LiteralValue, Type};
+use crate::virtual_machine::bytecode::compiler::Environment;
+use std::collections::HashMap;
+use std::fmt;
+use std::rc::Rc;
+
+/// スタックマシンの命令
+///
+/// 命令の引数は、`Chunk` の各テーブルの番号です。
+/// 式の値はスタックに積まれ、演算や呼び出しはスタックから値を取り出して結果を積みます。
+/// 変数はコンパイル時にフレームのスロットに解決され、実行時には名前で探しません。
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Instruction {
+    Constant(usize),                 // 定数をスタックに積む
+    LoadVariable(Slot),              // スロットの変数の値をスタックに積む
+    LoadUndefined(usize),            // 解決できなかった変数を参照する (VariableNotFound)
+    DeclareVariable(usize),          // スタックから取り出した値で変数を宣言する
+    DeclareFunction(usize),          // 関数を宣言する
+    Impl(usize),                     // メソッドを実装する
+    BinaryOperation(BinaryOperator), // 2つの値を取り出し、演算結果を積む
+    TypeCast(usize),                 // 値を取り出し、キャストした値を積む
+    TypeTest(usize),                 // 値を取り出し、型に当てはまるかを積む
+    Jump(usize),                     // 指定した命令に移る
+    JumpIfFalse(usize),              // 条件を取り出し、偽なら指定した命令に移る
+    Call(usize),                     // 引数を取り出して関数を呼び出し、戻り値を積む
+    CallMethod(usize),               // 呼び出し元の値と引数を取り出してメソッドを呼び出す
+    PartialApplication(usize),       // 引数を取り出し、部分適用した関数を積む
+    EnterBlock,                      // ブロックに入る前の行番号を積む
+    ExitBlock,                       // ブロックに入る前の行番号に戻す
+    SetLine(usize),                  // 評価中の行番号を設定する
+    Pop,                             // 式文の値を捨てる
+    Return,                          // 値を取り出し、チャンクの評価を終える
+    Unexpected,                      // 評価できない文 (ブロックの中のreturn文など)
+}
+
+/// 変数または関数のオーバーロードを置くフレームの位置
+///
+/// フレームは関数の呼び出しごとに作られ、プログラム全体は一番外側のフレームで実行されます。
+/// 関数の中で宣言された関数のフレームは、宣言したフレームを外側のフレームとして持ちます。
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Slot {
+    pub(crate) hops: usize,  // 外側のフレームをたどる回数 (実行中のフレームは0)
+    pub(crate) index: usize, // フレームの中の番号
+}
+
+/// コンパイルされた命令列と、命令から参照されるテーブル
+///
+/// プログラム全体、関数の本体、デフォルト値の式は、それぞれ1つのチャンクにコンパイルされます。
+#[derive(Debug, Default)]
+pub(crate) struct Chunk {
+    pub(crate) instructions: Vec<Instruction>,
+    pub(crate) constants: Vec<LiteralValue>,
+    pub(crate) names: Vec<String>,
+    pub(crate) types: Vec<Type>,
+    pub(crate) declarations: Vec<VariableDeclaration>,
+    pub(crate) functions: Vec<FunctionDeclaration>,
+    pub(crate) impls: Vec<CompiledImpl>,
+    pub(crate) type_casts: Vec<TypeCast>,
+    pub(crate) calls: Vec<Call>,
+    pub(crate) variable_slots: usize, // フレームに必要な変数のスロットの数
+    pub(crate) function_slots: usize, // フレームに必要な関数のスロットの数
+    pub(crate) global_slots: HashMap<String, usize>, // トップレベルで宣言された変数のスロット (プログラム全体のチャンクのみ)
+}
+
+/// 変数と定数の宣言
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct VariableDeclaration {
+    pub(crate) name: String,        // 変数名
+    pub(crate) var_type: Type,      // 宣言された型
+    pub(crate) is_constant: bool,   // 定数か
+    pub(crate) slot: Option<usize>, // 変数のスロット (同じスコープで宣言済みの場合はNone)
+}
+
+/// 関数の宣言
+///
+/// 同じスコープで宣言された同名の関数 (オーバーロード) は、同じスロットに置かれます。
+#[derive(Debug)]
+pub(crate) struct FunctionDeclaration {
+    pub(crate) slot: usize,
+    pub(crate) function: Rc<CompiledFunction>,
+}
+
+/// 型キャスト
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct TypeCast {
+    pub(crate) from_type: Option<Type>, // キャスト元の型 (省略した場合はNone)
+    pub(crate) to_type: Type,           // キャスト先の型
+    pub(crate) is_checked: bool,        // as? による失敗しうるキャストか
+}
+
+/// 関数とメソッドの呼び出し
+///
+/// 引数の値はスタックに積まれ、名前と展開の有無はここに残ります。
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Call {
+    pub(crate) name: String,                  // 関数名またはメソッド名
+    pub(crate) arguments: Vec<ArgumentShape>, // 引数の並び
+    pub(crate) callee: Callee,                // 呼び出される関数 (メソッドの場合はUndefined)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ArgumentShape {
+    pub(crate) name: Option<String>, // 引数名 (位置引数の場合はNone)
+    pub(crate) is_spread: bool,      // リストを展開して渡すか
+}
+
+/// コンパイル時に解決された、呼び出される関数
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Callee {
+    // 宣言された関数のオーバーロード
+    Function(Slot),
+    // 関数を値に持つ変数 (部分適用で作られた関数など)
+    // 値の関数は初めて呼び出す際にコンパイルするため、本体とデフォルト値から見えるスコープを残す
+    Variable {
+        slot: Slot,
+        body_environment: Rc<Environment>,
+        default_environment: Rc<Environment>,
+    },
+    // 関数も変数も見つからない (FunctionNotFound)
+    Undefined,
+}
+
+/// コンパイルされた関数
+///
+/// 本体とデフォルト値の式をチャンクとして持ち、呼び出しのたびにASTを複製せずに評価します。
+/// 本体は新しいフレームで実行され、パラメータは本体で宣言された変数と同じスコープのスロットに置かれます。
+/// デフォルト値の式は、関数が宣言されたフレームで実行されます。
+/// 宣言のNodeは、引数の束縛と部分適用に用います。
+#[derive(Debug)]
+pub(crate) struct CompiledFunction {
+    pub(crate) declaration: Rc<FunctionDeclarationNode>,
+    pub(crate) body: Chunk,
+    pub(crate) param_slots: Vec<Option<usize>>, // パラメータごとのスロット (同名のパラメータはNone)
+    pub(crate) default_values: Vec<Option<Chunk>>, // パラメータごとのデフォルト値
+}
+
+/// implで実装されたメソッド
+#[derive(Debug)]
+pub(crate) struct CompiledImpl {
+    pub(crate) type_name: String,
+    pub(crate) methods: Vec<Rc<CompiledFunction>>,
+}
+
+impl fmt::Display for Chunk {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, instruction) in self.instructions.iter().enumerate() {
+            let operand: String = match instruction {
+                Instruction::Constant(i) => format!("{:?}", self.constants[*i]),
+                Instruction::LoadUndefined(i) => self.names[*i].clone(),
+                Instruction::DeclareVariable(i) => self.declarations[*i].name.clone(),
+                Instruction::DeclareFunction(i) => {
+                    self.functions[*i].function.declaration.name.clone()
+                }
+                Instruction::Impl(i) => self.impls[*i].type_name.clone(),
+                Instruction::TypeCast(i) => self.type_casts[*i].to_type.to_string(),
+                Instruction::TypeTest(i) => self.types[*i].to_string(),
+                Instruction::Call(i)
+                | Instruction::CallMethod(i)
+                | Instruction::PartialApplication(i) => self.calls[*i].name.clone(),
+                _ => String::new(),
+            };
+            writeln!(
+                f,
+                "{}",
+                format!("{:>4} {:?} {}", index, instruction, operand).trim_end()
+            )?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,864 @@
+use crate::virtual_machine::ast::{FunctionDeclarationNode, FunctionValue, LiteralValue, Type};
+use crate::virtual_machine::bytecode::compiler::{compile_function, Environment};
+use crate::virtual_machine::bytecode::instruction::{
+    Call, Callee, Chunk, CompiledFunction, FunctionDeclaration, Instruction, Slot, TypeCast,
+    VariableDeclaration,
+};
+use crate::virtual_machine::evaluator::argument_binder::{
+    apply_partially, bind_arguments, bind_params, select_overload, validate_param_type,
+    CalledFunction, PassedArgument,
+};
+use crate::virtual_machine::evaluator::call_stack::{CallStack, Traceback};
+use crate::virtual_machine::evaluator::core::{is_value_of_type_with_methods, literal_to_type};
+use crate::virtual_machine::evaluator::evaluation_error::EvaluationError;
+use crate::virtual_machine::evaluator::mapper::function_mapper::has_same_signature;
+use crate::virtual_machine::evaluator::mapper::method_mapper::MethodMapper;
+use crate::virtual_machine::evaluator::statement_evaluator::check_declared_type;
+use crate::virtual_machine::evaluator::statement_evaluator::expression_evaluator::binary_operation_evaluator::calculate_binary_operation;
+use crate::virtual_machine::evaluator::statement_evaluator::expression_evaluator::call_of_function_evaluator::{check_return_type, with_receiver};
+use crate::virtual_machine::evaluator::statement_evaluator::expression_evaluator::type_cast_evaluator::cast_value;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+/// 一番外側のフレームの番号
+const GLOBAL_FRAME: usize = 0;
+
+/// チャンクを実行するスタックマシン
+///
+/// スコープ、行番号、エラーの規則はEvaluatorと同じです。
+/// 変数と関数はコンパイル時に解決されたスロットで参照し、実行時に名前で探しません。
+/// 関数の呼び出しごとにフレームを積み、関数の本体を評価し終えると取り除きます。
+/// 関数の本体とデフォルト値の式は、Rustの関数を再帰的に呼び出さず、戻り先のスタックを積んで一つのループで実行します。
+/// そのため、呼び出しの深さはRustのスタックの大きさに制限されず、呼び出しのスタックの上限までとなります。
+/// 呼び出しの履歴は、Evaluatorと同じく呼び出しのスタックに記録します。
+pub(crate) struct StackMachine {
+    line: usize,
+    stack: Vec<LiteralValue>,
+    block_lines: Vec<usize>, // ブロックに入る前の行番号
+    frames: Vec<Frame>,
+    declared_globals: Vec<bool>, // グローバルな変数のスロットごとの、宣言を実行したか
+    method_mapper: MethodMapper,
+    methods: HashMap<String, Rc<CompiledFunction>>, // "型名.メソッド名" ごとのコンパイル済みのメソッド
+    compiled_values: HashMap<CompiledValueKey, Rc<CompiledFunction>>, // 関数の値ごとのコンパイル済みの関数
+    call_stack: CallStack,
+}
+
+/// コンパイル済みの関数の値を探すキー
+///
+/// 関数の値の元の関数の宣言と、呼び出し位置から見えるスコープの組です。
+/// 同じ関数から作られた関数の値は同じキーとなるため、キーの数はプログラムの大きさで決まります。
+/// 宣言はコンパイル済みの関数が、スコープは実行中のチャンクの呼び出しが参照を持つため、実行を終えるまでアドレスは再利用されません。
+type CompiledValueKey = (*const FunctionDeclarationNode, *const Environment);
+
+/// 呼び出された関数のオーバーロード、関数の値 (部分適用で作られた関数の場合)、関数が宣言されたフレームの番号
+type ResolvedFunctions = (Vec<Rc<CompiledFunction>>, Option<Rc<FunctionValue>>, usize);
+
+/// 関数の呼び出しごとの変数と関数のスロット
+struct Frame {
+    variables: Vec<LiteralValue>,
+    functions: Vec<Vec<Rc<CompiledFunction>>>, // スロットごとのオーバーロード (宣言順)
+    enclosing: Option<usize>,                  // 関数が宣言されたフレームの番号
+}
+
+impl Frame {
+    fn new(chunk: &Chunk, enclosing: Option<usize>) -> Self {
+        Frame {
+            variables: vec![LiteralValue::None; chunk.variable_slots],
+            functions: vec![vec![]; chunk.function_slots],
+            enclosing,
+        }
+    }
+}
+
+/// 実行中の命令列
+#[derive(Clone)]
+enum Code {
+    Program,                                   // プログラム全体のチャンク
+    Body(Rc<CompiledFunction>),                // 関数の本体
+    DefaultValue(Rc<CompiledFunction>, usize), // パラメータのデフォルト値の式 (パラメータの番号)
+}
+
+impl Code {
+    fn chunk<'a>(&'a self, program: &'a Chunk) -> &'a Chunk {
+        match self {
+            Code::Program => program,
+            Code::Body(function) => &function.body,
+            Code::DefaultValue(function, index) => function.default_values[*index]
+                .as_ref()
+                .expect("default value must be compiled"),
+        }
+    }
+}
+
+/// 実行中の命令列、次に実行する命令の位置、命令列を実行するフレームの番号
+struct Position {
+    code: Code,
+    counter: usize,
+    frame: usize,
+}
+
+/// 呼び出した命令列を実行し終えた後に、実行を再開する位置と続きの処理
+struct ReturnAddress {
+    position: Position,
+    continuation: Continuation,
+}
+
+/// 呼び出した命令列を実行し終えた後の処理
+enum Continuation {
+    /// 関数の本体から戻り、フレームを取り除いて戻り値を検証する
+    Body {
+        name: String,
+        function: Rc<CompiledFunction>,
+        call_frame: usize, // 呼び出しのスタックのフレームの番号
+        line: usize,       // 呼び出し元の行番号
+    },
+    /// デフォルト値の式から戻り、値をパラメータに置いて呼び出しの準備を続ける
+    DefaultValue(PendingCall),
+}
+
+/// 引数をパラメータに置いている途中の関数呼び出し
+struct PendingCall {
+    name: String,
+    function: Rc<CompiledFunction>,
+    value: Option<Rc<FunctionValue>>, // 部分適用で作られた関数の値
+    declared_frame: Option<usize>,    // 関数が宣言されたフレームの番号 (メソッドはNone)
+    callee: Frame,
+    bound_params: std::vec::IntoIter<(String, Type, Option<LiteralValue>)>,
+    index: usize, // 次に値を置く、元の関数のパラメータの番号
+}
+
+impl StackMachine {
+    pub(crate) fn new() -> Self {
+        StackMachine {
+            line: 0,
+            stack: vec![],
+            block_lines: vec![],
+            frames: vec![],
+            declared_globals: vec![],
+            method_mapper: MethodMapper::new(),
+            methods: HashMap::new(),
+            compiled_values: HashMap::new(),
+            call_stack: CallStack::new(),
+        }
+    }
+
+    /// プログラム全体のチャンクを、一番外側のフレームで実行する
+    ///
+    /// return命令で取り出した値を返し、最後までreturn命令がなかった場合は `none` を返します。
+    pub(crate) fn run(&mut self, chunk: &Chunk) -> Result<LiteralValue, EvaluationError> {
+        self.frames = vec![Frame::new(chunk, None)];
+        self.declared_globals = vec![false; chunk.variable_slots];
+        let mut returns: Vec<ReturnAddress> = vec![];
+        let result: Result<LiteralValue, EvaluationError> = self.execute(chunk, &mut returns);
+        if result.is_err() {
+            self.unwind(returns);
+        }
+        result
+    }
+
+    /// 実行を終えたプログラムのグローバルな変数の値を求める
+    ///
+    /// モジュールからexportされた変数の値を求める際に用います。
+    ///
+    /// ## Raises
+    ///
+    /// * `EvaluationError::VariableNotFound` - トップレベルで宣言されていない変数と、宣言を実行する前にreturnした変数の場合
+    pub(crate) fn evaluate_variable(
+        &self,
+        chunk: &Chunk,
+        name: &str,
+    ) -> Result<LiteralValue, EvaluationError> {
+        match chunk.global_slots.get(name) {
+            Some(slot) if self.declared_globals[*slot] => {
+                Ok(self.frames[GLOBAL_FRAME].variables[*slot].clone())
+            }
+            // return 1;
+            // export let x: int = 5;  # x は宣言されていない
+            _ => Err(EvaluationError::VariableNotFound {
+                name: name.to_string(),
+                line: self.line,
+            }),
+        }
+    }
+
+    /// 実行がエラーで終わった時点の呼び出しの履歴を返す
+    pub(crate) fn traceback(&self) -> Traceback {
+        self.call_stack.traceback(self.line)
+    }
+
+    /// プログラム全体のチャンクを実行する
+    ///
+    /// 関数を呼び出すと、実行を再開する位置を `returns` に積んで関数の本体に移ります。
+    /// 本体やデフォルト値の式を実行し終えると、`returns` から取り出した位置に戻ります。
+    fn execute(
+        &mut self,
+        program: &Chunk,
+        returns: &mut Vec<ReturnAddress>,
+    ) -> Result<LiteralValue, EvaluationError> {
+        let mut position: Position = Position {
+            code: Code::Program,
+            counter: 0,
+            frame: GLOBAL_FRAME,
+        };
+        loop {
+            let chunk: &Chunk = position.code.chunk(program);
+            let frame: usize = position.frame;
+            // 命令列の最後まで実行した場合は、noneを返す
+            let Some(instruction) = chunk.instructions.get(position.counter) else {
+                match self.return_from(&mut position, returns, LiteralValue::None)? {
+                    Some(value) => return Ok(value),
+                    None => continue,
+                }
+            };
+            position.counter += 1;
+            match instruction {
+                Instruction::Constant(index) => self.stack.push(chunk.constants[*index].clone()),
+                Instruction::LoadVariable(slot) => {
+                    let frame: usize = self.frame_of(frame, slot);
+                    self.stack
+                        .push(self.frames[frame].variables[slot.index].clone());
+                }
+                Instruction::LoadUndefined(index) => {
+                    return Err(EvaluationError::VariableNotFound {
+                        name: chunk.names[*index].clone(),
+                        line: self.line,
+                    })
+                }
+                Instruction::DeclareVariable(index) => {
+                    let value: LiteralValue = self.pop();
+                    self.declare_variable(frame, &chunk.declarations[*index], value)?;
+                }
+                Instruction::DeclareFunction(index) => {
+                    self.declare_function(frame, &chunk.functions[*index])?;
+                }
+                Instruction::Impl(index) => {
+                    // メソッドは、implの対象の型名で登録する
+                    let type_name: &str = &chunk.impls[*index].type_name;
+                    for method in chunk.impls[*index].methods.iter() {
+                        self.method_mapper.set(
+                            self.line,
+                            type_name,
+                            Rc::clone(&method.declaration),
+                        )?;
+                        self.methods.insert(
+                            format!("{}.{}", type_name, method.declaration.name),
+                            Rc::clone(method),
+                        );
+                    }
+                }
+                Instruction::BinaryOperation(operator) => {
+                    let right: LiteralValue = self.pop();
+                    let left: LiteralValue = self.pop();
+                    self.stack.push(calculate_binary_operation(
+                        operator.clone(),
+                        left,
+                        right,
+                        self.line,
+                    )?);
+                }
+                Instruction::TypeCast(index) => {
+                    let value: LiteralValue = self.pop();
+                    let TypeCast {
+                        from_type,
+                        to_type,
+                        is_checked,
+                    } = chunk.type_casts[*index].clone();
+                    self.stack.push(cast_value(
+                        value, from_type, to_type, is_checked, self.line,
+                    )?);
+                }
+                Instruction::TypeTest(index) => {
+                    let value: LiteralValue = self.pop();
+                    self.stack
+                        .push(LiteralValue::Boolean(is_value_of_type_with_methods(
+                            value,
+                            chunk.types[*index].clone(),
+                            &self.method_mapper,
+                        )));
+                }
+                Instruction::Jump(target) => position.counter = *target,
+                Instruction::JumpIfFalse(target) => match self.pop() {
+                    LiteralValue::Boolean(true) => {}
+                    LiteralValue::Boolean(false) => position.counter = *target,
+                    // if 1 { ... } else { ... }  # InvalidCondition
+                    value => {
+                        return Err(EvaluationError::InvalidCondition {
+                            line: self.line,
+                            actual: literal_to_type(value).to_string(),
+                        })
+                    }
+                },
+                Instruction::Call(index) => {
+                    let call: &Call = &chunk.calls[*index];
+                    let arguments: Vec<PassedArgument> = self.pop_arguments(call);
+                    let (overloads, value, declared_frame) =
+                        self.resolve_called_functions(frame, call)?;
+                    let pending: PendingCall = self.bind_call(
+                        call.name.clone(),
+                        overloads,
+                        value,
+                        Some(declared_frame),
+                        arguments,
+                    )?;
+                    self.call(&mut position, returns, pending)?;
+                }
+                Instruction::CallMethod(index) => {
+                    let call: &Call = &chunk.calls[*index];
+                    let arguments: Vec<PassedArgument> = self.pop_arguments(call);
+                    let receiver: LiteralValue = self.pop();
+                    let pending: PendingCall =
+                        self.bind_method_call(&call.name, receiver, arguments)?;
+                    self.call(&mut position, returns, pending)?;
+                }
+                Instruction::PartialApplication(index) => {
+                    let call: &Call = &chunk.calls[*index];
+                    let arguments: Vec<PassedArgument> = self.pop_arguments(call);
+                    let value: LiteralValue = self.apply_partially(frame, call, arguments)?;
+                    self.stack.push(value);
+                }
+                Instruction::EnterBlock => self.block_lines.push(self.line),
+                Instruction::ExitBlock => {
+                    self.line = self.block_lines.pop().expect("block must be entered");
+                }
+                Instruction::SetLine(line) => self.line = *line,
+                Instruction::Pop => {
+                    self.pop();
+                }
+                Instruction::Return => {
+                    let value: LiteralValue = self.pop();
+                    if let Some(value) = self.return_from(&mut position, returns, value)? {
+                        return Ok(value);
+                    }
+                }
+                Instruction::Unexpected => {
+                    return Err(EvaluationError::UnexpectedError { line: self.line })
+                }
+            }
+        }
+    }
+
+    /// 関数の呼び出しの準備を進め、デフォルト値の式か関数の本体の実行に移る
+    fn call(
+        &mut self,
+        position: &mut Position,
+        returns: &mut Vec<ReturnAddress>,
+        pending: PendingCall,
+    ) -> Result<(), EvaluationError> {
+        let (code, frame, continuation) = self.continue_call(pending)?;
+        let caller: Position = std::mem::replace(
+            position,
+            Position {
+                code,
+                counter: 0,
+                frame,
+            },
+        );
+        returns.push(ReturnAddress {
+            position: caller,
+            continuation,
+        });
+        Ok(())
+    }
+
+    /// 実行し終えた命令列の値を受け取り、戻り先の位置で続きの処理をする
+    ///
+    /// プログラム全体のチャンクを実行し終えた場合は、プログラムの値を返します。
+    ///
+    /// ## Raises
+    ///
+    /// * `EvaluationError::ReturnTypeMismatch` - 戻り値が関数の戻り値の型に当てはまらない場合
+    /// * `EvaluationError::ParameterTypeMismatch` - デフォルト値の型が一致しない場合
+    fn return_from(
+        &mut self,
+        position: &mut Position,
+        returns: &mut Vec<ReturnAddress>,
+        value: LiteralValue,
+    ) -> Result<Option<LiteralValue>, EvaluationError> {
+        let Some(address) = returns.pop() else {
+            return Ok(Some(value));
+        };
+        *position = address.position;
+        match address.continuation {
+            Continuation::Body {
+                name,
+                function,
+                call_frame,
+                line,
+            } => {
+                self.frames.pop();
+                self.call_stack.pop(call_frame);
+                self.line = line;
+                check_return_type(
+                    &name,
+                    &function.declaration.return_type,
+                    &value,
+                    &self.method_mapper,
+                    self.line,
+                )?;
+                self.stack.push(value);
+            }
+            Continuation::DefaultValue(mut pending) => {
+                validate_param_type(
+                    &pending.name,
+                    &pending.function.declaration.params[pending.index],
+                    &value,
+                    &self.method_mapper,
+                    self.line,
+                )?;
+                self.put_param(&mut pending, value)?;
+                self.call(position, returns, pending)?;
+            }
+        }
+        Ok(None)
+    }
+
+    /// エラーで終わった関数の呼び出しのフレームを取り除き、エラーが起きた行番号を呼び出しの履歴に記録する
+    ///
+    /// 内側の呼び出しから順に、呼び出し元の行番号に戻しながら記録します。
+    fn unwind(&mut self, returns: Vec<ReturnAddress>) {
+        for address in returns.into_iter().rev() {
+            if let Continuation::Body {
+                call_frame, line, ..
+            } = address.continuation
+            {
+                self.frames.pop();
+                // エラーが起きた時点の履歴を残すため、呼び出しのフレームは取り除かない
+                self.call_stack.fail_at(call_frame, self.line);
+                self.line = line;
+            }
+        }
+    }
+
+    fn pop(&mut self) -> LiteralValue {
+        self.stack.pop().expect("stack must not be empty")
+    }
+
+    /// 実行中のフレームから、スロットのあるフレームまで外側のフレームをたどる
+    fn frame_of(&self, frame: usize, slot: &Slot) -> usize {
+        let mut frame: usize = frame;
+        for _ in 0..slot.hops {
+            frame = self.frames[frame]
+                .enclosing
+                .expect("slot must be resolved in an enclosing frame");
+        }
+        frame
+    }
+
+    /// 呼び出しの引数の値をスタックから取り出し、引数の名前と組にする
+    fn pop_arguments(&mut self, call: &Call) -> Vec<PassedArgument> {
+        let values: Vec<LiteralValue> = self
+            .stack
+            .split_off(self.stack.len() - call.arguments.len());
+        call.arguments
+            .iter()
+            .zip(values)
+            .map(|(shape, value)| PassedArgument {
+                name: shape.name.clone(),
+                value,
+                is_spread: shape.is_spread,
+            })
+            .collect()
+    }
+
+    /// 値が宣言された型に当てはまる場合に、変数をスロットに置く
+    ///
+    /// ## Raises
+    ///
+    /// * `EvaluationError::ReassignmentError` - 同じスコープで宣言済みの変数を宣言した場合
+    fn declare_variable(
+        &mut self,
+        frame: usize,
+        declaration: &VariableDeclaration,
+        value: LiteralValue,
+    ) -> Result<(), EvaluationError> {
+        check_declared_type(
+            &declaration.name,
+            &declaration.var_type,
+            &value,
+            declaration.is_constant,
+            &self.method_mapper,
+            self.line,
+        )?;
+        match declaration.slot {
+            Some(slot) => {
+                self.frames[frame].variables[slot] = value;
+                if frame == GLOBAL_FRAME {
+                    self.declared_globals[slot] = true;
+                }
+                Ok(())
+            }
+            None => Err(EvaluationError::ReassignmentError {
+                name: declaration.name.clone(),
+                line: self.line,
+            }),
+        }
+    }
+
+    /// 関数をオーバーロードのスロットに加える
+    ///
+    /// ## Raises
+    ///
+    /// * `EvaluationError::ReassignmentError` - パラメータの名前と型が全て同じ関数を宣言した場合
+    fn declare_function(
+        &mut self,
+        frame: usize,
+        declaration: &FunctionDeclaration,
+    ) -> Result<(), EvaluationError> {
+        let function: &Rc<CompiledFunction> = &declaration.function;
+        let overloads: &mut Vec<Rc<CompiledFunction>> =
+            &mut self.frames[frame].functions[declaration.slot];
+        if overloads
+            .iter()
+            .any(|overload| has_same_signature(&overload.declaration, &function.declaration))
+        {
+            return Err(EvaluationError::ReassignmentError {
+                name: function.declaration.name.clone(),
+                line: self.line,
+            });
+        }
+        overloads.push(Rc::clone(function));
+        Ok(())
+    }
+
+    /// 呼び出された関数のオーバーロードと、関数が宣言されたフレームの番号を求める
+    ///
+    /// 関数を値に持つ変数 (部分適用で作られた関数など) は、元の関数の宣言を呼び出す位置ごとに初めて呼び出す際にコンパイルし、
+    /// 以降の呼び出しではコンパイル済みの関数を使います。
+    /// 同じ関数から部分適用で作られた関数の値は、束縛された引数が異なってもコンパイル済みの関数を共有します。
+    /// 関数の値の場合は、束縛された引数を持つ関数の値もあわせて返します。
+    ///
+    /// ## Raises
+    ///
+    /// * `EvaluationError::FunctionNotFound` - 関数も変数も見つからない場合
+    /// * `EvaluationError::NotCallable` - 変数の値が関数でない場合
+    fn resolve_called_functions(
+        &mut self,
+        frame: usize,
+        call: &Call,
+    ) -> Result<ResolvedFunctions, EvaluationError> {
+        match &call.callee {
+            Callee::Function(slot) => {
+                let declared_frame: usize = self.frame_of(frame, slot);
+                Ok((
+                    self.frames[declared_frame].functions[slot.index].clone(),
+                    None,
+                    declared_frame,
+                ))
+            }
+            Callee::Variable {
+                slot,
+                body_environment,
+                default_environment,
+            } => {
+                let declared_frame: usize = self.frame_of(frame, slot);
+                match &self.frames[declared_frame].variables[slot.index] {
+                    LiteralValue::Function(value) => {
+                        let value: Rc<FunctionValue> = Rc::clone(value);
+                        let key: CompiledValueKey =
+                            (Rc::as_ptr(&value.declaration), Rc::as_ptr(body_environment));
+                        let compiled: Rc<CompiledFunction> = match self.compiled_values.get(&key) {
+                            Some(compiled) => Rc::clone(compiled),
+                            None => {
+                                let compiled: Rc<CompiledFunction> = Rc::new(compile_function(
+                                    &value.declaration,
+                                    body_environment,
+                                    default_environment,
+                                ));
+                                self.compiled_values.insert(key, Rc::clone(&compiled));
+                                compiled
+                            }
+                        };
+                        Ok((vec![compiled], Some(value), declared_frame))
+                    }
+                    value => Err(EvaluationError::NotCallable {
+                        name: call.name.clone(),
+                        actual: literal_to_type(value.clone()).to_string(),
+                        line: self.line,
+                    }),
+                }
+            }
+            Callee::Undefined => Err(EvaluationError::FunctionNotFound {
+                name: call.name.clone(),
+                line: self.line,
+            }),
+        }
+    }
+
+    /// 呼び出し元の値の型に実装されたメソッドを探し、引数を束縛する
+    ///
+    /// ## Raises
+    ///
+    /// * `EvaluationError::MethodNotFound` - 呼び出し元の値の型にメソッドが実装されていない場合
+    fn bind_method_call(
+        &mut self,
+        name: &str,
+        receiver: LiteralValue,
+        arguments: Vec<PassedArgument>,
+    ) -> Result<PendingCall, EvaluationError> {
+        let type_name: String = literal_to_type(receiver.clone()).to_string();
+        let method_name: String = format!("{}.{}", type_name, name);
+        let method: Rc<CompiledFunction> = match self.methods.get(&method_name) {
+            Some(method) => Rc::clone(method),
+            None => {
+                return Err(EvaluationError::MethodNotFound {
+                    type_name,
+                    name: name.to_string(),
+                    line: self.line,
+                })
+            }
+        };
+        self.bind_call(
+            method_name,
+            vec![method],
+            None,
+            None,
+            with_receiver(receiver, arguments),
+        )
+    }
+
+    /// オーバーロードから引数を束縛できる関数を選び、呼び出しの準備を始める
+    ///
+    /// `value` は部分適用で作られた関数の値で、`overloads` はコンパイル済みの元の関数です。
+    /// 引数は関数の値の残りのパラメータに束縛し、部分適用で束縛された引数とあわせて元の関数のパラメータに置きます。
+    /// `declared_frame` は関数が宣言されたフレームの番号で、デフォルト値の式もこのフレームで評価します。
+    /// `None` の場合 (メソッド) は、外側のフレームを持たずに関数の本体を実行します。
+    fn bind_call(
+        &mut self,
+        name: String,
+        overloads: Vec<Rc<CompiledFunction>>,
+        value: Option<Rc<FunctionValue>>,
+        declared_frame: Option<usize>,
+        arguments: Vec<PassedArgument>,
+    ) -> Result<PendingCall, EvaluationError> {
+        let (function, bound_params) = select_overload(&name, &overloads, self.line, |overload| {
+            bind_params(
+                &name,
+                &arguments,
+                value
+                    .as_ref()
+                    .map_or(&overload.declaration.params, |value| &value.params),
+                &self.method_mapper,
+                self.line,
+            )
+        })?;
+        Ok(PendingCall {
+            name,
+            callee: Frame::new(&function.body, declared_frame),
+            function,
+            value,
+            declared_frame,
+            bound_params: bound_params.into_iter(),
+            index: 0,
+        })
+    }
+
+    /// 呼び出しのパラメータに値を置き、次に実行する命令列とフレームの番号、戻った後の処理を返す
+    ///
+    /// 省略された引数のデフォルト値は、関数が宣言されたフレームで評価します。
+    /// 全てのパラメータに値を置いた場合は、新しいフレームを積んで関数の本体に移ります。
+    ///
+    /// ## Raises
+    ///
+    /// * `EvaluationError::ReassignmentError` - 同じ名前のパラメータがある場合
+    /// * `EvaluationError::CallDepthExceeded` - 呼び出しの深さが上限に達した場合
+    fn continue_call(
+        &mut self,
+        mut pending: PendingCall,
+    ) -> Result<(Code, usize, Continuation), EvaluationError> {
+        let function: Rc<CompiledFunction> = Rc::clone(&pending.function);
+        while let Some(param) = function.declaration.params.get(pending.index) {
+            let bound: Option<LiteralValue> = pending.value.as_ref().and_then(|value| {
+                value
+                    .prologue
+                    .iter()
+                    .find(|(name, _, _)| *name == param.name)
+                    .map(|(_, _, value)| value.clone())
+            });
+            let value: LiteralValue = match bound {
+                Some(value) => value,
+                None => match pending.bound_params.next() {
+                    Some((_, _, Some(value))) => value,
+                    Some((_, _, None)) => {
+                        let index: usize = pending.index;
+                        let frame: usize = pending.declared_frame.unwrap_or(GLOBAL_FRAME);
+                        return Ok((
+                            Code::DefaultValue(function, index),
+                            frame,
+                            Continuation::DefaultValue(pending),
+                        ));
+                    }
+                    None => unreachable!("bind_params binds every parameter not in the prologue"),
+                },
+            };
+            self.put_param(&mut pending, value)?;
+        }
+
+        let call_frame: usize = self.call_stack.push(&pending.name, self.line)?;
+        self.frames.push(pending.callee);
+        Ok((
+            Code::Body(Rc::clone(&function)),
+            self.frames.len() - 1,
+            Continuation::Body {
+                name: pending.name,
+                function,
+                call_frame,
+                line: self.line,
+            },
+        ))
+    }
+
+    /// 呼び出しの次のパラメータのスロットに値を置く
+    ///
+    /// ## Raises
+    ///
+    /// * `EvaluationError::ReassignmentError` - 同じ名前のパラメータがある場合
+    fn put_param(
+        &mut self,
+        pending: &mut PendingCall,
+        value: LiteralValue,
+    ) -> Result<(), EvaluationError> {
+        match pending.function.param_slots[pending.index] {
+            Some(slot) => pending.callee.variables[slot] = value,
+            // let f: fn = (x: int, x: int): int { return x; };
+            // f(1, 2);  # ReassignmentError
+            None => {
+                return Err(EvaluationError::ReassignmentError {
+                    name: pending.function.declaration.params[pending.index]
+                        .name
+                        .clone(),
+                    line: self.line,
+                })
+            }
+        }
+        pending.index += 1;
+        Ok(())
+    }
+
+    /// 関数を部分適用し、残りのパラメータを受け取る関数を返す
+    fn apply_partially(
+        &mut self,
+        frame: usize,
+        call: &Call,
+        arguments: Vec<PassedArgument>,
+    ) -> Result<LiteralValue, EvaluationError> {
+        let (overloads, value, _) = self.resolve_called_functions(frame, call)?;
+        let (function, bound_arguments) =
+            select_overload(&call.name, &overloads, self.line, |overload| {
+                bind_arguments(
+                    &call.name,
+                    &arguments,
+                    value
+                        .as_ref()
+                        .map_or(&overload.declaration.params, |value| &value.params),
+                    &self.method_mapper,
+                    self.line,
+                )
+            })?;
+        let function: CalledFunction = match value {
+            Some(value) => CalledFunction::Value(value),
+            None => CalledFunction::Declared(Rc::clone(&function.declaration)),
+        };
+        Ok(LiteralValue::Function(Rc::new(apply_partially(
+            &function,
+            bound_arguments,
+        ))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::virtual_machine::ast::LiteralValue;
+    use crate::virtual_machine::bytecode::{compile, Chunk, StackMachine};
+    use crate::virtual_machine::evaluator::call_stack::MAX_CALL_DEPTH;
+    use crate::virtual_machine::evaluator::core::parse_source;
+    use crate::virtual_machine::evaluator::evaluation_error::EvaluationError;
+
+    /// 部分適用で作られた関数は、同じ位置で何度呼び出しても一度だけコンパイルされることを確認するテスト
+    #[test]
+    fn test_compile_function_value_once() {
+        let chunk: Chunk = compile(&parse_source(
+            r#"
+let run: fn = (n: int): int {
+    let add: fn = (x: int, y: int): int { return x + y; };
+    let inc: fn = add(y: 1, ...);
+    let count: fn = (k: int, acc: int): int {
+        let is_zero: bool = ((k as string) + "0000000000000000000") as? int is int;
+        return if is_zero { acc } else { count(k: k - 1, acc: inc(x: acc)) };
+    };
+    return count(k: n, acc: 0);
+};
+return run(n: 5);
+"#,
+        ));
+        let mut stack_machine: StackMachine = StackMachine::new();
+        assert_eq!(stack_machine.run(&chunk), Ok(LiteralValue::Integer(5)));
+        assert_eq!(stack_machine.compiled_values.len(), 1);
+    }
+
+    /// 同じ関数から作られた関数の値は、束縛された引数が異なってもコンパイル済みの関数を共有することを確認するテスト
+    #[test]
+    fn test_share_compiled_function_between_partial_applications() {
+        let chunk: Chunk = compile(&parse_source(
+            r#"
+let run: fn = (n: int): int {
+    let add: fn = (x: int, y: int): int { return x + y; };
+    let count: fn = (k: int, acc: int): int {
+        let is_zero: bool = ((k as string) + "0000000000000000000") as? int is int;
+        let step: fn = add(x: acc, ...);
+        return if is_zero { acc } else { count(k: k - 1, acc: step(y: 1)) };
+    };
+    return count(k: n, acc: 0);
+};
+return run(n: 50);
+"#,
+        ));
+        let mut stack_machine: StackMachine = StackMachine::new();
+        assert_eq!(stack_machine.run(&chunk), Ok(LiteralValue::Integer(50)));
+        assert_eq!(stack_machine.compiled_values.len(), 1);
+    }
+
+    /// 深い再帰呼び出しがRustのスタックを使い切らず、上限を超えた場合はエラーになることを確認するテスト
+    #[test]
+    fn test_deep_recursion() {
+        let run = |n: i64| {
+            let chunk: Chunk = compile(&parse_source(&format!(
+                r#"
+let run: fn = (n: int): int {{
+    let add: fn = (x: int, y: int): int {{ return x + y; }};
+    let count: fn = (k: int, acc: int): int {{
+        let is_zero: bool = ((k as string) + "0000000000000000000") as? int is int;
+        let step: fn = add(x: acc, ...);
+        return if is_zero {{ acc }} else {{ count(k: k - 1, acc: step(y: 1)) }};
+    }};
+    return count(k: n, acc: 0);
+}};
+return run(n: {});
+"#,
+                n
+            )));
+            let mut stack_machine: StackMachine = StackMachine::new();
+            let result: Result<LiteralValue, EvaluationError> = stack_machine.run(&chunk);
+            (result, stack_machine.traceback())
+        };
+
+        assert_eq!(run(5000).0, Ok(LiteralValue::Integer(5000)));
+
+        let (result, traceback) = run(20000);
+        assert_eq!(
+            result,
+            Err(EvaluationError::CallDepthExceeded {
+                function_name: "step".to_string(),
+                max_depth: MAX_CALL_DEPTH,
+                line: 7,
+            })
+        );
+        let traceback: String = traceback.to_string();
+        assert!(
+            traceback.ends_with(
+                "  line 7, in count (called at line 7)\n  [previous line repeated 9997 more times]"
+            ),
+            "{}",
+            traceback
+        );
+    }
+}
@@ -1,47 +1,112 @@
-mod core;
-mod evaluation_error;
+pub(crate) mod argument_binder;
+pub(crate) mod call_stack;
+pub(crate) mod constant_folder;
+pub(crate) mod core;
+pub(crate) mod evaluation_error;
 pub(crate) mod mapper;
-mod statement_evaluator;
+pub(crate) mod statement_evaluator;
 
 use crate::virtual_machine::ast::{LiteralValue, Statement, AST};
 use crate::virtual_machine::evaluator::evaluation_error::EvaluationError;
+use call_stack::{CallStack, Traceback};
 use mapper::function_mapper::FunctionMapper;
+use mapper::method_mapper::MethodMapper;
 use mapper::variable_mapper::VariableMapper;
 use statement_evaluator::evaluate_statement;
 use statement_evaluator::expression_evaluator::evaluate_expression;
+use std::rc::Rc;
 
+/// ASTを直接評価するEvaluator
+///
+/// 文と式は借用して評価し、評価のたびにASTを複製しません。
+/// 実装されたメソッドは、関数の本体を評価するEvaluatorと `Rc` で共有します。
 pub struct Evaluator {
-    ast: AST,
+    ast: Rc<AST>,
     line: usize,
     function_mapper: FunctionMapper,
     variable_mapper: VariableMapper,
+    method_mapper: Rc<MethodMapper>,
+    is_function_body: bool,
+    call_stack: CallStack,
 }
 
 impl Evaluator {
     pub fn new(ast: AST, function_mapper: FunctionMapper, variable_mapper: VariableMapper) -> Self {
         Evaluator {
-            ast,
+            ast: Rc::new(ast),
             line: 0,
             function_mapper,
             variable_mapper,
+            method_mapper: Rc::new(MethodMapper::new()),
+            is_function_body: false,
+            call_stack: CallStack::new(),
         }
     }
 
+    /// 呼び出し元で実装されたメソッドを引き継ぐ
+    ///
+    /// メソッドはスコープを持たないため、関数の本体からも呼び出すことができます。
+    pub(crate) fn with_method_mapper(mut self, method_mapper: Rc<MethodMapper>) -> Self {
+        self.method_mapper = method_mapper;
+        self
+    }
+
+    /// 関数の本体を評価するEvaluatorとする
+    ///
+    /// 関数の本体で宣言された関数は、本体のパラメータや変数、関数を参照できます。
+    pub(crate) fn in_function_body(mut self) -> Self {
+        self.is_function_body = true;
+        self
+    }
+
     pub fn evaluate(&mut self) -> Result<LiteralValue, EvaluationError> {
         // ここで評価処理を行う
-        for stmt in self.ast.statements.clone() {
-            self.line = stmt.0;
+        // 文を借用している間もEvaluatorを変更できるように、ASTへの参照を増やしておく
+        let ast: Rc<AST> = Rc::clone(&self.ast);
+        for (line, stmt) in ast.statements.iter() {
+            self.line = *line;
 
             // Return文なら評価して OK(LiteralValue) を返す
             // それ以外の場合は次のステートメントを評価する (OKを返さずに次の評価を続ける)
-            if let Statement::Return(expr) = stmt.1.clone() {
-                return evaluate_expression(self, *expr);
+            if let Statement::Return(expr) = stmt {
+                return evaluate_expression(self, expr);
             }
-            evaluate_statement(self, stmt.1)?;
+            evaluate_statement(self, stmt)?;
         }
         // 最後までReturn文がなかった場合は None を返す
         // 自作関数の場合、Parserの時点でReturn文があることを保証しているので、ここでNoneを返すことはない
         // つまり、ここでNoneを返すのは、GlobalScopeの場合のみ
         Ok(LiteralValue::None)
     }
+
+    /// 関数の本体を評価する
+    ///
+    /// 本体の文は、関数宣言のNodeから借用して、それぞれの文の行番号で評価します。
+    pub(crate) fn evaluate_function_body(
+        &mut self,
+        body: &[(usize, Statement)],
+    ) -> Result<LiteralValue, EvaluationError> {
+        for (line, stmt) in body.iter() {
+            self.line = *line;
+            if let Statement::Return(expr) = stmt {
+                return evaluate_expression(self, expr);
+            }
+            evaluate_statement(self, stmt)?;
+        }
+        Ok(LiteralValue::None)
+    }
+
+    /// 評価がエラーで終わった時点の呼び出しの履歴を返す
+    ///
+    /// 関数の呼び出しの中でエラーが起きた場合、呼び出された関数と行番号が順に並びます。
+    pub(crate) fn traceback(&self) -> Traceback {
+        self.call_stack.traceback(self.line)
+    }
+
+    /// 変数を評価して値を求める
+    ///
+    /// モジュールからexportされた変数の値を求める際に用います。
+    pub(crate) fn evaluate_variable(&self, name: &str) -> Result<LiteralValue, EvaluationError> {
+        self.variable_mapper.get(name, self.line)
+    }
 }
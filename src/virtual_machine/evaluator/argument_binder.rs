@@ -0,0 +1,415 @@
+use crate::virtual_machine::ast::{
+    FunctionDeclarationNode, FunctionValue, LiteralValue, ParameterNode, Type,
+};
+use crate::virtual_machine::evaluator::core::{is_value_of_type_with_methods, literal_to_type};
+use crate::virtual_machine::evaluator::evaluation_error::EvaluationError;
+use crate::virtual_machine::evaluator::mapper::method_mapper::MethodMapper;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+/// 評価済みの引数
+///
+/// 引数は呼び出す関数を探す前に、左から順に評価されます。
+/// EvaluatorとStackMachineは、評価した引数を同じ規則でパラメータに束縛します。
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct PassedArgument {
+    pub(crate) name: Option<String>, // 引数名 (位置引数の場合はNone)
+    pub(crate) value: LiteralValue,  // 引数の値
+    pub(crate) is_spread: bool,      // リストを展開して渡すか
+}
+
+/// パラメータの名前と束縛された値の組みと、可変長パラメータに渡された値のリスト
+pub(crate) type BoundArguments = (HashMap<String, LiteralValue>, Option<Vec<LiteralValue>>);
+
+/// パラメータの宣言順に並べた、パラメータの名前、型、束縛された値
+///
+/// 省略された引数のうち、デフォルト値を持つパラメータの値は `None` となります。
+pub(crate) type BoundParams = Vec<(String, Type, Option<LiteralValue>)>;
+
+/// 呼び出される関数
+///
+/// 宣言された関数と、部分適用で作られた関数の値を同じ規則で呼び出します。
+#[derive(Debug, Clone)]
+pub(crate) enum CalledFunction {
+    Declared(Rc<FunctionDeclarationNode>), // 宣言された関数
+    Value(Rc<FunctionValue>),              // 関数の値
+}
+
+impl CalledFunction {
+    /// 関数の宣言 (関数の値の場合は、部分適用した元の関数の宣言)
+    pub(crate) fn declaration(&self) -> &Rc<FunctionDeclarationNode> {
+        match self {
+            CalledFunction::Declared(declaration) => declaration,
+            CalledFunction::Value(value) => &value.declaration,
+        }
+    }
+
+    /// 引数を束縛するパラメータ
+    pub(crate) fn params(&self) -> &[ParameterNode] {
+        match self {
+            CalledFunction::Declared(declaration) => &declaration.params,
+            CalledFunction::Value(value) => &value.params,
+        }
+    }
+
+    /// 部分適用で束縛された引数
+    pub(crate) fn prologue(&self) -> &[(String, Type, LiteralValue)] {
+        match self {
+            CalledFunction::Declared(_) => &[],
+            CalledFunction::Value(value) => &value.prologue,
+        }
+    }
+}
+
+/// オーバーロードの中から、引数を束縛できる関数を選ぶ
+///
+/// 各オーバーロードに `bind` で引数を束縛し、成功した関数が1つだけならその関数を選びます。
+/// オーバーロードが1つしかない場合は、束縛のエラーをそのまま返します。
+///
+/// ```shot
+/// let open: fn = (path: string): int { return 0; };
+/// let open: fn = (fd: int): int { return fd; };
+/// open(path: "a.txt");  # 1つ目の open
+/// open(fd: 3);          # 2つ目の open
+/// ```
+///
+/// ## Raises
+///
+/// * `EvaluationError::NoMatchingOverload` - 引数を束縛できるオーバーロードがない場合
+/// * `EvaluationError::AmbiguousFunctionCall` - 引数を束縛できるオーバーロードが複数ある場合
+pub(crate) fn select_overload<F: Clone, T>(
+    name: &str,
+    overloads: &[F],
+    line: usize,
+    bind: impl Fn(&F) -> Result<T, EvaluationError>,
+) -> Result<(F, T), EvaluationError> {
+    if let [overload] = overloads {
+        let bound: T = bind(overload)?;
+        return Ok((overload.clone(), bound));
+    }
+
+    let mut matched: Vec<(F, T)> = vec![];
+    for overload in overloads {
+        match bind(overload) {
+            Ok(bound) => matched.push((overload.clone(), bound)),
+            // 引数を束縛できないオーバーロードは候補から外す
+            Err(error) if is_binding_error(&error) => continue,
+            Err(error) => return Err(error),
+        }
+    }
+
+    match matched.len() {
+        // open(mode: "r");  # NoMatchingOverload
+        0 => Err(EvaluationError::NoMatchingOverload {
+            name: name.to_string(),
+            line,
+        }),
+        1 => Ok(matched.remove(0)),
+        // let open: fn = (path: string, mode: string = "r"): int { return 0; };
+        // open(path: "a.txt");  # AmbiguousFunctionCall
+        candidates => Err(EvaluationError::AmbiguousFunctionCall {
+            name: name.to_string(),
+            candidates,
+            line,
+        }),
+    }
+}
+
+/// 引数をパラメータに束縛できなかったことを表すエラーか判定する
+fn is_binding_error(error: &EvaluationError) -> bool {
+    matches!(
+        error,
+        EvaluationError::ArgumentLengthError { .. }
+            | EvaluationError::DuplicateArgument { .. }
+            | EvaluationError::InvalidSpreadArgument { .. }
+            | EvaluationError::MissingArgument { .. }
+            | EvaluationError::ParameterNotFound { .. }
+            | EvaluationError::ParameterTypeMismatch { .. }
+    )
+}
+
+/// 引数をパラメータに束縛し、パラメータの宣言順に並べる
+///
+/// 省略された引数のデフォルト値は、呼び出し元で評価してから `validate_param_type` で検証します。
+/// 可変長パラメータは、引数が渡されなかった場合も空のリストとなります。
+///
+/// ## Raises
+///
+/// * `bind_arguments` と同じエラー
+/// * `EvaluationError::MissingArgument` - デフォルト値のない引数が省略された場合
+pub(crate) fn bind_params(
+    function_name: &str,
+    arguments: &[PassedArgument],
+    params: &[ParameterNode],
+    method_mapper: &MethodMapper,
+    line: usize,
+) -> Result<BoundParams, EvaluationError> {
+    let (mut passed_arguments, variadic_arguments) =
+        bind_arguments(function_name, arguments, params, method_mapper, line)?;
+
+    let mut result: BoundParams = vec![];
+    for param in params {
+        if param.is_variadic {
+            result.push((
+                param.name.clone(),
+                Type::List(Box::new(param.param_type.clone())),
+                Some(LiteralValue::List(
+                    variadic_arguments.clone().unwrap_or_default(),
+                )),
+            ));
+            continue;
+        }
+
+        let value: Option<LiteralValue> = match passed_arguments.remove(&param.name) {
+            Some(value) => Some(value),
+            // 省略された引数はデフォルト値を用いる
+            // let f: fn = (x: int = 0): Void { return none; };
+            // f(); // x = 0
+            None if param.default_value.is_some() => None,
+            // デフォルト値のない引数が省略された
+            // let f: fn = (x: int): Void { return none; };
+            // f(); // MissingArgument
+            None => {
+                return Err(EvaluationError::MissingArgument {
+                    function_name: function_name.to_string(),
+                    param_name: param.name.clone(),
+                    line,
+                });
+            }
+        };
+        result.push((param.name.clone(), param.param_type.clone(), value));
+    }
+    Ok(result)
+}
+
+/// 渡された引数をパラメータに束縛する
+///
+/// 名前付き引数と位置引数を検証し、束縛されたパラメータの名前と値の組みを返します。
+/// 可変長パラメータに渡された値はリストにまとめ、渡されなかった場合は `None` とします。
+///
+/// ## Raises
+///
+/// * `EvaluationError::ArgumentLengthError` - 引数の数がパラメータの数を超える場合
+/// * `EvaluationError::DuplicateArgument` - 同じパラメータに引数が複数回渡された場合
+/// * `EvaluationError::InvalidSpreadArgument` - 可変長パラメータ以外に展開した引数が渡された場合
+/// * `EvaluationError::ParameterTypeMismatch` - 引数の型が一致しない場合
+/// * `EvaluationError::ParameterNotFound` - 引数の名前が一致しない場合
+pub(crate) fn bind_arguments(
+    function_name: &str,
+    arguments: &[PassedArgument],
+    params: &[ParameterNode],
+    method_mapper: &MethodMapper,
+    line: usize,
+) -> Result<BoundArguments, EvaluationError> {
+    // 引数の数がパラメータの数を超えていないかチェック
+    // 可変長パラメータがある場合は、引数の数に上限はない
+    let has_variadic_param: bool = params.iter().any(|param| param.is_variadic);
+    if !has_variadic_param && arguments.len() > params.len() {
+        return Err(EvaluationError::ArgumentLengthError {
+            function_name: function_name.to_string(),
+            expected: params.len(),
+            actual: arguments.len(),
+            line,
+        });
+    }
+
+    let mut passed_arguments: HashMap<String, LiteralValue> = HashMap::new();
+    let mut variadic_arguments: Option<Vec<LiteralValue>> = None;
+    let mut positional_argument_count: usize = 0;
+    for argument in arguments {
+        let param: &ParameterNode = match &argument.name {
+            // 名前付き引数は名前でパラメータを探す
+            Some(argument_name) => match params.iter().find(|param| &param.name == argument_name) {
+                Some(param) => param,
+                // パラメータの名前付き引数の名前解決に失敗
+                // let f: fn = (x: int): Void { return none; };
+                // f(z: 1); // ParameterNotFound
+                None => {
+                    return Err(EvaluationError::ParameterNotFound {
+                        function_name: function_name.to_string(),
+                        param_name: argument_name.clone(),
+                        line,
+                    });
+                }
+            },
+            // 位置引数はパラメータの宣言順に束縛する
+            // let f: fn = (x: int, y: int): Void { return none; };
+            // f(1, 2); // x = 1, y = 2
+            None => {
+                positional_argument_count += 1;
+                match search_positional_param(positional_argument_count - 1, params) {
+                    Some(param) => param,
+                    // 位置引数の数がパラメータの数を超えている
+                    // let f: fn = (x: int): Void { return none; };
+                    // f(1, 2); // ArgumentLengthError
+                    None => {
+                        return Err(EvaluationError::ArgumentLengthError {
+                            function_name: function_name.to_string(),
+                            expected: params.len(),
+                            actual: positional_argument_count,
+                            line,
+                        });
+                    }
+                }
+            }
+        };
+
+        // 可変長パラメータへの引数はリストにまとめる
+        // let f: fn = (...parts: string): Void { return none; };
+        // f(parts: "a", parts: "b"); // parts = ["a", "b"]
+        if param.is_variadic {
+            variadic_arguments
+                .get_or_insert_with(Vec::new)
+                .append(&mut collect_variadic_argument(
+                    function_name,
+                    param,
+                    &argument.value,
+                    argument.is_spread,
+                    method_mapper,
+                    line,
+                )?);
+            continue;
+        }
+
+        // 同じパラメータに複数回引数が渡されている
+        // let f: fn = (x: int): Void { return none; };
+        // f(x: 1, x: 2); // DuplicateArgument
+        // f(1, x: 2);    // DuplicateArgument
+        if passed_arguments.contains_key(&param.name) {
+            return Err(EvaluationError::DuplicateArgument {
+                function_name: function_name.to_string(),
+                param_name: param.name.clone(),
+                line,
+            });
+        }
+
+        // 可変長パラメータ以外に展開した引数が渡されている
+        // let f: fn = (x: int): Void { return none; };
+        // f(x: ...xs); // InvalidSpreadArgument
+        if argument.is_spread {
+            return Err(EvaluationError::InvalidSpreadArgument {
+                function_name: function_name.to_string(),
+                param_name: param.name.clone(),
+                line,
+            });
+        }
+
+        validate_param_type(function_name, param, &argument.value, method_mapper, line)?;
+        passed_arguments.insert(param.name.clone(), argument.value.clone());
+    }
+
+    Ok((passed_arguments, variadic_arguments))
+}
+
+/// 束縛された引数を、部分適用で作られる関数の値のプロローグに加える
+///
+/// 元の関数の宣言は複製せずに共有し、関数の値を部分適用した場合もプロローグを引き継ぎます。
+/// 残りのパラメータは、宣言順、型、デフォルト値を保ったまま新しい関数の値のパラメータとなります。
+/// 可変長パラメータに引数が渡された場合は、可変長パラメータも束縛されます。
+pub(crate) fn apply_partially(
+    function: &CalledFunction,
+    (mut bound_arguments, variadic_arguments): BoundArguments,
+) -> FunctionValue {
+    let mut params: Vec<ParameterNode> = vec![];
+    let mut prologue: Vec<(String, Type, LiteralValue)> = function.prologue().to_vec();
+    for param in function.params().iter().cloned() {
+        let bound_argument: Option<(Type, LiteralValue)> = match param.is_variadic {
+            true => variadic_arguments.clone().map(|values| {
+                (
+                    Type::List(Box::new(param.param_type.clone())),
+                    LiteralValue::List(values),
+                )
+            }),
+            false => bound_arguments
+                .remove(&param.name)
+                .map(|value| (param.param_type.clone(), value)),
+        };
+        match bound_argument {
+            Some((param_type, value)) => prologue.push((param.name, param_type, value)),
+            None => params.push(param),
+        }
+    }
+
+    FunctionValue {
+        declaration: Rc::clone(function.declaration()),
+        params,
+        prologue,
+    }
+}
+
+/// 可変長パラメータに渡された引数を、リストの要素として検証
+///
+/// 展開された引数の場合は、リストの各要素の型を検証します。
+///
+/// ## Raises
+///
+/// * `EvaluationError::ParameterTypeMismatch` - 引数の型が一致しない場合
+fn collect_variadic_argument(
+    function_name: &str,
+    param: &ParameterNode,
+    value: &LiteralValue,
+    is_spread: bool,
+    method_mapper: &MethodMapper,
+    line: usize,
+) -> Result<Vec<LiteralValue>, EvaluationError> {
+    if !is_spread {
+        validate_param_type(function_name, param, value, method_mapper, line)?;
+        return Ok(vec![value.clone()]);
+    }
+
+    match value {
+        LiteralValue::List(values) => {
+            for element in values {
+                validate_param_type(function_name, param, element, method_mapper, line)?;
+            }
+            Ok(values.clone())
+        }
+        // リスト以外は展開できない
+        // let f: fn = (...parts: string): Void { return none; };
+        // f(parts: ..."a"); // ParameterTypeMismatch
+        value => Err(EvaluationError::ParameterTypeMismatch {
+            function_name: function_name.to_string(),
+            param_name: param.name.clone(),
+            line,
+            expected: Type::List(Box::new(param.param_type.clone())).to_string(),
+            actual: literal_to_type(value.clone()).to_string(),
+        }),
+    }
+}
+
+/// 引数の値の型が、パラメータの型と一致しているかを検証
+///
+/// ## Raises
+///
+/// * `EvaluationError::ParameterTypeMismatch` - 引数の型が一致しない場合
+pub(crate) fn validate_param_type(
+    function_name: &str,
+    param: &ParameterNode,
+    value: &LiteralValue,
+    method_mapper: &MethodMapper,
+    line: usize,
+) -> Result<(), EvaluationError> {
+    // パラメータの型と一致していない
+    // let f: fn = (x: int): Void { return none; };
+    // f(x: 1.0); // ParameterTypeMismatch
+    if !is_value_of_type_with_methods(value.clone(), param.param_type.clone(), method_mapper) {
+        return Err(EvaluationError::ParameterTypeMismatch {
+            function_name: function_name.to_string(),
+            param_name: param.name.clone(),
+            line,
+            expected: param.param_type.to_string(),
+            actual: literal_to_type(value.clone()).to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// 位置引数に対応するパラメータを、パラメータの宣言順に探す
+///
+/// 可変長パラメータ以外のパラメータを使い切った後の位置引数は、可変長パラメータに束縛されます。
+fn search_positional_param(index: usize, params: &[ParameterNode]) -> Option<&ParameterNode> {
+    match params.iter().filter(|param| !param.is_variadic).nth(index) {
+        Some(param) => Some(param),
+        None => params.iter().find(|param| param.is_variadic),
+    }
+}
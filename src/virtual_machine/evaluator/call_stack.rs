@@ -0,0 +1,197 @@
+use crate::virtual_machine::evaluator::evaluation_error::EvaluationError;
+use std::fmt;
+
+/// 関数の呼び出しの深さの上限
+///
+/// 終わらない再帰呼び出しは、上限に達した時点で `EvaluationError::CallDepthExceeded` となります。
+pub(crate) const MAX_CALL_DEPTH: usize = 10_000;
+
+/// 関数の呼び出しごとのフレーム
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct CallFrame {
+    pub(crate) function: String, // 呼び出された関数名 (メソッドの場合は "型名.メソッド名")
+    pub(crate) call_line: usize, // 呼び出し元の行番号
+    pub(crate) line: usize,      // 関数の本体で評価中の行番号
+}
+
+/// 関数の呼び出しのスタック
+///
+/// 関数を呼び出すとフレームを積み、本体の評価を終えると取り除きます。
+/// 積まれたフレームの数は、`MAX_CALL_DEPTH` を超えません。
+/// 本体の評価がエラーで終わった場合は、フレームを取り除かずに、エラーが起きた行番号を記録します。
+/// そのため、プログラムの評価がエラーで終わった後は、エラーが起きた時点の呼び出しの履歴が残ります。
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct CallStack {
+    frames: Vec<CallFrame>,
+}
+
+impl CallStack {
+    pub(crate) fn new() -> Self {
+        CallStack { frames: vec![] }
+    }
+
+    /// 関数の呼び出しのフレームを積み、フレームの番号を返す
+    ///
+    /// ## Raises
+    ///
+    /// * `EvaluationError::CallDepthExceeded` - 呼び出しの深さが上限に達した場合
+    pub(crate) fn push(
+        &mut self,
+        function: &str,
+        call_line: usize,
+    ) -> Result<usize, EvaluationError> {
+        if self.frames.len() >= MAX_CALL_DEPTH {
+            return Err(EvaluationError::CallDepthExceeded {
+                function_name: function.to_string(),
+                max_depth: MAX_CALL_DEPTH,
+                line: call_line,
+            });
+        }
+        self.frames.push(CallFrame {
+            function: function.to_string(),
+            call_line,
+            line: call_line,
+        });
+        Ok(self.frames.len() - 1)
+    }
+
+    /// 関数の本体の評価を終えたフレームを取り除く
+    pub(crate) fn pop(&mut self, frame: usize) {
+        self.frames.truncate(frame);
+    }
+
+    /// 関数の本体の評価がエラーで終わった行番号を、フレームに記録する
+    ///
+    /// より深い呼び出しで起きたエラーの場合、そのフレームは既に記録されて残っています。
+    pub(crate) fn fail_at(&mut self, frame: usize, line: usize) {
+        self.frames[frame].line = line;
+    }
+
+    /// トップレベルで評価中の行番号と合わせて、呼び出しの履歴を作る
+    pub(crate) fn traceback(&self, line: usize) -> Traceback {
+        Traceback {
+            line,
+            frames: self.frames.clone(),
+        }
+    }
+}
+
+/// エラーが起きた時点の呼び出しの履歴
+///
+/// 最も古い呼び出し (トップレベル) から順に表示します。
+/// 再帰呼び出しで同じフレームが続く場合は、一度だけ表示して繰り返した回数を添えます。
+///
+/// ```text
+/// Traceback (most recent call last):
+///   line 9, in <main>
+///   line 5, in outer (called at line 9)
+///   line 2, in inner (called at line 5)
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Traceback {
+    line: usize, // トップレベルで評価中の行番号
+    frames: Vec<CallFrame>,
+}
+
+impl Traceback {
+    /// 関数の呼び出しの中でエラーが起きたか
+    pub(crate) fn is_in_function(&self) -> bool {
+        !self.frames.is_empty()
+    }
+}
+
+impl fmt::Display for Traceback {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Traceback (most recent call last):")?;
+        write!(f, "  line {}, in <main>", self.line)?;
+        let mut index: usize = 0;
+        while let Some(frame) = self.frames.get(index) {
+            write!(
+                f,
+                "\n  line {}, in {} (called at line {})",
+                frame.line, frame.function, frame.call_line
+            )?;
+            let repeated: usize = self.frames[index + 1..]
+                .iter()
+                .take_while(|next| *next == frame)
+                .count();
+            if repeated > 0 {
+                write!(f, "\n  [previous line repeated {} more times]", repeated)?;
+            }
+            index += repeated + 1;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::virtual_machine::evaluator::call_stack::{CallStack, MAX_CALL_DEPTH};
+    use crate::virtual_machine::evaluator::evaluation_error::EvaluationError;
+
+    /// エラーで終わったフレームが残り、呼び出しの履歴として表示されることを確認するテスト
+    #[test]
+    fn test_traceback_keeps_failed_frames() {
+        let mut call_stack: CallStack = CallStack::new();
+        let outer: usize = call_stack.push("outer", 9).unwrap();
+        let helper: usize = call_stack.push("helper", 4).unwrap();
+        call_stack.pop(helper);
+        let inner: usize = call_stack.push("inner", 5).unwrap();
+        call_stack.fail_at(inner, 2);
+        call_stack.fail_at(outer, 5);
+
+        assert_eq!(
+            call_stack.traceback(9).to_string(),
+            [
+                "Traceback (most recent call last):",
+                "  line 9, in <main>",
+                "  line 5, in outer (called at line 9)",
+                "  line 2, in inner (called at line 5)",
+            ]
+            .join("\n")
+        );
+    }
+
+    /// 再帰呼び出しで続く同じフレームが、繰り返した回数とともに一度だけ表示されることを確認するテスト
+    #[test]
+    fn test_traceback_folds_repeated_frames() {
+        let mut call_stack: CallStack = CallStack::new();
+        let run: usize = call_stack.push("run", 9).unwrap();
+        let frames: Vec<usize> = (0..4)
+            .map(|_| call_stack.push("count", 3).unwrap())
+            .collect();
+        for frame in frames.into_iter().rev() {
+            call_stack.fail_at(frame, 3);
+        }
+        call_stack.fail_at(run, 3);
+
+        assert_eq!(
+            call_stack.traceback(9).to_string(),
+            [
+                "Traceback (most recent call last):",
+                "  line 9, in <main>",
+                "  line 3, in run (called at line 9)",
+                "  line 3, in count (called at line 3)",
+                "  [previous line repeated 3 more times]",
+            ]
+            .join("\n")
+        );
+    }
+
+    /// 呼び出しの深さが上限に達するとエラーになることを確認するテスト
+    #[test]
+    fn test_push_fails_at_max_call_depth() {
+        let mut call_stack: CallStack = CallStack::new();
+        for _ in 0..MAX_CALL_DEPTH {
+            assert!(call_stack.push("count", 3).is_ok());
+        }
+        assert_eq!(
+            call_stack.push("count", 3),
+            Err(EvaluationError::CallDepthExceeded {
+                function_name: "count".to_string(),
+                max_depth: MAX_CALL_DEPTH,
+                line: 3,
+            })
+        );
+    }
+}
@@ -0,0 +1,523 @@
+use crate::virtual_machine::ast::{
+    BinaryOperationNode, BlockNode, ExpressionNode, FunctionDeclarationNode, IfNode, ImplNode,
+    LiteralNode, LiteralValue, Statement, TypeCastNode, TypeTestNode, VariableDeclarationNode, AST,
+};
+use crate::virtual_machine::evaluator::core::{is_value_of_type, literal_to_type};
+use crate::virtual_machine::evaluator::evaluation_error::EvaluationError;
+use crate::virtual_machine::evaluator::mapper::function_mapper::FunctionMapper;
+use crate::virtual_machine::evaluator::mapper::variable_mapper::VariableMapper;
+use crate::virtual_machine::evaluator::statement_evaluator::expression_evaluator::evaluate_expression;
+use crate::virtual_machine::evaluator::Evaluator;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+/// 定数のスコープのスタック
+///
+/// 変数もシャドーイングを正しく扱うために `None` として登録します。
+type ConstantScopes = Vec<HashMap<String, Option<LiteralValue>>>;
+
+/// 定数宣言の初期化式を評価前に値へ畳み込む
+///
+/// 定数の初期化式は定数式でなければなりません。定数式は以下のいずれかです。
+///
+/// - リテラル: `5`
+/// - 定数式同士の四則演算: `MAX_RETRIES * 10`
+/// - 定数式の型キャスト: `MAX_RETRIES as int -> float`
+/// - 定数式の型の検査: `MAX_RETRIES is int`
+/// - 定数式による条件分岐: `if DEBUG { 1 } else { 5 }`
+/// - それより前に宣言された定数の参照: `MAX_RETRIES`
+///
+/// 畳み込まれた定数宣言の初期化式はリテラルに置き換えられます。
+/// 定数はスコープごとに管理されるため、関数の本体では関数内で宣言された定数のみ参照できます。
+/// ブロックの中では外側の定数を参照でき、同じ名前の定数や変数を宣言するとシャドーイングされます。
+/// 関数の中で宣言された関数の本体では、宣言された位置から見える定数も参照できます。
+///
+/// ## Raises
+///
+/// * `EvaluationError::NonConstantExpression` - 初期化式が定数式でない場合
+/// * `EvaluationError::ConstantTypeMismatch` - 畳み込んだ値が宣言された型に当てはまらない場合
+/// * `EvaluationError::ReassignmentError` - 同じスコープで同じ名前の定数が宣言された場合
+pub(crate) fn fold_constants(ast: AST) -> Result<AST, EvaluationError> {
+    let mut folded_ast: AST = AST::new();
+    let mut constants: ConstantScopes = vec![HashMap::new()];
+    for (line, statement) in ast.statements {
+        let folded_statement: Statement = fold_statement(statement, &mut constants, line, false)?;
+        folded_ast.push_statement(line, folded_statement);
+    }
+    Ok(folded_ast)
+}
+
+fn fold_statement(
+    statement: Statement,
+    constants: &mut ConstantScopes,
+    line: usize,
+    is_function_body: bool,
+) -> Result<Statement, EvaluationError> {
+    match statement {
+        Statement::DeclarationOfConstant(node) => {
+            if innermost_scope(constants).contains_key(&node.name) {
+                return Err(EvaluationError::ReassignmentError {
+                    name: node.name.clone(),
+                    line,
+                });
+            }
+
+            let value: LiteralValue =
+                fold_constant_expression(&node.name, *node.value, constants, line)?;
+
+            // const X: int = 1.0;  # ConstantTypeMismatch
+            if !is_value_of_type(value.clone(), node.var_type.clone()) {
+                return Err(EvaluationError::ConstantTypeMismatch {
+                    name: node.name.clone(),
+                    line,
+                    expected: node.var_type.to_string(),
+                    actual: literal_to_type(value).to_string(),
+                });
+            }
+
+            innermost_scope(constants).insert(node.name.clone(), Some(value.clone()));
+            Ok(Statement::DeclarationOfConstant(Box::new(
+                VariableDeclarationNode {
+                    name: node.name,
+                    var_type: node.var_type,
+                    value: Box::new(ExpressionNode::Literal(Box::new(LiteralNode { value }))),
+                },
+            )))
+        }
+        Statement::DeclarationOfFunction(node) => {
+            let node: FunctionDeclarationNode = Rc::unwrap_or_clone(node);
+            // 関数の本体は新しいスコープとして畳み込む
+            // 関数の中で宣言された関数は、外側のスコープの定数を引き継ぐ
+            let mut function_constants: ConstantScopes = match is_function_body {
+                true => constants.clone(),
+                false => vec![],
+            };
+            // パラメータは外側の同名の定数をシャドーイングする
+            function_constants.push(
+                node.params
+                    .iter()
+                    .map(|param| (param.name.clone(), None))
+                    .collect(),
+            );
+            let mut body: Vec<(usize, Statement)> = vec![];
+            for (statement_line, statement) in node.body {
+                body.push((
+                    statement_line,
+                    fold_statement(statement, &mut function_constants, statement_line, true)?,
+                ));
+            }
+            Ok(Statement::DeclarationOfFunction(Rc::new(
+                FunctionDeclarationNode { body, ..node },
+            )))
+        }
+        Statement::DeclarationOfVariable(node) => {
+            // 変数は定数式から参照できないが、外側の同名の定数をシャドーイングする
+            innermost_scope(constants).insert(node.name.clone(), None);
+            Ok(Statement::DeclarationOfVariable(node))
+        }
+        Statement::Block(block) => {
+            // ブロックは新しいスコープとして畳み込む
+            constants.push(HashMap::new());
+            let mut statements: Vec<(usize, Statement)> = vec![];
+            for (line, statement) in block.statements {
+                match fold_statement(statement, constants, line, is_function_body) {
+                    Ok(statement) => statements.push((line, statement)),
+                    Err(error) => {
+                        constants.pop();
+                        return Err(error);
+                    }
+                }
+            }
+            constants.pop();
+            Ok(Statement::Block(Box::new(BlockNode { statements })))
+        }
+        Statement::Impl(node) => {
+            // メソッドはグローバルに宣言された関数と同じく畳み込む
+            let mut methods: Vec<Rc<FunctionDeclarationNode>> = vec![];
+            for method in node.methods {
+                match fold_statement(
+                    Statement::DeclarationOfFunction(method),
+                    constants,
+                    line,
+                    false,
+                )? {
+                    Statement::DeclarationOfFunction(method) => methods.push(method),
+                    _ => unreachable!(),
+                }
+            }
+            Ok(Statement::Impl(Box::new(ImplNode {
+                target: node.target,
+                methods,
+            })))
+        }
+        Statement::Export(declaration) => Ok(Statement::Export(Box::new(fold_statement(
+            *declaration,
+            constants,
+            line,
+            is_function_body,
+        )?))),
+        _ => Ok(statement),
+    }
+}
+
+/// 一番内側のスコープを取得する
+fn innermost_scope(constants: &mut ConstantScopes) -> &mut HashMap<String, Option<LiteralValue>> {
+    constants
+        .last_mut()
+        .expect("ConstantScopes always has the global scope")
+}
+
+/// 定数式を評価して値を求める
+///
+/// 定数の参照をリテラルに置き換えた上で、通常の式として評価します。
+fn fold_constant_expression(
+    name: &str,
+    expression: ExpressionNode,
+    constants: &ConstantScopes,
+    line: usize,
+) -> Result<LiteralValue, EvaluationError> {
+    let constant_expression: ExpressionNode =
+        substitute_constants(name, expression, constants, line)?;
+
+    // 定数式は関数や変数を参照しないので、空の環境で評価できる
+    let mut evaluator: Evaluator =
+        Evaluator::new(AST::new(), FunctionMapper::new(), VariableMapper::new());
+    evaluator.line = line;
+    evaluate_expression(&mut evaluator, &constant_expression)
+}
+
+/// 定数の参照をリテラルに置き換える
+///
+/// 定数式でない式 (関数呼び出しや変数の参照) が含まれる場合はエラーを返します。
+fn substitute_constants(
+    name: &str,
+    expression: ExpressionNode,
+    constants: &ConstantScopes,
+    line: usize,
+) -> Result<ExpressionNode, EvaluationError> {
+    match expression {
+        ExpressionNode::Literal(_) => Ok(expression),
+        ExpressionNode::BinaryOperation(node) => Ok(ExpressionNode::BinaryOperation(Box::new(
+            BinaryOperationNode {
+                left: Box::new(substitute_constants(name, *node.left, constants, line)?),
+                operator: node.operator,
+                right: Box::new(substitute_constants(name, *node.right, constants, line)?),
+            },
+        ))),
+        ExpressionNode::TypeCast(node) => Ok(ExpressionNode::TypeCast(Box::new(TypeCastNode {
+            from_type: node.from_type,
+            to_type: node.to_type,
+            is_checked: node.is_checked,
+            expression: Box::new(substitute_constants(
+                name,
+                *node.expression,
+                constants,
+                line,
+            )?),
+        }))),
+        ExpressionNode::TypeTest(node) => Ok(ExpressionNode::TypeTest(Box::new(TypeTestNode {
+            expression: Box::new(substitute_constants(
+                name,
+                *node.expression,
+                constants,
+                line,
+            )?),
+            tested_type: node.tested_type,
+        }))),
+        ExpressionNode::If(node) => Ok(ExpressionNode::If(Box::new(IfNode {
+            condition: Box::new(substitute_constants(
+                name,
+                *node.condition,
+                constants,
+                line,
+            )?),
+            then_branch: Box::new(substitute_constants(
+                name,
+                *node.then_branch,
+                constants,
+                line,
+            )?),
+            else_branch: Box::new(substitute_constants(
+                name,
+                *node.else_branch,
+                constants,
+                line,
+            )?),
+        }))),
+        ExpressionNode::CallOfVariable(node) => match constants
+            .iter()
+            .rev()
+            .find_map(|scope| scope.get(&node.name))
+        {
+            Some(Some(value)) => Ok(ExpressionNode::Literal(Box::new(LiteralNode {
+                value: value.clone(),
+            }))),
+            // let x: int = 1;
+            // const X: int = x;  # NonConstantExpression
+            _ => Err(EvaluationError::NonConstantExpression {
+                name: name.to_string(),
+                line,
+            }),
+        },
+        // const X: int = f();  # NonConstantExpression
+        // const X: fn = f(x: 1, ...);  # NonConstantExpression
+        // const X: string = h.url();  # NonConstantExpression
+        ExpressionNode::CallOfFunction(_)
+        | ExpressionNode::PartialApplication(_)
+        | ExpressionNode::CallOfMethod(_) => Err(EvaluationError::NonConstantExpression {
+            name: name.to_string(),
+            line,
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::virtual_machine::ast::{
+        BinaryOperationNode, BinaryOperator, BlockNode, ExpressionNode, FunctionCallNode,
+        FunctionDeclarationNode, LiteralNode, LiteralValue, ParameterNode, Statement, Type,
+        TypeCastNode, VariableCallNode, VariableDeclarationNode, AST,
+    };
+    use crate::virtual_machine::evaluator::constant_folder::fold_constants;
+    use crate::virtual_machine::evaluator::evaluation_error::EvaluationError;
+    use std::rc::Rc;
+
+    fn create_constant_declaration(name: &str, var_type: Type, value: ExpressionNode) -> Statement {
+        Statement::DeclarationOfConstant(Box::new(VariableDeclarationNode {
+            name: name.to_string(),
+            var_type,
+            value: Box::new(value),
+        }))
+    }
+
+    fn literal(value: LiteralValue) -> ExpressionNode {
+        ExpressionNode::Literal(Box::new(LiteralNode { value }))
+    }
+
+    /// 他の定数を参照する定数の初期化式を畳み込めることを確認します。
+    ///
+    /// const MAX_RETRIES: int = 5;
+    /// const TIMEOUT: float = (MAX_RETRIES * 10) as int -> float;  # 50.0
+    #[test]
+    fn test_fold_constants_with_other_constant() {
+        let mut ast: AST = AST::new();
+        ast.push_statement(
+            1,
+            create_constant_declaration(
+                "MAX_RETRIES",
+                Type::Integer,
+                literal(LiteralValue::Integer(5)),
+            ),
+        );
+        ast.push_statement(
+            2,
+            create_constant_declaration(
+                "TIMEOUT",
+                Type::Float,
+                ExpressionNode::TypeCast(Box::new(TypeCastNode {
+                    from_type: Some(Type::Integer),
+                    to_type: Type::Float,
+                    is_checked: false,
+                    expression: Box::new(ExpressionNode::BinaryOperation(Box::new(
+                        BinaryOperationNode {
+                            left: Box::new(ExpressionNode::CallOfVariable(Box::new(
+                                VariableCallNode {
+                                    name: "MAX_RETRIES".to_string(),
+                                },
+                            ))),
+                            operator: BinaryOperator::Multiply,
+                            right: Box::new(literal(LiteralValue::Integer(10))),
+                        },
+                    ))),
+                })),
+            ),
+        );
+
+        let folded_ast: AST = fold_constants(ast).unwrap();
+
+        assert_eq!(
+            folded_ast.statements[1].1,
+            create_constant_declaration("TIMEOUT", Type::Float, literal(LiteralValue::Float(50.0)))
+        );
+    }
+
+    /// 変数を参照する定数の初期化式はエラーになることを確認します。
+    ///
+    /// const X: int = x;  # NonConstantExpression
+    #[test]
+    fn test_fold_constants_with_variable() {
+        let mut ast: AST = AST::new();
+        ast.push_statement(
+            1,
+            create_constant_declaration(
+                "X",
+                Type::Integer,
+                ExpressionNode::CallOfVariable(Box::new(VariableCallNode {
+                    name: "x".to_string(),
+                })),
+            ),
+        );
+
+        assert_eq!(
+            fold_constants(ast).map(|ast| ast.statements),
+            Err(EvaluationError::NonConstantExpression {
+                name: "X".to_string(),
+                line: 1
+            })
+        );
+    }
+
+    /// 関数の本体の定数の初期化式のエラーは、その文の行番号で報告されることを確認します。
+    ///
+    /// let f: fn = (x: int): int {
+    ///     const X: int = x;  # NonConstantExpression
+    ///     return X;
+    /// };
+    #[test]
+    fn test_fold_constants_in_function_body() {
+        let mut ast: AST = AST::new();
+        ast.push_statement(
+            1,
+            Statement::DeclarationOfFunction(Rc::new(FunctionDeclarationNode {
+                name: "f".to_string(),
+                params: vec![ParameterNode {
+                    name: "x".to_string(),
+                    param_type: Type::Integer,
+                    default_value: None,
+                    is_variadic: false,
+                }],
+                return_type: Type::Integer,
+                body: vec![
+                    (
+                        2,
+                        create_constant_declaration(
+                            "X",
+                            Type::Integer,
+                            ExpressionNode::CallOfVariable(Box::new(VariableCallNode {
+                                name: "x".to_string(),
+                            })),
+                        ),
+                    ),
+                    (
+                        3,
+                        Statement::Return(Box::new(ExpressionNode::CallOfVariable(Box::new(
+                            VariableCallNode {
+                                name: "X".to_string(),
+                            },
+                        )))),
+                    ),
+                ],
+            })),
+        );
+
+        assert_eq!(
+            fold_constants(ast).map(|ast| ast.statements),
+            Err(EvaluationError::NonConstantExpression {
+                name: "X".to_string(),
+                line: 2
+            })
+        );
+    }
+
+    /// 関数呼び出しを含む定数の初期化式はエラーになることを確認します。
+    ///
+    /// const X: int = 1 + f();  # NonConstantExpression
+    #[test]
+    fn test_fold_constants_with_function_call() {
+        let mut ast: AST = AST::new();
+        ast.push_statement(
+            1,
+            create_constant_declaration(
+                "X",
+                Type::Integer,
+                ExpressionNode::BinaryOperation(Box::new(BinaryOperationNode {
+                    left: Box::new(literal(LiteralValue::Integer(1))),
+                    operator: BinaryOperator::Add,
+                    right: Box::new(ExpressionNode::CallOfFunction(Box::new(FunctionCallNode {
+                        name: "f".to_string(),
+                        arguments: vec![],
+                    }))),
+                })),
+            ),
+        );
+
+        assert_eq!(
+            fold_constants(ast).map(|ast| ast.statements),
+            Err(EvaluationError::NonConstantExpression {
+                name: "X".to_string(),
+                line: 1
+            })
+        );
+    }
+
+    /// 畳み込んだ値の型が宣言された型と異なる場合はエラーになることを確認します。
+    ///
+    /// const X: int = 1.0;  # ConstantTypeMismatch
+    #[test]
+    fn test_fold_constants_type_mismatch() {
+        let mut ast: AST = AST::new();
+        ast.push_statement(
+            1,
+            create_constant_declaration("X", Type::Integer, literal(LiteralValue::Float(1.0))),
+        );
+
+        assert_eq!(
+            fold_constants(ast).map(|ast| ast.statements),
+            Err(EvaluationError::ConstantTypeMismatch {
+                name: "X".to_string(),
+                line: 1,
+                expected: "int".to_string(),
+                actual: "float".to_string(),
+            })
+        );
+    }
+
+    /// ブロックの中で外側の定数をシャドーイングできることを確認します。
+    ///
+    /// const X: int = 1;
+    /// { const X: int = X + 1; }  # 2
+    #[test]
+    fn test_fold_constants_shadowing_in_block() {
+        let mut ast: AST = AST::new();
+        ast.push_statement(
+            1,
+            create_constant_declaration("X", Type::Integer, literal(LiteralValue::Integer(1))),
+        );
+        ast.push_statement(
+            2,
+            Statement::Block(Box::new(BlockNode {
+                statements: vec![(
+                    2,
+                    create_constant_declaration(
+                        "X",
+                        Type::Integer,
+                        ExpressionNode::BinaryOperation(Box::new(BinaryOperationNode {
+                            left: Box::new(ExpressionNode::CallOfVariable(Box::new(
+                                VariableCallNode {
+                                    name: "X".to_string(),
+                                },
+                            ))),
+                            operator: BinaryOperator::Add,
+                            right: Box::new(literal(LiteralValue::Integer(1))),
+                        })),
+                    ),
+                )],
+            })),
+        );
+
+        let folded: AST = fold_constants(ast).unwrap();
+        assert_eq!(
+            folded.statements[1].1,
+            Statement::Block(Box::new(BlockNode {
+                statements: vec![(
+                    2,
+                    create_constant_declaration(
+                        "X",
+                        Type::Integer,
+                        literal(LiteralValue::Integer(2))
+                    )
+                )],
+            }))
+        );
+    }
+}
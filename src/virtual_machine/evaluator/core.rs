@@ -1,13 +1,18 @@
 #[allow(unused_imports)]
 use crate::virtual_machine::ast::AST;
 #[allow(unused_imports)]
-use crate::virtual_machine::ast::{Statement, Type};
+use crate::virtual_machine::ast::{LiteralValue, Statement, Type};
 #[allow(unused_imports)]
 use crate::virtual_machine::evaluator::mapper::function_mapper::FunctionMapper;
+use crate::virtual_machine::evaluator::mapper::method_mapper::MethodMapper;
 #[allow(unused_imports)]
 use crate::virtual_machine::evaluator::mapper::variable_mapper::VariableMapper;
 #[allow(unused_imports)]
 use crate::virtual_machine::evaluator::Evaluator;
+#[allow(unused_imports)]
+use crate::virtual_machine::parser::Parser;
+#[allow(unused_imports)]
+use crate::virtual_machine::scanner::Scanner;
 
 #[cfg(test)]
 pub(crate) fn initialize_evaluator_with_custom_ast(statements: Vec<Statement>) -> Evaluator {
@@ -18,12 +23,215 @@ pub(crate) fn initialize_evaluator_with_custom_ast(statements: Vec<Statement>) -
     Evaluator::new(ast, FunctionMapper::new(), VariableMapper::new())
 }
 
+/// ソースコードを字句解析と構文解析して、ASTを作る
+#[cfg(test)]
+pub(crate) fn parse_source(source_code: &str) -> AST {
+    let source_code_vector: Vec<String> =
+        source_code.lines().map(|line| line.to_string()).collect();
+    let tokens = Scanner::new(source_code.to_string(), source_code_vector)
+        .scan()
+        .unwrap();
+    Parser::new(tokens).parse().unwrap()
+}
+
 pub(crate) fn type_to_string(t: Type) -> String {
     match t {
         Type::Float => "float".to_string(),
         Type::Function => "function".to_string(),
         Type::Integer => "int".to_string(),
         Type::String => "string".to_string(),
+        Type::Boolean => "bool".to_string(),
         Type::Void => "void".to_string(),
+        Type::List(element_type) => format!("[{}]", type_to_string(*element_type)),
+        Type::Optional(inner_type) => format!("{}?", type_to_string(*inner_type)),
+        Type::Union(member_types) => member_types
+            .into_iter()
+            .map(type_to_string)
+            .collect::<Vec<String>>()
+            .join(" | "),
+        Type::Any => "any".to_string(),
+        Type::Newtype(name, _) => name,
+        Type::Interface(name, _) => name,
+        Type::Infer => "infer".to_string(),
+    }
+}
+
+/// リテラルの値から型を求める
+///
+/// リストの場合は、先頭の要素の型を要素の型とします。
+pub(crate) fn literal_to_type(value: LiteralValue) -> Type {
+    match value {
+        LiteralValue::Integer(_) => Type::Integer,
+        LiteralValue::Float(_) => Type::Float,
+        LiteralValue::String(_) => Type::String,
+        LiteralValue::Boolean(_) => Type::Boolean,
+        LiteralValue::None => Type::Void,
+        LiteralValue::Function(_) => Type::Function,
+        LiteralValue::Newtype(name, value) => {
+            Type::Newtype(name, Box::new(literal_to_type(*value)))
+        }
+        // 空のリストの要素の型は決まらないため、Void型として扱う
+        LiteralValue::List(values) => Type::List(Box::new(match values.first() {
+            Some(value) => literal_to_type(value.clone()),
+            None => Type::Void,
+        })),
+    }
+}
+
+/// 値が型に当てはまるかを判定する
+///
+/// 値の型が期待する型の部分型であれば、値は型に当てはまります。
+/// Optional型には、内側の型の値と `none` が当てはまります。
+/// リスト型には、全ての要素が要素の型に当てはまるリストが当てはまります。
+pub(crate) fn is_value_of_type(value: LiteralValue, expected: Type) -> bool {
+    match (value, expected) {
+        // リストは、全ての要素が要素の型に当てはまるかを検査する
+        // 空のリストは、どの要素の型のリストにも当てはまる
+        (LiteralValue::List(values), Type::List(element_type)) => values
+            .into_iter()
+            .all(|value| is_value_of_type(value, *element_type.clone())),
+        (value, Type::Optional(inner_type)) => {
+            value == LiteralValue::None || is_value_of_type(value, *inner_type)
+        }
+        (value, Type::Union(member_types)) => member_types
+            .into_iter()
+            .any(|member_type| is_value_of_type(value.clone(), member_type)),
+        (value, expected) => is_subtype(literal_to_type(value), expected),
+    }
+}
+
+/// 実装されたメソッドも考慮して、値が型に当てはまるかを判定する
+///
+/// interface型には、interfaceの全てのメソッドを実装した型の値が当てはまります。
+/// それ以外の型は `is_value_of_type` と同じです。
+pub(crate) fn is_value_of_type_with_methods(
+    value: LiteralValue,
+    expected: Type,
+    method_mapper: &MethodMapper,
+) -> bool {
+    match expected {
+        Type::Interface(_, signatures) => {
+            method_mapper.conforms(&literal_to_type(value).to_string(), &signatures)
+        }
+        Type::Optional(inner_type) => {
+            value == LiteralValue::None
+                || is_value_of_type_with_methods(value, *inner_type, method_mapper)
+        }
+        Type::Union(member_types) => member_types.into_iter().any(|member_type| {
+            is_value_of_type_with_methods(value.clone(), member_type, method_mapper)
+        }),
+        expected => is_value_of_type(value, expected),
+    }
+}
+
+/// 型 `sub` が型 `sup` の部分型であるかを判定する
+///
+/// - `any` は全ての型の上位の型です
+/// - Union型は、全ての型が上位の型の部分型であれば部分型です
+/// - Union型の上位の型は、いずれかの型の上位の型です
+/// - Optional型 `T?` は、`T | void` と同じように扱います
+/// - リスト型は、要素の型が部分型であれば部分型です
+/// - newtypeで宣言された型は、同じ型名の型の部分型です。元の型とは部分型の関係を持ちません
+pub(crate) fn is_subtype(sub: Type, sup: Type) -> bool {
+    match (sub, sup) {
+        (_, Type::Any) => true,
+        (Type::Newtype(sub_name, _), Type::Newtype(sup_name, _)) => sub_name == sup_name,
+        (sub, sup) if sub == sup => true,
+        (Type::Union(member_types), sup) => member_types
+            .into_iter()
+            .all(|member_type| is_subtype(member_type, sup.clone())),
+        (Type::Optional(inner_type), sup) => {
+            is_subtype(*inner_type, sup.clone()) && is_subtype(Type::Void, sup)
+        }
+        (sub, Type::Union(member_types)) => member_types
+            .into_iter()
+            .any(|member_type| is_subtype(sub.clone(), member_type)),
+        (sub, Type::Optional(inner_type)) => sub == Type::Void || is_subtype(sub, *inner_type),
+        (Type::List(sub_element), Type::List(sup_element)) => {
+            is_subtype(*sub_element, *sup_element)
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Union型とany型の部分型の関係を確認するテスト
+    #[test]
+    fn test_is_subtype_with_union_and_any() {
+        let int_or_string: Type = Type::Union(vec![Type::Integer, Type::String]);
+
+        assert!(is_subtype(Type::Integer, int_or_string.clone()));
+        assert!(!is_subtype(Type::Float, int_or_string.clone()));
+        assert!(is_subtype(int_or_string.clone(), Type::Any));
+        assert!(!is_subtype(Type::Any, int_or_string.clone()));
+        assert!(is_subtype(
+            Type::Union(vec![Type::String, Type::Integer]),
+            int_or_string.clone()
+        ));
+        assert!(is_subtype(
+            Type::Optional(Box::new(Type::Integer)),
+            Type::Union(vec![Type::Integer, Type::Void])
+        ));
+        assert!(is_subtype(
+            Type::List(Box::new(Type::Integer)),
+            Type::List(Box::new(int_or_string))
+        ));
+    }
+
+    /// newtypeで宣言された型が、元の型と区別されることを確認するテスト
+    #[test]
+    fn test_is_value_of_type_with_newtype() {
+        let user_id: Type = Type::Newtype("UserId".to_string(), Box::new(Type::Integer));
+        let port: Type = Type::Newtype("Port".to_string(), Box::new(Type::Integer));
+        let value: LiteralValue =
+            LiteralValue::Newtype("UserId".to_string(), Box::new(LiteralValue::Integer(1)));
+
+        assert!(is_value_of_type(value.clone(), user_id.clone()));
+        assert!(!is_value_of_type(value.clone(), port));
+        assert!(!is_value_of_type(value.clone(), Type::Integer));
+        assert!(!is_value_of_type(LiteralValue::Integer(1), user_id));
+        assert!(is_value_of_type(value, Type::Any));
+    }
+
+    /// 空のリストが、どの要素の型のリストにも当てはまることを確認するテスト
+    #[test]
+    fn test_is_value_of_type_with_empty_list() {
+        let empty: LiteralValue = LiteralValue::List(vec![]);
+
+        assert!(is_value_of_type(
+            empty.clone(),
+            Type::List(Box::new(Type::String))
+        ));
+        assert!(is_value_of_type(
+            empty.clone(),
+            Type::List(Box::new(Type::Integer))
+        ));
+        assert!(!is_value_of_type(empty, Type::String));
+    }
+
+    /// 値がUnion型とany型に当てはまるかを確認するテスト
+    #[test]
+    fn test_is_value_of_type_with_union_and_any() {
+        let int_or_string: Type = Type::Union(vec![Type::Integer, Type::String]);
+
+        assert!(is_value_of_type(
+            LiteralValue::Integer(1),
+            int_or_string.clone()
+        ));
+        assert!(!is_value_of_type(
+            LiteralValue::Boolean(true),
+            int_or_string.clone()
+        ));
+        assert!(is_value_of_type(LiteralValue::None, Type::Any));
+        assert!(is_value_of_type(
+            LiteralValue::List(vec![
+                LiteralValue::Integer(1),
+                LiteralValue::String("a".to_string())
+            ]),
+            Type::List(Box::new(int_or_string))
+        ));
     }
 }
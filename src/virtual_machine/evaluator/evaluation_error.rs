@@ -4,6 +4,38 @@ use thiserror::Error;
 pub enum EvaluationError {
     #[error("Function {name} not found at line {line}")]
     FunctionNotFound { name: String, line: usize },
+    #[error("No overload of function {name} matches the arguments at line {line}")]
+    NoMatchingOverload { name: String, line: usize },
+    #[error("Call of function {name} is ambiguous: {candidates} overloads match the arguments at line {line}")]
+    AmbiguousFunctionCall {
+        name: String,
+        candidates: usize,
+        line: usize,
+    },
+    #[error("{name} is not a function but {actual} at line {line}")]
+    NotCallable {
+        name: String,
+        actual: String,
+        line: usize,
+    },
+    #[error("Method {name} is not implemented for {type_name} at line {line}")]
+    MethodNotFound {
+        type_name: String,
+        name: String,
+        line: usize,
+    },
+    #[error("Method {name} is already implemented for {type_name} at line {line}")]
+    DuplicateMethod {
+        type_name: String,
+        name: String,
+        line: usize,
+    },
+    #[error("Call of function {function_name} exceeds the maximum call depth {max_depth} at line {line}")]
+    CallDepthExceeded {
+        function_name: String,
+        max_depth: usize,
+        line: usize,
+    },
     #[error("Variable {name} not found at line {line}")]
     VariableNotFound { name: String, line: usize },
     #[error("Variable {name} is already defined. You cannot reassign a variable at line {line}")]
@@ -14,6 +46,26 @@ pub enum EvaluationError {
         param_name: String,
         line: usize,
     },
+    #[error(
+        "Argument {param_name} is passed more than once to function {function_name} at line {line}"
+    )]
+    DuplicateArgument {
+        function_name: String,
+        param_name: String,
+        line: usize,
+    },
+    #[error("Argument {param_name} of function {function_name} cannot be spread because it is not a variadic parameter at line {line}")]
+    InvalidSpreadArgument {
+        function_name: String,
+        param_name: String,
+        line: usize,
+    },
+    #[error("Argument {param_name} of function {function_name} is missing at line {line}")]
+    MissingArgument {
+        function_name: String,
+        param_name: String,
+        line: usize,
+    },
     #[error("Parameter type mismatch at line {line}. Function {function_name} expects {expected} but {actual} is given.")]
     ParameterTypeMismatch {
         function_name: String,
@@ -22,12 +74,27 @@ pub enum EvaluationError {
         expected: String,
         actual: String,
     },
+    #[error("Return type mismatch at line {line}. Function {function_name} expects to return {expected} but {actual} is returned.")]
+    ReturnTypeMismatch {
+        function_name: String,
+        line: usize,
+        expected: String,
+        actual: String,
+    },
     #[error("Type Cast Error at line {line}. from_type: {from_type}, to_type: {to_type}")]
     InvalidTypeCast {
         line: usize,
         from_type: String,
         to_type: String,
     },
+    #[error(
+        "Type Cast Error at line {line}. The value is {actual}, but the cast expects {expected}"
+    )]
+    TypeCastSourceMismatch {
+        line: usize,
+        expected: String,
+        actual: String,
+    },
     #[error("Type Cast Error at line {line}. from_type: {from_type}, value: {value}")]
     FailedToTypeCast {
         line: usize,
@@ -35,6 +102,35 @@ pub enum EvaluationError {
         to_type: String,
         value: String,
     },
+    #[error("Binary operation error at line {line}. Operator {operator} cannot be applied to {left_type} and {right_type}")]
+    InvalidBinaryOperation {
+        line: usize,
+        operator: String,
+        left_type: String,
+        right_type: String,
+    },
+    #[error("Condition must be bool but {actual} is given at line {line}")]
+    InvalidCondition { actual: String, line: usize },
+    #[error("Division by zero at line {line}")]
+    DivisionByZero { line: usize },
+    #[error("Integer overflow at line {line}")]
+    IntegerOverflow { line: usize },
+    #[error("Constant {name} must be initialized with a constant expression at line {line}")]
+    NonConstantExpression { name: String, line: usize },
+    #[error("Variable type mismatch at line {line}. Variable {name} expects {expected} but {actual} is given.")]
+    VariableTypeMismatch {
+        name: String,
+        line: usize,
+        expected: String,
+        actual: String,
+    },
+    #[error("Constant type mismatch at line {line}. Constant {name} expects {expected} but {actual} is given.")]
+    ConstantTypeMismatch {
+        name: String,
+        line: usize,
+        expected: String,
+        actual: String,
+    },
     #[error("Argument length error at line {line}. Function {function_name} expects {expected} arguments, but {actual} arguments are given.")]
     ArgumentLengthError {
         function_name: String,
@@ -1,2 +1,3 @@
 pub mod function_mapper;
+pub mod method_mapper;
 pub mod variable_mapper;
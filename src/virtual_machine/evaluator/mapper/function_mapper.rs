@@ -1,18 +1,55 @@
 use crate::virtual_machine::ast::FunctionDeclarationNode;
 use crate::virtual_machine::evaluator::evaluation_error::EvaluationError;
 use std::collections::HashMap;
+use std::rc::Rc;
 
+/// 関数の定義をスコープごとに管理する
+///
+/// スコープはブロックに入るたびに積まれ、ブロックを抜けると取り除かれます。
+/// 関数は内側のスコープから順に探されます。
+///
+/// 同じスコープには、パラメータの名前か型が異なる同名の関数 (オーバーロード) を宣言できます。
+/// どのオーバーロードを呼び出すかは、関数呼び出しの評価時に引数から決まります。
 pub(crate) struct FunctionMapper {
-    map: HashMap<String, FunctionDeclarationNode>,
+    scopes: Vec<HashMap<String, Vec<Rc<FunctionDeclarationNode>>>>,
 }
 
 impl FunctionMapper {
     pub fn new() -> Self {
         FunctionMapper {
-            map: Default::default(),
+            scopes: vec![Default::default()],
         }
     }
 
+    /// ブロックに入る際に、新しいスコープを積む
+    pub fn push_scope(&mut self) {
+        self.scopes.push(Default::default());
+    }
+
+    /// ブロックを抜ける際に、一番内側のスコープを取り除く
+    pub fn pop_scope(&mut self) {
+        self.scopes.pop();
+    }
+
+    /// 指定した深さまでのスコープを複製する
+    ///
+    /// 関数の中で宣言された関数は、宣言された位置から見えるスコープを引き継いで評価されます。
+    pub fn capture(&self, depth: usize) -> Self {
+        FunctionMapper {
+            scopes: self.scopes[..depth].to_vec(),
+        }
+    }
+
+    /// 関数が宣言されたスコープの深さを求める
+    ///
+    /// 一番外側のスコープの深さは1です。見つからない場合は `None` を返します。
+    pub fn depth_of(&self, name: &str) -> Option<usize> {
+        self.scopes
+            .iter()
+            .rposition(|scope| scope.contains_key(name))
+            .map(|index| index + 1)
+    }
+
     /// 関数を取得する
     ///
     /// # Arguments
@@ -20,15 +57,19 @@ impl FunctionMapper {
     /// - `line` - 行番号
     ///
     /// # Returns
-    /// - `Result<FunctionDeclarationNode, EvaluationError>` - Map結果
-    ///   - `FunctionDeclarationNode` - 関数の定義
+    /// - `Result<&[Rc<FunctionDeclarationNode>], EvaluationError>` - Map結果
+    ///   - `&[Rc<FunctionDeclarationNode>]` - 一番内側のスコープで宣言された、同名の関数の定義 (宣言順)
     ///   - `EvaluationError` - 評価エラー
     ///
     /// # Raises
     /// - `EvaluationError::FunctionNotFound` - 関数が見つからない場合
-    pub fn get(&self, name: &str, line: usize) -> Result<FunctionDeclarationNode, EvaluationError> {
-        match self.map.get(name) {
-            Some(value) => Ok(value.clone()),
+    pub fn get(
+        &self,
+        name: &str,
+        line: usize,
+    ) -> Result<&[Rc<FunctionDeclarationNode>], EvaluationError> {
+        match self.scopes.iter().rev().find_map(|scope| scope.get(name)) {
+            Some(value) => Ok(value),
             None => Err(EvaluationError::FunctionNotFound {
                 name: name.to_string(),
                 line,
@@ -46,29 +87,56 @@ impl FunctionMapper {
     /// - `()` - 正常終了
     ///
     /// # Raises
-    /// - `EvaluationError::ReassignmentError` - 関数の再定義が行われた場合
+    /// - `EvaluationError::ReassignmentError` - 同じスコープで、パラメータの名前と型が全て同じ関数が宣言された場合
     pub fn set(
         &mut self,
         line: usize,
-        definition: FunctionDeclarationNode,
+        definition: Rc<FunctionDeclarationNode>,
     ) -> Result<(), EvaluationError> {
         let name: String = definition.name.clone();
-        if self.map.contains_key(&name) {
-            return Err(EvaluationError::ReassignmentError {
-                name: name.clone(),
-                line,
-            });
+        let scope: &mut HashMap<String, Vec<Rc<FunctionDeclarationNode>>> = self
+            .scopes
+            .last_mut()
+            .expect("FunctionMapper always has the global scope");
+        let overloads: &mut Vec<Rc<FunctionDeclarationNode>> =
+            scope.entry(name.clone()).or_default();
+
+        // パラメータの名前と型が全て同じ関数は区別できない
+        // let open: fn = (path: string): void { return none; };
+        // let open: fn = (path: string): int { return 0; };  # Error
+        if overloads
+            .iter()
+            .any(|overload| has_same_signature(overload, &definition))
+        {
+            return Err(EvaluationError::ReassignmentError { name, line });
         }
 
-        self.map.insert(name, definition);
+        overloads.push(definition);
         Ok(())
     }
 }
 
+/// 2つの関数のパラメータの名前と型の組みが、順序を除いて一致するか判定する
+pub(crate) fn has_same_signature(
+    left: &FunctionDeclarationNode,
+    right: &FunctionDeclarationNode,
+) -> bool {
+    let signature = |function: &FunctionDeclarationNode| {
+        let mut params: Vec<(String, String)> = function
+            .params
+            .iter()
+            .map(|param| (param.name.clone(), param.param_type.to_string()))
+            .collect();
+        params.sort();
+        params
+    };
+    signature(left) == signature(right)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::virtual_machine::ast::{FunctionDeclarationNode, Type};
+    use crate::virtual_machine::ast::{FunctionDeclarationNode, ParameterNode, Type};
 
     /// 関数マッパーの新規作成テスト
     /// 関数マッパーを新規作成し、正常に初期化されていることを確認します。
@@ -80,15 +148,25 @@ mod tests {
         let mut mapper = FunctionMapper::new();
 
         // 関数宣言ノードを作成
-        let function_node = FunctionDeclarationNode {
+        let function_node = Rc::new(FunctionDeclarationNode {
             name: "add".to_string(),
             params: vec![
-                ("a".to_string(), Type::Integer),
-                ("b".to_string(), Type::Integer),
+                ParameterNode {
+                    name: "a".to_string(),
+                    param_type: Type::Integer,
+                    default_value: None,
+                    is_variadic: false,
+                },
+                ParameterNode {
+                    name: "b".to_string(),
+                    param_type: Type::Integer,
+                    default_value: None,
+                    is_variadic: false,
+                },
             ],
             return_type: Type::Integer,
             body: vec![], // 実際のコードの場合は、関数の本体を記述するが、ここでは省略
-        };
+        });
 
         // 関数を設定
         let result: Result<(), EvaluationError> = mapper.set(1, function_node.clone());
@@ -97,11 +175,12 @@ mod tests {
         assert!(result.is_ok());
 
         // 設定した関数を取得
-        let retrieved: Result<FunctionDeclarationNode, EvaluationError> = mapper.get("add", 2);
+        let retrieved: Result<&[Rc<FunctionDeclarationNode>], EvaluationError> =
+            mapper.get("add", 2);
 
         // 正しい値が取得できることを確認
         assert!(retrieved.is_ok());
-        assert_eq!(retrieved.unwrap(), function_node);
+        assert_eq!(retrieved.unwrap(), [function_node]);
     }
 
     /// 存在しない関数を取得した場合のテスト
@@ -146,15 +225,54 @@ mod tests {
         };
 
         // 最初の関数を設定
-        let first_result: Result<(), EvaluationError> = mapper.set(3, first_function_node);
+        let first_result: Result<(), EvaluationError> = mapper.set(3, Rc::new(first_function_node));
 
         // 正常に設定されたことを確認
         assert!(first_result.is_ok());
 
         // 同じ名前で関数を再設定
-        let second_result: Result<(), EvaluationError> = mapper.set(5, second_function_node);
+        let second_result: Result<(), EvaluationError> =
+            mapper.set(5, Rc::new(second_function_node));
 
         // エラーが返されることを確認
         assert!(second_result.is_err());
     }
+
+    /// パラメータの名前か型が異なる同名の関数は、オーバーロードとして宣言できることを確認します。
+    ///
+    /// let open: fn = (path: string): void { return none; };
+    /// let open: fn = (fd: int): void { return none; };     # OK
+    /// let open: fn = (path: int): void { return none; };   # OK
+    #[test]
+    fn test_function_mapper_overload() {
+        let mut mapper = FunctionMapper::new();
+        let open = |param_name: &str, param_type: Type| {
+            Rc::new(FunctionDeclarationNode {
+                name: "open".to_string(),
+                params: vec![ParameterNode {
+                    name: param_name.to_string(),
+                    param_type,
+                    default_value: None,
+                    is_variadic: false,
+                }],
+                return_type: Type::Void,
+                body: vec![],
+            })
+        };
+
+        assert!(mapper.set(1, open("path", Type::String)).is_ok());
+        assert!(mapper.set(2, open("fd", Type::Integer)).is_ok());
+        assert!(mapper.set(3, open("path", Type::Integer)).is_ok());
+        assert_eq!(
+            mapper.set(4, open("path", Type::String)),
+            Err(EvaluationError::ReassignmentError {
+                name: "open".to_string(),
+                line: 4,
+            })
+        );
+        assert_eq!(
+            mapper.get("open", 5).map(|overloads| overloads.len()),
+            Ok(3)
+        );
+    }
 }
@@ -0,0 +1,168 @@
+use crate::virtual_machine::ast::{FunctionDeclarationNode, MethodSignatureNode};
+use crate::virtual_machine::evaluator::evaluation_error::EvaluationError;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+/// implで実装されたメソッドを型ごとに管理する
+///
+/// メソッドは型名とメソッド名の組みで探されます。
+/// 関数と異なりスコープを持たず、どの関数の本体からも呼び出すことができます。
+#[derive(Clone)]
+pub(crate) struct MethodMapper {
+    methods: HashMap<String, HashMap<String, Rc<FunctionDeclarationNode>>>,
+}
+
+impl MethodMapper {
+    pub fn new() -> Self {
+        MethodMapper {
+            methods: HashMap::new(),
+        }
+    }
+
+    /// メソッドを取得する
+    ///
+    /// # Arguments
+    /// - `type_name` - 呼び出し元の値の型名
+    /// - `name` - メソッド名
+    /// - `line` - 行番号
+    ///
+    /// # Raises
+    /// - `EvaluationError::MethodNotFound` - 型にメソッドが実装されていない場合
+    pub fn get(
+        &self,
+        type_name: &str,
+        name: &str,
+        line: usize,
+    ) -> Result<Rc<FunctionDeclarationNode>, EvaluationError> {
+        match self
+            .methods
+            .get(type_name)
+            .and_then(|methods| methods.get(name))
+        {
+            Some(method) => Ok(Rc::clone(method)),
+            None => Err(EvaluationError::MethodNotFound {
+                type_name: type_name.to_string(),
+                name: name.to_string(),
+                line,
+            }),
+        }
+    }
+
+    /// メソッドを設定する
+    ///
+    /// # Raises
+    /// - `EvaluationError::DuplicateMethod` - 同じ型に同じ名前のメソッドが実装された場合
+    pub fn set(
+        &mut self,
+        line: usize,
+        type_name: &str,
+        method: Rc<FunctionDeclarationNode>,
+    ) -> Result<(), EvaluationError> {
+        let methods: &mut HashMap<String, Rc<FunctionDeclarationNode>> =
+            self.methods.entry(type_name.to_string()).or_default();
+        if methods.contains_key(&method.name) {
+            return Err(EvaluationError::DuplicateMethod {
+                type_name: type_name.to_string(),
+                name: method.name.clone(),
+                line,
+            });
+        }
+        methods.insert(method.name.clone(), method);
+        Ok(())
+    }
+
+    /// 型がinterfaceの全てのメソッドを実装しているか判定する
+    ///
+    /// メソッドの名前、selfを除くパラメータの名前と型、戻り値の型が全て一致する場合に実装しているとみなします。
+    pub fn conforms(&self, type_name: &str, signatures: &[MethodSignatureNode]) -> bool {
+        signatures.iter().all(|signature| {
+            let method: &FunctionDeclarationNode = match self
+                .methods
+                .get(type_name)
+                .and_then(|methods| methods.get(&signature.name))
+            {
+                Some(method) => method,
+                None => return false,
+            };
+            let params_match: bool = method.params.len() == signature.params.len() + 1
+                && method.params[1..].iter().zip(signature.params.iter()).all(
+                    |(param, expected)| {
+                        param.name == expected.name
+                            && param.param_type == expected.param_type
+                            && param.is_variadic == expected.is_variadic
+                    },
+                );
+            params_match && method.return_type == signature.return_type
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::virtual_machine::ast::{ParameterNode, Type};
+
+    fn parameter(name: &str, param_type: Type) -> ParameterNode {
+        ParameterNode {
+            name: name.to_string(),
+            param_type,
+            default_value: None,
+            is_variadic: false,
+        }
+    }
+
+    fn url_method(return_type: Type) -> Rc<FunctionDeclarationNode> {
+        Rc::new(FunctionDeclarationNode {
+            name: "url".to_string(),
+            params: vec![
+                parameter("self", Type::String),
+                parameter("secure", Type::Boolean),
+            ],
+            return_type,
+            body: vec![],
+        })
+    }
+
+    /// 実装したメソッドを型名とメソッド名で取得できることを確認します。
+    /// 同じ型に同じ名前のメソッドを実装した場合はエラーとなります。
+    #[test]
+    fn test_method_mapper_set_and_get() {
+        let mut mapper: MethodMapper = MethodMapper::new();
+        assert!(mapper.set(1, "string", url_method(Type::String)).is_ok());
+
+        assert_eq!(mapper.get("string", "url", 2), Ok(url_method(Type::String)));
+        assert_eq!(
+            mapper.get("int", "url", 2),
+            Err(EvaluationError::MethodNotFound {
+                type_name: "int".to_string(),
+                name: "url".to_string(),
+                line: 2,
+            })
+        );
+        assert_eq!(
+            mapper.set(3, "string", url_method(Type::String)),
+            Err(EvaluationError::DuplicateMethod {
+                type_name: "string".to_string(),
+                name: "url".to_string(),
+                line: 3,
+            })
+        );
+    }
+
+    /// メソッドのシグネチャが一致する場合のみ、interfaceを実装しているとみなすことを確認します。
+    #[test]
+    fn test_method_mapper_conforms() {
+        let mut mapper: MethodMapper = MethodMapper::new();
+        mapper.set(1, "string", url_method(Type::String)).unwrap();
+        mapper.set(1, "int", url_method(Type::Integer)).unwrap();
+
+        let signatures: Vec<MethodSignatureNode> = vec![MethodSignatureNode {
+            name: "url".to_string(),
+            params: vec![parameter("secure", Type::Boolean)],
+            return_type: Type::String,
+        }];
+        assert!(mapper.conforms("string", &signatures));
+        assert!(!mapper.conforms("int", &signatures));
+        assert!(!mapper.conforms("float", &signatures));
+    }
+}
@@ -1,33 +1,69 @@
-use crate::virtual_machine::ast::VariableDeclarationNode;
+use crate::virtual_machine::ast::LiteralValue;
 use crate::virtual_machine::evaluator::evaluation_error::EvaluationError;
 use std::collections::HashMap;
 
+/// 変数の値をスコープごとに管理する
+///
+/// 変数には、宣言の際に初期化式を評価した値を登録します。
+/// スコープはブロックに入るたびに積まれ、ブロックを抜けると取り除かれます。
+/// 変数は内側のスコープから順に探されるため、内側の変数が外側の同名の変数をシャドーイングします。
 pub(crate) struct VariableMapper {
-    map: HashMap<String, VariableDeclarationNode>,
+    scopes: Vec<HashMap<String, LiteralValue>>,
 }
 
+type Scope = HashMap<String, LiteralValue>;
+
 impl VariableMapper {
     pub fn new() -> Self {
         VariableMapper {
-            map: Default::default(),
+            scopes: vec![Default::default()],
         }
     }
 
-    /// 変数を取得する
+    /// ブロックに入る際に、新しいスコープを積む
+    pub fn push_scope(&mut self) {
+        self.scopes.push(Default::default());
+    }
+
+    /// ブロックを抜ける際に、一番内側のスコープを取り除く
+    pub fn pop_scope(&mut self) {
+        self.scopes.pop();
+    }
+
+    /// 指定した深さまでのスコープを複製する
+    ///
+    /// 関数の中で宣言された関数は、宣言された位置から見えるスコープを引き継いで評価されます。
+    pub fn capture(&self, depth: usize) -> Self {
+        VariableMapper {
+            scopes: self.scopes[..depth].to_vec(),
+        }
+    }
+
+    /// 変数が宣言されたスコープの深さを求める
+    ///
+    /// 一番外側のスコープの深さは1です。見つからない場合は `None` を返します。
+    pub fn depth_of(&self, name: &str) -> Option<usize> {
+        self.scopes
+            .iter()
+            .rposition(|scope| scope.contains_key(name))
+            .map(|index| index + 1)
+    }
+
+    /// 変数の値を取得する
     ///
     /// # Arguments
     /// - `name` - 変数名
     /// - `line` - 行番号
     ///
     /// # Returns
-    /// - `Result<VariableDeclarationNode, EvaluationError>` - Map結果
-    ///   - `VariableDeclarationNode` - 変数の定義
+    /// - `Result<LiteralValue, EvaluationError>` - Map結果
+    ///   - `LiteralValue` - 変数の値
     ///   - `EvaluationError` - 評価エラー
     ///
     /// # Raises
     /// - `EvaluationError::VariableNotFound` - 変数が見つからない場合
-    pub fn get(&self, name: &str, line: usize) -> Result<VariableDeclarationNode, EvaluationError> {
-        match self.map.get(name) {
+    pub fn get(&self, name: &str, line: usize) -> Result<LiteralValue, EvaluationError> {
+        match self.scopes.iter().rev().find_map(|scope| scope.get(name)) {
             Some(value) => Ok(value.clone()),
             None => Err(EvaluationError::VariableNotFound {
                 name: name.to_string(),
@@ -36,10 +72,12 @@ impl VariableMapper {
         }
     }
 
-    /// 変数を設定する
+    /// 変数の値を設定する
     ///
     /// # Arguments
-    /// - `definition` - 変数の定義
+    /// - `line` - 行番号
+    /// - `name` - 変数名
+    /// - `value` - 評価済みの変数の値
     ///
     /// # Returns
     /// - `Result<(), EvaluationError>` - 設定結果
@@ -47,22 +85,28 @@ impl VariableMapper {
     ///   - `EvaluationError` - 評価エラー
     ///
     /// # Raises
-    /// - `EvaluationError::ReassignmentError` - 変数の再代入が行われた場合
+    /// - `EvaluationError::ReassignmentError` - 同じスコープで変数の再宣言が行われた場合
     pub fn set(
         &mut self,
         line: usize,
-        definition: VariableDeclarationNode,
+        name: &str,
+        value: LiteralValue,
     ) -> Result<(), EvaluationError> {
-        // 再代入を許可しない
-        // let x = 1; x = 2;  # Error
-        if self.map.contains_key(&definition.name) {
+        // 同じスコープでの再宣言を許可しない
+        // let x = 1; let x = 2;  # Error
+        // 外側のスコープの変数と同じ名前の宣言はシャドーイングとして許可する
+        let scope: &mut Scope = self
+            .scopes
+            .last_mut()
+            .expect("VariableMapper always has the global scope");
+        if scope.contains_key(name) {
             return Err(EvaluationError::ReassignmentError {
-                name: definition.name.clone(),
+                name: name.to_string(),
                 line,
             });
         };
 
-        self.map.insert(definition.name.clone(), definition);
+        scope.insert(name.to_string(), value);
 
         Ok(())
     }
@@ -71,9 +115,6 @@ impl VariableMapper {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::virtual_machine::ast::{
-        ExpressionNode, LiteralNode, LiteralValue, Type, VariableDeclarationNode,
-    };
 
     /// 変数マッパーの新規作成テスト
     /// 変数マッパーを新規作成し、正常に初期化されていることを確認します。
@@ -83,27 +124,17 @@ mod tests {
     fn test_variable_mapper_set_and_get() {
         let mut mapper = VariableMapper::new();
 
-        // 変数宣言ノードを作成
-        let variable_node = VariableDeclarationNode {
-            name: "x".to_string(),
-            var_type: Type::Integer,
-            value: Box::new(ExpressionNode::Literal(Box::new(LiteralNode {
-                value: LiteralValue::Integer(1),
-            }))),
-        };
-
         // 変数を設定
-        let result: Result<(), EvaluationError> = mapper.set(1, variable_node.clone());
+        let result: Result<(), EvaluationError> = mapper.set(1, "x", LiteralValue::Integer(1));
 
         // 正常に設定されたことを確認
         assert!(result.is_ok());
 
         // 設定した変数を取得
-        let retrieved: Result<VariableDeclarationNode, EvaluationError> = mapper.get("x", 2);
+        let retrieved: Result<LiteralValue, EvaluationError> = mapper.get("x", 2);
 
         // 正しい値が取得できることを確認
-        assert!(retrieved.is_ok());
-        assert_eq!(retrieved.unwrap(), variable_node);
+        assert_eq!(retrieved, Ok(LiteralValue::Integer(1)));
     }
 
     /// 存在しない変数を取得した場合のテスト
@@ -130,35 +161,39 @@ mod tests {
     fn test_variable_mapper_reassignment_error() {
         let mut mapper = VariableMapper::new();
 
-        // 最初の変数宣言ノード
-        let first_variable_node = VariableDeclarationNode {
-            name: "x".to_string(),
-            var_type: Type::Integer,
-            value: Box::new(ExpressionNode::Literal(Box::new(LiteralNode {
-                value: LiteralValue::Integer(1),
-            }))),
-        };
-
-        // 上書きする変数宣言ノード
-        let second_variable_node = VariableDeclarationNode {
-            name: "x".to_string(),
-            var_type: Type::Integer,
-            value: Box::new(ExpressionNode::Literal(Box::new(LiteralNode {
-                value: LiteralValue::Integer(2),
-            }))),
-        };
-
         // 最初の変数を設定
-        let first_result: Result<(), EvaluationError> = mapper.set(3, first_variable_node.clone());
+        let first_result: Result<(), EvaluationError> =
+            mapper.set(3, "x", LiteralValue::Integer(1));
 
         // 正常に設定されたことを確認
         assert!(first_result.is_ok());
 
         // 同じ名前で変数を再設定
         let second_result: Result<(), EvaluationError> =
-            mapper.set(5, second_variable_node.clone());
+            mapper.set(5, "x", LiteralValue::Integer(2));
 
         // エラーが返されることを確認
         assert!(second_result.is_err());
     }
+
+    /// 内側のスコープで変数をシャドーイングした場合のテスト
+    /// 内側のスコープの変数が優先され、スコープを抜けると外側の変数に戻ることを確認します。
+    ///
+    /// let x: int = 1;
+    /// { let x: int = 2; x; }  # 2
+    /// x;                      # 1
+    #[test]
+    fn test_variable_mapper_shadowing_in_inner_scope() {
+        let mut mapper = VariableMapper::new();
+
+        assert!(mapper.set(1, "x", LiteralValue::Integer(1)).is_ok());
+        mapper.push_scope();
+        assert!(mapper.set(2, "x", LiteralValue::Integer(2)).is_ok());
+        assert_eq!(mapper.get("x", 2), Ok(LiteralValue::Integer(2)));
+        assert_eq!(mapper.depth_of("x"), Some(2));
+
+        mapper.pop_scope();
+        assert_eq!(mapper.get("x", 3), Ok(LiteralValue::Integer(1)));
+        assert_eq!(mapper.depth_of("x"), Some(1));
+    }
 }
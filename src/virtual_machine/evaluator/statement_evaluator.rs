@@ -1,17 +1,22 @@
 pub(crate) mod expression_evaluator;
 
-use crate::virtual_machine::ast::Statement;
+use crate::virtual_machine::ast::{
+    BlockNode, LiteralValue, Statement, Type, VariableDeclarationNode,
+};
+use crate::virtual_machine::evaluator::core::{is_value_of_type_with_methods, literal_to_type};
 use crate::virtual_machine::evaluator::evaluation_error::EvaluationError;
 use crate::virtual_machine::evaluator::evaluation_error::EvaluationError::UnexpectedError;
+use crate::virtual_machine::evaluator::mapper::method_mapper::MethodMapper;
 use crate::virtual_machine::evaluator::statement_evaluator::expression_evaluator::evaluate_expression;
 use crate::virtual_machine::evaluator::Evaluator;
+use std::rc::Rc;
 
 pub(crate) fn evaluate_statement(
     evaluator: &mut Evaluator,
-    statement: Statement,
+    statement: &Statement,
 ) -> Result<(), EvaluationError> {
     // Statementを評価する
-    match statement.clone() {
+    match statement {
         Statement::Expression(expr) => {
             // Expressionを評価する
             evaluate_expression(evaluator, expr)?;
@@ -20,17 +25,262 @@ pub(crate) fn evaluate_statement(
         Statement::DeclarationOfFunction(func) => {
             evaluator
                 .function_mapper
-                .set(evaluator.line, *func.clone())?;
+                .set(evaluator.line, Rc::clone(func))?;
             Ok(())
         }
-        Statement::DeclarationOfVariable(var) => {
-            evaluator
-                .variable_mapper
-                .set(evaluator.line, *var.clone())?;
+        Statement::DeclarationOfVariable(var) => declare_variable(evaluator, var, false),
+        Statement::DeclarationOfConstant(constant) => {
+            // 定数は畳み込み済みなので、変数と同じように登録する
+            declare_variable(evaluator, constant, true)
+        }
+        Statement::Export(declaration) => {
+            // exportはモジュールの外から参照するための印なので、中の宣言をそのまま評価する
+            evaluate_statement(evaluator, declaration)
+        }
+        Statement::DeclarationOfNewtype(_) | Statement::DeclarationOfInterface(_) => {
+            // newtypeとinterfaceの型名は構文解析で解決済みなので、評価時には何もしない
+            Ok(())
+        }
+        Statement::Impl(node) => {
+            // メソッドは、implの対象の型名で登録する
+            let type_name: String = node.target.to_string();
+            for method in node.methods.iter() {
+                Rc::make_mut(&mut evaluator.method_mapper).set(
+                    evaluator.line,
+                    &type_name,
+                    Rc::clone(method),
+                )?;
+            }
             Ok(())
         }
+        Statement::Block(block) => {
+            // ブロックは独自のスコープで評価する
+            evaluator.variable_mapper.push_scope();
+            evaluator.function_mapper.push_scope();
+            let result: Result<(), EvaluationError> = evaluate_block(evaluator, block);
+            evaluator.function_mapper.pop_scope();
+            evaluator.variable_mapper.pop_scope();
+            result
+        }
         _ => Err(UnexpectedError {
             line: evaluator.line,
         }),
     }
 }
+
+/// 変数の初期化式を評価し、値が宣言された型に当てはまる場合に登録する
+///
+/// 初期化式は宣言の際に一度だけ評価し、変数には評価した値を登録します。
+///
+/// ```shot
+/// let a: int = "x";  # VariableTypeMismatch
+/// ```
+///
+/// ## Raises
+///
+/// * `EvaluationError::VariableTypeMismatch` - 変数の値が宣言された型に当てはまらない場合
+/// * `EvaluationError::ConstantTypeMismatch` - 定数の値が宣言された型に当てはまらない場合
+/// * `EvaluationError::ReassignmentError` - 同じスコープで宣言済みの場合
+fn declare_variable(
+    evaluator: &mut Evaluator,
+    node: &VariableDeclarationNode,
+    is_constant: bool,
+) -> Result<(), EvaluationError> {
+    let value: LiteralValue = evaluate_expression(evaluator, &node.value)?;
+    check_declared_type(
+        &node.name,
+        &node.var_type,
+        &value,
+        is_constant,
+        &evaluator.method_mapper,
+        evaluator.line,
+    )?;
+    evaluator
+        .variable_mapper
+        .set(evaluator.line, &node.name, value)
+}
+
+/// 変数の値が宣言された型に当てはまるかを検証する
+///
+/// 型推論で型が決まらなかった変数は検証しません。
+///
+/// ## Raises
+///
+/// * `EvaluationError::VariableTypeMismatch` - 変数の値が宣言された型に当てはまらない場合
+/// * `EvaluationError::ConstantTypeMismatch` - 定数の値が宣言された型に当てはまらない場合
+pub(crate) fn check_declared_type(
+    name: &str,
+    var_type: &Type,
+    value: &LiteralValue,
+    is_constant: bool,
+    method_mapper: &MethodMapper,
+    line: usize,
+) -> Result<(), EvaluationError> {
+    if *var_type == Type::Infer
+        || is_value_of_type_with_methods(value.clone(), var_type.clone(), method_mapper)
+    {
+        return Ok(());
+    }
+
+    let (name, expected, actual) = (
+        name.to_string(),
+        var_type.to_string(),
+        literal_to_type(value.clone()).to_string(),
+    );
+    Err(match is_constant {
+        true => EvaluationError::ConstantTypeMismatch {
+            name,
+            line,
+            expected,
+            actual,
+        },
+        false => EvaluationError::VariableTypeMismatch {
+            name,
+            line,
+            expected,
+            actual,
+        },
+    })
+}
+
+/// ブロックの中の文を順に評価する
+///
+/// 評価が終わると、行番号はブロックの前の値に戻します。
+fn evaluate_block(evaluator: &mut Evaluator, block: &BlockNode) -> Result<(), EvaluationError> {
+    let line: usize = evaluator.line;
+    for (statement_line, statement) in block.statements.iter() {
+        evaluator.line = *statement_line;
+        evaluate_statement(evaluator, statement)?;
+    }
+    evaluator.line = line;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::virtual_machine::ast::{
+        BinaryOperationNode, BinaryOperator, ExpressionNode, FunctionDeclarationNode, LiteralNode,
+        LiteralValue, Statement, Type, VariableDeclarationNode,
+    };
+    use crate::virtual_machine::evaluator::core::{
+        initialize_evaluator_with_custom_ast, parse_source,
+    };
+    use crate::virtual_machine::evaluator::evaluation_error::EvaluationError;
+    use crate::virtual_machine::evaluator::mapper::function_mapper::FunctionMapper;
+    use crate::virtual_machine::evaluator::mapper::variable_mapper::VariableMapper;
+    use crate::virtual_machine::evaluator::Evaluator;
+    use std::rc::Rc;
+
+    fn literal(value: LiteralValue) -> Box<ExpressionNode> {
+        Box::new(ExpressionNode::Literal(Box::new(LiteralNode { value })))
+    }
+
+    /// 変数には、初期化式を評価した値が登録されることを確認するテスト
+    ///
+    /// let a: int = 1 + 2;
+    #[test]
+    fn test_declare_variable_with_evaluated_value() {
+        let mut evaluator: Evaluator =
+            initialize_evaluator_with_custom_ast(vec![Statement::DeclarationOfVariable(Box::new(
+                VariableDeclarationNode {
+                    name: "a".to_string(),
+                    var_type: Type::Integer,
+                    value: Box::new(ExpressionNode::BinaryOperation(Box::new(
+                        BinaryOperationNode {
+                            left: literal(LiteralValue::Integer(1)),
+                            operator: BinaryOperator::Add,
+                            right: literal(LiteralValue::Integer(2)),
+                        },
+                    ))),
+                },
+            ))]);
+
+        assert_eq!(evaluator.evaluate(), Ok(LiteralValue::None));
+        assert_eq!(
+            evaluator.variable_mapper.get("a", 0),
+            Ok(LiteralValue::Integer(3))
+        );
+    }
+
+    /// 変数を読むたびに初期化式を評価し直さず、宣言の際に求めた値を返すことを確認するテスト
+    ///
+    /// 評価の後で関数を取り除いても、初期化式で関数を呼び出した変数の値を読めます。
+    ///
+    /// let double: fn = (n: int): int { return n * 2; };
+    /// let r: int = double(n: 10);
+    /// let s: int = r + r + r;
+    #[test]
+    fn test_variable_keeps_value_of_declaration() {
+        let mut evaluator: Evaluator = Evaluator::new(
+            parse_source(
+                r#"
+let double: fn = (n: int): int { return n * 2; };
+let r: int = double(n: 10);
+let s: int = r + r + r;
+"#,
+            ),
+            FunctionMapper::new(),
+            VariableMapper::new(),
+        );
+
+        assert_eq!(evaluator.evaluate(), Ok(LiteralValue::None));
+        assert_eq!(
+            evaluator.evaluate_variable("s"),
+            Ok(LiteralValue::Integer(60))
+        );
+        // 初期化式を評価し直すと、double が見つからずにエラーになる
+        evaluator.function_mapper = FunctionMapper::new();
+        assert_eq!(
+            evaluator.evaluate_variable("r"),
+            Ok(LiteralValue::Integer(20))
+        );
+    }
+
+    /// 初期化式の値が宣言された型に当てはまらない場合、宣言の行でエラーになることを確認するテスト
+    ///
+    /// let a: int = "x";  # VariableTypeMismatch
+    #[test]
+    fn test_declare_variable_type_mismatch() {
+        let mut evaluator: Evaluator =
+            initialize_evaluator_with_custom_ast(vec![Statement::DeclarationOfVariable(Box::new(
+                VariableDeclarationNode {
+                    name: "a".to_string(),
+                    var_type: Type::Integer,
+                    value: literal(LiteralValue::String("x".to_string())),
+                },
+            ))]);
+
+        assert_eq!(
+            evaluator.evaluate(),
+            Err(EvaluationError::VariableTypeMismatch {
+                name: "a".to_string(),
+                line: 0,
+                expected: "int".to_string(),
+                actual: "string".to_string(),
+            })
+        );
+    }
+
+    /// 関数の宣言では、ASTのNodeを複製せずに共有して登録することを確認するテスト
+    ///
+    /// fn f(): int { return 1; };
+    #[test]
+    fn test_declare_function_shares_node() {
+        let function: Rc<FunctionDeclarationNode> = Rc::new(FunctionDeclarationNode {
+            name: "f".to_string(),
+            params: vec![],
+            return_type: Type::Integer,
+            body: vec![(1, Statement::Return(literal(LiteralValue::Integer(1))))],
+        });
+        let mut evaluator: Evaluator =
+            initialize_evaluator_with_custom_ast(vec![Statement::DeclarationOfFunction(
+                Rc::clone(&function),
+            )]);
+
+        assert_eq!(evaluator.evaluate(), Ok(LiteralValue::None));
+        let overloads: &[Rc<FunctionDeclarationNode>] =
+            evaluator.function_mapper.get("f", 0).unwrap();
+        assert_eq!(overloads.len(), 1);
+        assert!(Rc::ptr_eq(&overloads[0], &function));
+    }
+}
@@ -1,6 +1,9 @@
-mod call_of_function_evaluator;
+pub(crate) mod binary_operation_evaluator;
+pub(crate) mod call_of_function_evaluator;
 mod call_of_variable_evaluator;
-mod type_cast_evaluator;
+mod if_evaluator;
+pub(crate) mod type_cast_evaluator;
+mod type_test_evaluator;
 
 use crate::virtual_machine::ast::{ExpressionNode, LiteralValue};
 use crate::virtual_machine::evaluator::evaluation_error::EvaluationError;
@@ -9,29 +12,45 @@ use crate::virtual_machine::evaluator::statement_evaluator::expression_evaluator
 
 pub fn evaluate_expression(
     evaluator: &mut Evaluator,
-    expression: ExpressionNode,
+    expression: &ExpressionNode,
 ) -> Result<LiteralValue, EvaluationError> {
     match expression {
-        ExpressionNode::Literal(literal) => Ok(literal.value),
+        ExpressionNode::Literal(literal) => Ok(literal.value.clone()),
         // BinaryOperation
+        ExpressionNode::BinaryOperation(node) => {
+            binary_operation_evaluator::evaluate_binary_operation(evaluator, node)
+        }
         // CallOfFunction
         ExpressionNode::CallOfFunction(node) => Ok(
-            call_of_function_evaluator::call_of_function_evaluator(evaluator, *node)?,
+            call_of_function_evaluator::call_of_function_evaluator(evaluator, node)?,
         ),
+        // PartialApplication
+        ExpressionNode::PartialApplication(node) => {
+            call_of_function_evaluator::partial_application_evaluator(evaluator, node)
+        }
         // CallOfVariable
         ExpressionNode::CallOfVariable(node) => Ok(call_of_variable_evaluator::call_of_variable(
-            evaluator, *node,
+            evaluator, node,
         )?),
         // TypeCast
-        ExpressionNode::TypeCast(node) => evaluate_type_cast(evaluator, *node),
-        _ => Ok(LiteralValue::None),
+        ExpressionNode::TypeCast(node) => evaluate_type_cast(evaluator, node),
+        // TypeTest
+        ExpressionNode::TypeTest(node) => type_test_evaluator::evaluate_type_test(evaluator, node),
+        // If
+        ExpressionNode::If(node) => if_evaluator::evaluate_if(evaluator, node),
+        // CallOfMethod
+        ExpressionNode::CallOfMethod(node) => {
+            call_of_function_evaluator::call_of_method_evaluator(evaluator, node)
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::virtual_machine::ast::LiteralNode;
-    use crate::virtual_machine::ast::{ExpressionNode, LiteralValue, Statement};
+    use crate::virtual_machine::ast::{
+        ExpressionNode, IfNode, LiteralValue, Statement, Type, TypeTestNode,
+    };
     use crate::virtual_machine::evaluator::core::initialize_evaluator_with_custom_ast;
     use crate::virtual_machine::evaluator::evaluation_error::EvaluationError;
     use crate::virtual_machine::evaluator::statement_evaluator::expression_evaluator::evaluate_expression;
@@ -47,8 +66,54 @@ mod tests {
         let mut evaluator: Evaluator =
             initialize_evaluator_with_custom_ast(vec![Statement::Expression(*expression.clone())]);
         let actual: Result<LiteralValue, EvaluationError> =
-            evaluate_expression(&mut evaluator, *expression);
+            evaluate_expression(&mut evaluator, &expression);
         assert_eq!(actual, Ok(expected.clone()));
         assert_eq!(actual.unwrap(), expected);
     }
+
+    fn literal(value: LiteralValue) -> ExpressionNode {
+        ExpressionNode::Literal(Box::new(LiteralNode { value }))
+    }
+
+    /// 型検査の結果によって分岐を選べることを確認します。
+    ///
+    /// if "a" is int | float { 1 } else { 2 }  # 2
+    #[test]
+    fn test_evaluate_if_with_type_test() {
+        let expression = ExpressionNode::If(Box::new(IfNode {
+            condition: Box::new(ExpressionNode::TypeTest(Box::new(TypeTestNode {
+                expression: Box::new(literal(LiteralValue::String("a".to_string()))),
+                tested_type: Type::Union(vec![Type::Integer, Type::Float]),
+            }))),
+            then_branch: Box::new(literal(LiteralValue::Integer(1))),
+            else_branch: Box::new(literal(LiteralValue::Integer(2))),
+        }));
+        let mut evaluator: Evaluator = initialize_evaluator_with_custom_ast(vec![]);
+
+        assert_eq!(
+            evaluate_expression(&mut evaluator, &expression),
+            Ok(LiteralValue::Integer(2))
+        );
+    }
+
+    /// 条件が bool でない場合にエラーとなることを確認します。
+    ///
+    /// if 1 { 1 } else { 2 }  # InvalidCondition
+    #[test]
+    fn test_evaluate_if_with_invalid_condition() {
+        let expression = ExpressionNode::If(Box::new(IfNode {
+            condition: Box::new(literal(LiteralValue::Integer(1))),
+            then_branch: Box::new(literal(LiteralValue::Integer(1))),
+            else_branch: Box::new(literal(LiteralValue::Integer(2))),
+        }));
+        let mut evaluator: Evaluator = initialize_evaluator_with_custom_ast(vec![]);
+
+        assert_eq!(
+            evaluate_expression(&mut evaluator, &expression),
+            Err(EvaluationError::InvalidCondition {
+                actual: "int".to_string(),
+                line: 0,
+            })
+        );
+    }
 }
@@ -0,0 +1,177 @@
+use crate::virtual_machine::ast::{BinaryOperationNode, BinaryOperator, LiteralValue};
+use crate::virtual_machine::evaluator::core::literal_to_type;
+use crate::virtual_machine::evaluator::evaluation_error::EvaluationError;
+use crate::virtual_machine::evaluator::statement_evaluator::expression_evaluator::evaluate_expression;
+use crate::virtual_machine::evaluator::Evaluator;
+
+/// 二項演算を評価する
+///
+/// 左辺、右辺の順に式を評価し、演算子を適用します。
+pub(crate) fn evaluate_binary_operation(
+    evaluator: &mut Evaluator,
+    node: &BinaryOperationNode,
+) -> Result<LiteralValue, EvaluationError> {
+    let left: LiteralValue = evaluate_expression(evaluator, &node.left)?;
+    let right: LiteralValue = evaluate_expression(evaluator, &node.right)?;
+    calculate_binary_operation(node.operator.clone(), left, right, evaluator.line)
+}
+
+/// 評価済みの値に二項演算子を適用する
+///
+/// 暗黙の型変換は行わないため、左辺と右辺は同じ型である必要があります。
+///
+/// - int と int: 四則演算 (除算は切り捨て)
+/// - float と float: 四則演算
+/// - string と string: `+` による連結
+///
+/// ## Raises
+///
+/// * `EvaluationError::InvalidBinaryOperation` - 演算子を適用できない型の組み合わせの場合
+/// * `EvaluationError::DivisionByZero` - 整数を0で割った場合
+/// * `EvaluationError::IntegerOverflow` - 整数の演算結果が範囲を超えた場合
+pub(crate) fn calculate_binary_operation(
+    operator: BinaryOperator,
+    left: LiteralValue,
+    right: LiteralValue,
+    line: usize,
+) -> Result<LiteralValue, EvaluationError> {
+    match (operator, left, right) {
+        (BinaryOperator::Divide, LiteralValue::Integer(_), LiteralValue::Integer(0)) => {
+            Err(EvaluationError::DivisionByZero { line })
+        }
+        (operator, LiteralValue::Integer(l), LiteralValue::Integer(r)) => {
+            let result: Option<i64> = match operator {
+                BinaryOperator::Add => l.checked_add(r),
+                BinaryOperator::Subtract => l.checked_sub(r),
+                BinaryOperator::Multiply => l.checked_mul(r),
+                BinaryOperator::Divide => l.checked_div(r),
+            };
+            match result {
+                Some(value) => Ok(LiteralValue::Integer(value)),
+                None => Err(EvaluationError::IntegerOverflow { line }),
+            }
+        }
+        (operator, LiteralValue::Float(l), LiteralValue::Float(r)) => {
+            Ok(LiteralValue::Float(match operator {
+                BinaryOperator::Add => l + r,
+                BinaryOperator::Subtract => l - r,
+                BinaryOperator::Multiply => l * r,
+                BinaryOperator::Divide => l / r,
+            }))
+        }
+        (BinaryOperator::Add, LiteralValue::String(l), LiteralValue::String(r)) => {
+            Ok(LiteralValue::String(l + &r))
+        }
+        (operator, left, right) => Err(EvaluationError::InvalidBinaryOperation {
+            line,
+            operator: operator.to_string(),
+            left_type: literal_to_type(left).to_string(),
+            right_type: literal_to_type(right).to_string(),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::virtual_machine::ast::{
+        BinaryOperationNode, BinaryOperator, ExpressionNode, LiteralNode, LiteralValue,
+    };
+    use crate::virtual_machine::evaluator::core::initialize_evaluator_with_custom_ast;
+    use crate::virtual_machine::evaluator::evaluation_error::EvaluationError;
+    use crate::virtual_machine::evaluator::statement_evaluator::expression_evaluator::binary_operation_evaluator::{
+        calculate_binary_operation, evaluate_binary_operation,
+    };
+    use crate::virtual_machine::evaluator::Evaluator;
+
+    /// 入れ子になった二項演算を評価できることを確認します。
+    ///
+    /// (1 + 2) * 3;  # 9
+    #[test]
+    fn test_evaluate_nested_binary_operation() {
+        let node: BinaryOperationNode = BinaryOperationNode {
+            left: Box::new(ExpressionNode::BinaryOperation(Box::new(
+                BinaryOperationNode {
+                    left: Box::new(ExpressionNode::Literal(Box::new(LiteralNode {
+                        value: LiteralValue::Integer(1),
+                    }))),
+                    operator: BinaryOperator::Add,
+                    right: Box::new(ExpressionNode::Literal(Box::new(LiteralNode {
+                        value: LiteralValue::Integer(2),
+                    }))),
+                },
+            ))),
+            operator: BinaryOperator::Multiply,
+            right: Box::new(ExpressionNode::Literal(Box::new(LiteralNode {
+                value: LiteralValue::Integer(3),
+            }))),
+        };
+        let mut evaluator: Evaluator = initialize_evaluator_with_custom_ast(vec![]);
+        let actual: Result<LiteralValue, EvaluationError> =
+            evaluate_binary_operation(&mut evaluator, &node);
+        assert_eq!(actual, Ok(LiteralValue::Integer(9)));
+    }
+
+    /// float同士の演算ができることを確認します。
+    ///
+    /// 1.5 / 0.5;  # 3.0
+    #[test]
+    fn test_calculate_float_division() {
+        let actual: Result<LiteralValue, EvaluationError> = calculate_binary_operation(
+            BinaryOperator::Divide,
+            LiteralValue::Float(1.5),
+            LiteralValue::Float(0.5),
+            1,
+        );
+        assert_eq!(actual, Ok(LiteralValue::Float(3.0)));
+    }
+
+    /// 文字列同士を `+` で連結できることを確認します。
+    ///
+    /// "shun" + "sock";  # "shunsock"
+    #[test]
+    fn test_calculate_string_concatenation() {
+        let actual: Result<LiteralValue, EvaluationError> = calculate_binary_operation(
+            BinaryOperator::Add,
+            LiteralValue::String("shun".to_string()),
+            LiteralValue::String("sock".to_string()),
+            1,
+        );
+        assert_eq!(actual, Ok(LiteralValue::String("shunsock".to_string())));
+    }
+
+    /// 整数を0で割った場合、エラーが返されることを確認します。
+    ///
+    /// 1 / 0;  # DivisionByZero
+    #[test]
+    fn test_calculate_division_by_zero() {
+        let actual: Result<LiteralValue, EvaluationError> = calculate_binary_operation(
+            BinaryOperator::Divide,
+            LiteralValue::Integer(1),
+            LiteralValue::Integer(0),
+            1,
+        );
+        assert_eq!(actual, Err(EvaluationError::DivisionByZero { line: 1 }));
+    }
+
+    /// 異なる型同士の演算はエラーが返されることを確認します。
+    ///
+    /// 1 + 1.0;  # InvalidBinaryOperation
+    #[test]
+    fn test_calculate_mismatched_types() {
+        let actual: Result<LiteralValue, EvaluationError> = calculate_binary_operation(
+            BinaryOperator::Add,
+            LiteralValue::Integer(1),
+            LiteralValue::Float(1.0),
+            1,
+        );
+        assert_eq!(
+            actual,
+            Err(EvaluationError::InvalidBinaryOperation {
+                line: 1,
+                operator: "+".to_string(),
+                left_type: "int".to_string(),
+                right_type: "float".to_string(),
+            })
+        );
+    }
+}
@@ -1,227 +1,469 @@
 use crate::virtual_machine::ast::AST;
 use crate::virtual_machine::ast::{
-    ExpressionNode, FunctionDeclarationNode, LiteralNode, VariableDeclarationNode,
+    ArgumentNode, FunctionCallNode, LiteralValue, MethodCallNode, Type,
 };
-use crate::virtual_machine::ast::{FunctionCallNode, LiteralValue, Type};
+use crate::virtual_machine::ast::{FunctionDeclarationNode, ParameterNode};
+use crate::virtual_machine::evaluator::argument_binder::{
+    apply_partially, bind_arguments, bind_params, select_overload, validate_param_type,
+    BoundParams, CalledFunction, PassedArgument,
+};
+use crate::virtual_machine::evaluator::core::{is_value_of_type_with_methods, literal_to_type};
 use crate::virtual_machine::evaluator::evaluation_error::EvaluationError;
 use crate::virtual_machine::evaluator::mapper::function_mapper::FunctionMapper;
+use crate::virtual_machine::evaluator::mapper::method_mapper::MethodMapper;
 use crate::virtual_machine::evaluator::mapper::variable_mapper::VariableMapper;
 use crate::virtual_machine::evaluator::statement_evaluator::expression_evaluator::evaluate_expression;
 use crate::virtual_machine::evaluator::Evaluator;
+use std::rc::Rc;
 
 pub(crate) fn call_of_function_evaluator(
     evaluator: &mut Evaluator,
-    node: FunctionCallNode,
+    node: &FunctionCallNode,
 ) -> Result<LiteralValue, EvaluationError> {
-    println!("call_of_function_evaluator is called: {:?}", node);
     // 関数呼び出しNodeから呼び出した関数名と引数を取得
-    println!("reading function name and arguments from FunctionCallNode");
     let calling_function_name: String = node.name.clone();
-    let calling_function_arguments: Vec<(String, ExpressionNode)> = node.arguments.clone();
-    println!(
-        "function name: {:?}, arguments: {:?}",
-        calling_function_name, calling_function_arguments
-    );
+    let calling_function_arguments: Vec<PassedArgument> =
+        evaluate_arguments(evaluator, &node.arguments)?;
 
     // 関数宣言Nodeから呼び出された関数の情報を取得
-    println!("reading function information from FunctionDeclarationNode");
-    let called_function: FunctionDeclarationNode = evaluator
-        .function_mapper
-        .get(&calling_function_name, evaluator.line)?;
-    println!("checked function information: {:?}", called_function);
-    let called_function_arguments: Vec<(String, Type)> = called_function.params.clone();
-    println!("checked arguments information: {:?}", called_function);
-
-    // Validate
-    let params: Vec<(String, Type, LiteralValue)> = validate_params(
+    // オーバーロードがある場合は、引数を束縛できる関数を選ぶ
+    let (called_function, bound_params, depth) =
+        select_called_function(evaluator, &calling_function_name, |overload| {
+            bind_params(
+                &calling_function_name,
+                &calling_function_arguments,
+                overload,
+                &evaluator.method_mapper,
+                evaluator.line,
+            )
+        })?;
+
+    call_function(
+        evaluator,
+        &calling_function_name,
+        called_function,
+        Some(depth),
+        bound_params,
+    )
+}
+
+/// メソッド呼び出しを評価する
+///
+/// 呼び出し元の値の型に実装されたメソッドを探し、呼び出し元の値を先頭の `self` パラメータに渡して呼び出します。
+/// メソッドの本体は、グローバルに宣言された関数と同じく新しいスコープで評価します。
+///
+/// ```shot
+/// impl Host { let url: fn = (self): string { return "https://" + (self as string); }; }
+/// h.url();  # Host.url(self: h) と同じ
+/// ```
+///
+/// ## Raises
+///
+/// * `EvaluationError::MethodNotFound` - 呼び出し元の値の型にメソッドが実装されていない場合
+/// * `bind_params` と同じエラー
+pub(crate) fn call_of_method_evaluator(
+    evaluator: &mut Evaluator,
+    node: &MethodCallNode,
+) -> Result<LiteralValue, EvaluationError> {
+    let receiver: LiteralValue = evaluate_expression(evaluator, &node.receiver)?;
+    let arguments: Vec<PassedArgument> = evaluate_arguments(evaluator, &node.arguments)?;
+    let type_name: String = literal_to_type(receiver.clone()).to_string();
+    let method: Rc<FunctionDeclarationNode> =
+        evaluator
+            .method_mapper
+            .get(&type_name, &node.name, evaluator.line)?;
+
+    let method_name: String = format!("{}.{}", type_name, node.name);
+    let bound_params: BoundParams = bind_params(
+        &method_name,
+        &with_receiver(receiver, arguments),
+        &method.params,
+        &evaluator.method_mapper,
+        evaluator.line,
+    )?;
+
+    call_function(
+        evaluator,
+        &method_name,
+        CalledFunction::Declared(method),
+        None,
+        bound_params,
+    )
+}
+
+/// 呼び出し元の値を、`self` に束縛する位置引数として引数の先頭に加える
+pub(crate) fn with_receiver(
+    receiver: LiteralValue,
+    arguments: Vec<PassedArgument>,
+) -> Vec<PassedArgument> {
+    let mut passed_arguments: Vec<PassedArgument> = vec![PassedArgument {
+        name: None,
+        value: receiver,
+        is_spread: false,
+    }];
+    passed_arguments.extend(arguments);
+    passed_arguments
+}
+
+/// 引数を束縛した関数を呼び出す
+///
+/// `enclosing_depth` は関数が宣言されたスコープの深さです。
+/// `None` の場合は、外側のスコープを引き継がずに関数の本体を評価します。
+///
+/// ## Raises
+///
+/// * `EvaluationError::ReturnTypeMismatch` - 戻り値が関数の戻り値の型に当てはまらない場合
+fn call_function(
+    evaluator: &mut Evaluator,
+    calling_function_name: &str,
+    called_function: CalledFunction,
+    enclosing_depth: Option<usize>,
+    bound_params: BoundParams,
+) -> Result<LiteralValue, EvaluationError> {
+    let params: Vec<(String, Type, LiteralValue)> = evaluate_default_values(
         evaluator,
-        calling_function_name.clone(),
-        calling_function_arguments.clone(),
-        called_function_arguments.clone(),
+        calling_function_name,
+        called_function.params(),
+        enclosing_depth,
+        bound_params,
     )?;
-    println!("validated params: {:?}", params);
 
     // 関数呼び出しのためのスコープを設定
-    println!("setting up scope for function call");
-    let mut ast: AST = AST::new();
-    for stmt in called_function.body.clone() {
-        ast.push_statement(evaluator.line, stmt);
-    }
+    // 関数の本体は複製せず、関数宣言のNodeから借用して評価する
+    let (function_mapper, variable_mapper) = match enclosing_depth {
+        Some(depth) => capture_enclosing_scope(evaluator, depth),
+        None => (FunctionMapper::new(), VariableMapper::new()),
+    };
     let function_scope_evaluator: &mut Evaluator =
-        &mut Evaluator::new(ast, FunctionMapper::new(), VariableMapper::new());
-    setup_scope(function_scope_evaluator, params)?;
-    let function_return_value: LiteralValue = function_scope_evaluator.evaluate()?;
-    println!("function return value: {:?}", function_return_value);
+        &mut Evaluator::new(AST::new(), function_mapper, variable_mapper)
+            .in_function_body()
+            .with_method_mapper(Rc::clone(&evaluator.method_mapper));
+    function_scope_evaluator.line = evaluator.line;
+    // 部分適用で束縛された引数は、パラメータに続けて宣言する
+    setup_scope(
+        function_scope_evaluator,
+        params
+            .into_iter()
+            .chain(called_function.prologue().iter().cloned()),
+    )?;
+
+    // 呼び出しのフレームを積み、呼び出しのスタックを本体を評価するEvaluatorに引き渡す
+    let frame: usize = evaluator
+        .call_stack
+        .push(calling_function_name, evaluator.line)?;
+    function_scope_evaluator.call_stack = std::mem::take(&mut evaluator.call_stack);
+    let result: Result<LiteralValue, EvaluationError> =
+        function_scope_evaluator.evaluate_function_body(&called_function.declaration().body);
+    evaluator.call_stack = std::mem::take(&mut function_scope_evaluator.call_stack);
+    let function_return_value: LiteralValue = match result {
+        Ok(value) => {
+            evaluator.call_stack.pop(frame);
+            value
+        }
+        Err(error) => {
+            // エラーが起きた時点の履歴を残すため、フレームは取り除かない
+            evaluator
+                .call_stack
+                .fail_at(frame, function_scope_evaluator.line);
+            return Err(error);
+        }
+    };
+
+    check_return_type(
+        calling_function_name,
+        &called_function.declaration().return_type,
+        &function_return_value,
+        &evaluator.method_mapper,
+        evaluator.line,
+    )?;
 
     Ok(function_return_value)
 }
 
-/// 関数呼び出しの引数の情報を元に、呼び出した関数のbodyで用いるEvaluatorとVariableMapperを作成
+/// 戻り値が関数の戻り値の型に当てはまるかを検証する
 ///
-/// ## Arguments
+/// 型推論で戻り値の型が決まらなかった関数は検証しません。
 ///
-/// * `evaluator` - Evaluator
-/// * `function_params` - 関数呼び出しの引数の情報
+/// ## Raises
 ///
-/// ## Returns
+/// * `EvaluationError::ReturnTypeMismatch` - 戻り値が関数の戻り値の型に当てはまらない場合
+pub(crate) fn check_return_type(
+    function_name: &str,
+    return_type: &Type,
+    value: &LiteralValue,
+    method_mapper: &MethodMapper,
+    line: usize,
+) -> Result<(), EvaluationError> {
+    // 戻り値の型と一致していない
+    // let f: fn = (): int { return "a"; };
+    // f(); // ReturnTypeMismatch
+    if *return_type != Type::Infer
+        && !is_value_of_type_with_methods(value.clone(), return_type.clone(), method_mapper)
+    {
+        return Err(EvaluationError::ReturnTypeMismatch {
+            function_name: function_name.to_string(),
+            line,
+            expected: return_type.to_string(),
+            actual: literal_to_type(value.clone()).to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// 関数の部分適用を評価する
 ///
-/// * `Result<(), EvaluationError>` - Ok(())
+/// 渡された引数をパラメータに束縛し、残りのパラメータを受け取る新しい関数を返します。
+/// 束縛された引数は、新しい関数の本体の前に変数として宣言されます。
+/// 元の関数の宣言は複製せず、新しい関数と共有します。
+/// 残りのパラメータは、宣言順、型、デフォルト値を保ったまま新しい関数のパラメータとなります。
+///
+/// ```shot
+/// let add: fn = (x: int, y: int): int { return x + y; };
+/// let inc: fn = add(x: 1, ...);
+/// # inc は次の関数と同じ
+/// # (y: int): int { let x: int = 1; return x + y; }
+/// ```
+///
+/// 可変長パラメータに引数が渡された場合は、可変長パラメータも束縛されます。
 ///
 /// ## Raises
 ///
-/// * `EvaluationError` - 変数のセットに失敗（同じ変数名が存在する場合など）
-fn setup_scope(
+/// * `bind_params` と同じエラー (デフォルト値のない引数が省略された場合を除く)
+pub(crate) fn partial_application_evaluator(
     evaluator: &mut Evaluator,
-    function_params: Vec<(String, Type, LiteralValue)>,
-) -> Result<(), EvaluationError> {
-    for param in function_params {
-        let node: VariableDeclarationNode = VariableDeclarationNode {
-            name: param.0.clone(),
-            var_type: param.1.clone(),
-            value: Box::new(generate_literal_node(param.2)),
-        };
-        evaluator.variable_mapper.set(evaluator.line, node)?;
-    }
-    Ok(())
+    node: &FunctionCallNode,
+) -> Result<LiteralValue, EvaluationError> {
+    let arguments: Vec<PassedArgument> = evaluate_arguments(evaluator, &node.arguments)?;
+    let (called_function, bound_arguments, _) =
+        select_called_function(evaluator, &node.name, |overload| {
+            bind_arguments(
+                &node.name,
+                &arguments,
+                overload,
+                &evaluator.method_mapper,
+                evaluator.line,
+            )
+        })?;
+
+    Ok(LiteralValue::Function(Rc::new(apply_partially(
+        &called_function,
+        bound_arguments,
+    ))))
 }
 
-fn generate_literal_node(value: LiteralValue) -> ExpressionNode {
-    ExpressionNode::Literal(Box::new(LiteralNode { value }))
+/// 呼び出された関数のオーバーロードから、`bind` で引数を束縛できる関数を選ぶ
+///
+/// 関数として宣言されていない場合は、関数を値に持つ変数 (部分適用で作られた関数など) を探します。
+/// オーバーロードは `FunctionMapper` から借用し、選ばれた関数だけを `Rc` で共有します。
+/// `bind` には、関数のパラメータ (関数の値の場合は束縛されていないパラメータ) を渡します。
+/// 選ばれた関数、束縛の結果、関数が宣言されたスコープの深さを返します。
+///
+/// ## Raises
+///
+/// * `EvaluationError::FunctionNotFound` - 関数も変数も見つからない場合
+/// * `EvaluationError::NotCallable` - 変数の値が関数でない場合
+/// * `select_overload` と同じエラー
+fn select_called_function<T>(
+    evaluator: &Evaluator,
+    name: &str,
+    bind: impl Fn(&[ParameterNode]) -> Result<T, EvaluationError>,
+) -> Result<(CalledFunction, T, usize), EvaluationError> {
+    if let Some(depth) = evaluator.function_mapper.depth_of(name) {
+        let overloads: &[Rc<FunctionDeclarationNode>] =
+            evaluator.function_mapper.get(name, evaluator.line)?;
+        let (called_function, bound) =
+            select_overload(name, overloads, evaluator.line, |overload| {
+                bind(&overload.params)
+            })?;
+        return Ok((CalledFunction::Declared(called_function), bound, depth));
+    }
+
+    // let inc: fn = add(x: 1, ...);
+    // inc(y: 2);
+    let depth: usize = match evaluator.variable_mapper.depth_of(name) {
+        Some(depth) => depth,
+        None => {
+            return Err(EvaluationError::FunctionNotFound {
+                name: name.to_string(),
+                line: evaluator.line,
+            })
+        }
+    };
+    let value: LiteralValue = evaluator.variable_mapper.get(name, evaluator.line)?;
+    match value {
+        LiteralValue::Function(function) => {
+            let (called_function, bound) =
+                select_overload(name, &[function], evaluator.line, |value| {
+                    bind(&value.params)
+                })?;
+            Ok((CalledFunction::Value(called_function), bound, depth))
+        }
+        // let x: int = 1;
+        // x();  # NotCallable
+        value => Err(EvaluationError::NotCallable {
+            name: name.to_string(),
+            actual: literal_to_type(value).to_string(),
+            line: evaluator.line,
+        }),
+    }
 }
 
-/// 関数呼び出しの引数の情報を元に、呼び出された関数の引数の情報を検証
+/// 呼び出された関数の本体で参照できるスコープを作成
 ///
-/// - 引数の数が一致しているか
-/// - 引数の名前が一致しているか
-/// - 型が一致しているかを検証
+/// グローバルに宣言された関数の本体は、パラメータと本体で宣言された変数と関数のみ参照できます。
+/// 関数の中で宣言された関数の本体は、加えて宣言された位置から見える変数と関数を参照できます。
+/// どちらの場合も、パラメータは新しいスコープに置かれるため、外側の同名の変数をシャドーイングします。
+///
+/// ```shot
+/// let f: fn = (x: int): int {
+///     let offset: int = 1;
+///     let g: fn = (y: int): int {
+///         return x + y + offset;  # f のパラメータと変数を参照できる
+///     };
+///     return g(y: 2);
+/// };
+/// ```
+fn capture_enclosing_scope(
+    evaluator: &Evaluator,
+    depth: usize,
+) -> (FunctionMapper, VariableMapper) {
+    if !evaluator.is_function_body {
+        return (FunctionMapper::new(), VariableMapper::new());
+    }
+
+    // 関数が宣言されたスコープまでを引き継ぐ
+    // 関数と変数のスコープはブロックごとに同時に積まれるので、深さは一致する
+    let mut function_mapper: FunctionMapper = evaluator.function_mapper.capture(depth);
+    let mut variable_mapper: VariableMapper = evaluator.variable_mapper.capture(depth);
+    function_mapper.push_scope();
+    variable_mapper.push_scope();
+    (function_mapper, variable_mapper)
+}
+
+/// 関数呼び出しの引数の情報を元に、呼び出した関数のbodyで用いるEvaluatorとVariableMapperを作成
 ///
 /// ## Arguments
 ///
 /// * `evaluator` - Evaluator
-/// * `calling_function_name` - 関数呼び出しの関数名
-/// * `calling_function_params` - 関数呼び出しの引数の情報
-/// * `called_function_params` - 呼び出された関数の引数の情報
+/// * `function_params` - 関数呼び出しの引数の情報
 ///
 /// ## Returns
 ///
-/// * `Result<Vec<(String, Type, LiteralValue)>, EvaluationError>` - Ok(Vec<(String, Type, LiteralValue)>)
+/// * `Result<(), EvaluationError>` - Ok(())
 ///
 /// ## Raises
 ///
-/// * `EvaluationError::ArgumentLengthError` - 引数の数が一致しない場合
-/// * `EvaluationError::ParameterTypeMismatch` - 引数の型が一致しない場合
-/// * `EvaluationError::ParameterNotFound` - 引数の名前が一致しない場合
-fn validate_params(
+/// * `EvaluationError` - 変数のセットに失敗（同じ変数名が存在する場合など）
+fn setup_scope(
     evaluator: &mut Evaluator,
-    calling_function_name: String,
-    calling_function_params: Vec<(String, ExpressionNode)>,
-    called_function_params: Vec<(String, Type)>,
-) -> Result<Vec<(String, Type, LiteralValue)>, EvaluationError> {
-    // 引数の数が一致しているかチェック
-    if calling_function_params.len() != called_function_params.len() {
-        return Err(EvaluationError::ArgumentLengthError {
-            function_name: calling_function_name.clone(),
-            expected: called_function_params.len(),
-            actual: calling_function_params.len(),
-            line: evaluator.line,
-        });
+    function_params: impl IntoIterator<Item = (String, Type, LiteralValue)>,
+) -> Result<(), EvaluationError> {
+    for (name, _, value) in function_params {
+        evaluator
+            .variable_mapper
+            .set(evaluator.line, &name, value)?;
     }
+    Ok(())
+}
 
-    // 戻り値の定義
-    let mut result: Vec<(String, Type, LiteralValue)> = vec![];
-
-    // 引数の型が一致しているかチェック
-    let mut i: usize = 0;
-    while i < calling_function_params.len() {
-        let calling_function_param_name: String = calling_function_params[i].0.clone();
-        let calling_function_param_value: LiteralValue =
-            evaluate_expression(evaluator, calling_function_params[i].1.clone())?;
-        let calling_function_param_type: Type =
-            literal_to_type(calling_function_param_value.clone());
-
-        // indexを使った処理はここで終わるので、インクリメント
-        i += 1;
-
-        match search_argument_name_in_called_function_params(
-            calling_function_param_name.clone(),
-            called_function_params.clone(),
-        ) {
-            Some(called_param_type) => {
-                // パラメータの型と一致していない
-                // let f: fn = (x: int): Void { return none; };
-                // f(x: 1.0); // ParameterTypeMismatch
-                if calling_function_param_type.clone() != called_param_type.clone() {
-                    return Err(EvaluationError::ParameterTypeMismatch {
-                        function_name: calling_function_name.clone(),
-                        param_name: calling_function_param_name.clone(),
-                        line: evaluator.line,
-                        expected: called_param_type.to_string(),
-                        actual: calling_function_param_type.to_string(),
-                    });
-                }
-                result.push((
-                    calling_function_param_name,
-                    called_param_type,
-                    calling_function_param_value,
-                ));
-            }
-            // パラメータの名前付き引数の名前解決に失敗
-            // let f: fn = (x: int): Void { return none; };
-            // f(z: 1); // ParameterNotFound
-            None => {
-                return Err(EvaluationError::ParameterNotFound {
-                    function_name: calling_function_name.clone(),
-                    param_name: calling_function_param_name.clone(),
-                    line: evaluator.line,
-                });
-            }
-        }
+/// 渡された引数を、左から順に評価する
+fn evaluate_arguments(
+    evaluator: &mut Evaluator,
+    arguments: &[ArgumentNode],
+) -> Result<Vec<PassedArgument>, EvaluationError> {
+    let mut passed_arguments: Vec<PassedArgument> = vec![];
+    for argument in arguments.iter() {
+        passed_arguments.push(PassedArgument {
+            name: argument.name.clone(),
+            value: evaluate_expression(evaluator, &argument.value)?,
+            is_spread: argument.is_spread,
+        });
     }
-    Ok(result)
+    Ok(passed_arguments)
 }
 
-/// 呼び出しに用いられた引数の名前が、呼び出された関数の引数の中に存在するかを検証
+/// 省略された引数のデフォルト値を、関数が宣言された位置から見えるスコープで評価する
 ///
-/// ## Arguments
+/// `enclosing_depth` は関数が宣言されたスコープの深さです。
+/// `None` の場合 (メソッド) は、外側のスコープを参照せずに評価します。
 ///
-/// * `name` - 引数の名前
-/// * `params` - 呼び出された関数の引数の情報
+/// ```shot
+/// let f: fn = (x: int): int {
+///     let offset: int = 1;
+///     let g: fn = (y: int = offset): int { return x + y; };  # f の変数を参照できる
+///     return g();
+/// };
+/// ```
 ///
-/// ## Returns
+/// ## Raises
 ///
-/// * `Option<Type>` - 呼び出された関数の引数の型
-fn search_argument_name_in_called_function_params(
-    name: String,
-    params: Vec<(String, Type)>,
-) -> Option<Type> {
-    for param in params {
-        if param.0 == name {
-            return Some(param.1);
-        }
-    }
-    None
-}
+/// * `EvaluationError::ParameterTypeMismatch` - デフォルト値の型が一致しない場合
+fn evaluate_default_values(
+    evaluator: &mut Evaluator,
+    calling_function_name: &str,
+    called_function_params: &[ParameterNode],
+    enclosing_depth: Option<usize>,
+    bound_params: BoundParams,
+) -> Result<Vec<(String, Type, LiteralValue)>, EvaluationError> {
+    let (function_mapper, variable_mapper) = match enclosing_depth {
+        Some(depth) => (
+            evaluator.function_mapper.capture(depth),
+            evaluator.variable_mapper.capture(depth),
+        ),
+        None => (FunctionMapper::new(), VariableMapper::new()),
+    };
+    let mut default_scope_evaluator: Evaluator =
+        Evaluator::new(AST::new(), function_mapper, variable_mapper)
+            .with_method_mapper(Rc::clone(&evaluator.method_mapper));
+    default_scope_evaluator.line = evaluator.line;
+    default_scope_evaluator.is_function_body = evaluator.is_function_body;
+    // デフォルト値の式で呼び出された関数のフレームも、呼び出し元と同じスタックに積む
+    default_scope_evaluator.call_stack = std::mem::take(&mut evaluator.call_stack);
 
-fn literal_to_type(value: LiteralValue) -> Type {
-    match value {
-        LiteralValue::Integer(_) => Type::Integer,
-        LiteralValue::Float(_) => Type::Float,
-        LiteralValue::String(_) => Type::String,
-        LiteralValue::None => Type::Void,
-    }
+    let result: Result<Vec<(String, Type, LiteralValue)>, EvaluationError> = called_function_params
+        .iter()
+        .zip(bound_params)
+        .map(|(called_param, (name, param_type, value))| {
+            let value: LiteralValue = match (value, &called_param.default_value) {
+                (Some(value), _) => value,
+                (None, Some(default_value)) => {
+                    let value: LiteralValue =
+                        evaluate_expression(&mut default_scope_evaluator, default_value)?;
+                    validate_param_type(
+                        calling_function_name,
+                        called_param,
+                        &value,
+                        &evaluator.method_mapper,
+                        evaluator.line,
+                    )?;
+                    value
+                }
+                (None, None) => {
+                    unreachable!("bind_params leaves only parameters with default values")
+                }
+            };
+            Ok((name, param_type, value))
+        })
+        .collect();
+    evaluator.call_stack = std::mem::take(&mut default_scope_evaluator.call_stack);
+    result
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::virtual_machine::ast::{Statement, VariableCallNode};
-    use crate::virtual_machine::ast::{FunctionCallNode, FunctionDeclarationNode, LiteralNode};
-    use crate::virtual_machine::ast::{ExpressionNode, LiteralValue, Type, VariableDeclarationNode, AST};
+    use crate::virtual_machine::ast::{ArgumentNode, Statement, VariableCallNode};
+    use std::rc::Rc;
+    use crate::virtual_machine::ast::{FunctionCallNode, FunctionDeclarationNode, LiteralNode, ParameterNode};
+    use crate::virtual_machine::ast::{ExpressionNode, LiteralValue, Type, AST};
     use crate::virtual_machine::evaluator::core::initialize_evaluator_with_custom_ast;
+    use crate::virtual_machine::evaluator::argument_binder::{bind_params, BoundParams, PassedArgument};
     use crate::virtual_machine::evaluator::evaluation_error::EvaluationError;
     use crate::virtual_machine::evaluator::Evaluator;
     use crate::virtual_machine::evaluator::mapper::function_mapper::FunctionMapper;
     use crate::virtual_machine::evaluator::mapper::variable_mapper::VariableMapper;
-    use crate::virtual_machine::evaluator::statement_evaluator::expression_evaluator::call_of_function_evaluator::{call_of_function_evaluator, setup_scope, validate_params};
+    use crate::virtual_machine::evaluator::mapper::method_mapper::MethodMapper;
+    use crate::virtual_machine::ast::{BinaryOperationNode, BinaryOperator, MethodCallNode, MethodSignatureNode};
+    use crate::virtual_machine::evaluator::statement_evaluator::expression_evaluator::call_of_function_evaluator::{call_of_function_evaluator, call_of_method_evaluator, partial_application_evaluator, evaluate_arguments, setup_scope};
 
     /// setup_scope 関数は引数の情報を元にevaluatorを正しく初期化する
     ///
@@ -230,13 +472,7 @@ mod tests {
     #[test]
     fn test_setup_scope() {
         // 期待される値
-        let expected: VariableDeclarationNode = VariableDeclarationNode {
-            name: "x".to_string(),
-            var_type: Type::Integer,
-            value: Box::new(ExpressionNode::Literal(Box::new(LiteralNode {
-                value: LiteralValue::Integer(1),
-            }))),
-        };
+        let expected: LiteralValue = LiteralValue::Integer(1);
 
         // テスト対象のセットアップ
         let ast: AST = AST::new();
@@ -250,138 +486,185 @@ mod tests {
 
         // 結果の検証
         assert_eq!(r, Ok(()));
-        let variable_data: VariableDeclarationNode =
-            match function_scope_evaluator.variable_mapper.get("x", 0) {
-                Ok(v) => v,
-                Err(_) => panic!("test_setup_scope failed: 作成した変数が見つかりません"),
-            };
+        let variable_data: LiteralValue = match function_scope_evaluator.variable_mapper.get("x", 0)
+        {
+            Ok(v) => v,
+            Err(_) => panic!("test_setup_scope failed: 作成した変数が見つかりません"),
+        };
         assert_eq!(variable_data, expected);
     }
 
-    /// validate_params 関数は正しい関数への入力を正常に検証する
+    /// bind_params 関数は正しい関数への入力を正常に検証する
     ///
     /// let f: fn = (x: int, y: float): int { return x; };
     /// f(x: 1, y: 1.0); -- 正常な関数呼び出し
     #[test]
-    fn test_validate_params() {
+    fn test_bind_params() {
         // 期待される値
-        let expected: Vec<(String, Type, LiteralValue)> = vec![
-            ("x".to_string(), Type::Integer, LiteralValue::Integer(1)),
-            ("y".to_string(), Type::Float, LiteralValue::Float(1.0)),
+        let expected: BoundParams = vec![
+            (
+                "x".to_string(),
+                Type::Integer,
+                Some(LiteralValue::Integer(1)),
+            ),
+            ("y".to_string(), Type::Float, Some(LiteralValue::Float(1.0))),
         ];
 
         // テスト対象のセットアップ
         let mut evaluator: Evaluator = initialize_evaluator_with_custom_ast(vec![]);
         let calling_function_name: String = "f".to_string();
-        let calling_function_params: Vec<(String, ExpressionNode)> = vec![
-            (
-                "x".to_string(),
-                ExpressionNode::Literal(Box::new(LiteralNode {
+        let calling_function_params: Vec<ArgumentNode> = vec![
+            ArgumentNode {
+                name: Some("x".to_string()),
+                value: ExpressionNode::Literal(Box::new(LiteralNode {
                     value: LiteralValue::Integer(1),
                 })),
-            ),
-            (
-                "y".to_string(),
-                ExpressionNode::Literal(Box::new(LiteralNode {
+                is_spread: false,
+            },
+            ArgumentNode {
+                name: Some("y".to_string()),
+                value: ExpressionNode::Literal(Box::new(LiteralNode {
                     value: LiteralValue::Float(1.0),
                 })),
-            ),
+                is_spread: false,
+            },
         ];
-        let called_function_params: Vec<(String, Type)> = vec![
-            ("x".to_string(), Type::Integer),
-            ("y".to_string(), Type::Float),
+        let called_function_params: Vec<ParameterNode> = vec![
+            ParameterNode {
+                name: "x".to_string(),
+                param_type: Type::Integer,
+                default_value: None,
+                is_variadic: false,
+            },
+            ParameterNode {
+                name: "y".to_string(),
+                param_type: Type::Float,
+                default_value: None,
+                is_variadic: false,
+            },
         ];
 
         // テスト対象の実行
-        let r: Result<Vec<(String, Type, LiteralValue)>, EvaluationError> = validate_params(
-            &mut evaluator,
-            calling_function_name.clone(),
-            calling_function_params.clone(),
-            called_function_params.clone(),
+        let arguments: Vec<PassedArgument> =
+            evaluate_arguments(&mut evaluator, &calling_function_params).unwrap();
+        let r: Result<BoundParams, EvaluationError> = bind_params(
+            &calling_function_name,
+            &arguments,
+            &called_function_params,
+            &evaluator.method_mapper,
+            evaluator.line,
         );
 
         // 結果の検証
         assert_eq!(r, Ok(expected));
     }
 
-    /// validate_params 関数は引数の数が一致しない場合、エラーを返す
+    /// bind_params 関数はデフォルト値のない引数が省略された場合、エラーを返す
     ///
     /// let f: fn = (x: int, y: float): int { return x; };
-    /// f(x: 1); -- 引数の数が一致しない
+    /// f(x: 1); -- 引数yが省略されている
     #[test]
-    fn test_validate_params_argument_length_error() {
+    fn test_bind_params_missing_argument() {
         // テスト対象のセットアップ
         let mut evaluator: Evaluator = initialize_evaluator_with_custom_ast(vec![]);
         let calling_function_name: String = "f".to_string();
         // -- let f: fn = (x: int, y: float): int { return x; }; の引数
-        let called_function_params: Vec<(String, Type)> = vec![
-            ("x".to_string(), Type::Integer),
-            ("y".to_string(), Type::Float),
+        let called_function_params: Vec<ParameterNode> = vec![
+            ParameterNode {
+                name: "x".to_string(),
+                param_type: Type::Integer,
+                default_value: None,
+                is_variadic: false,
+            },
+            ParameterNode {
+                name: "y".to_string(),
+                param_type: Type::Float,
+                default_value: None,
+                is_variadic: false,
+            },
         ];
         // -- let f: fn = (x: int, y: float): int { return x; }; の引数のうち、yがない
-        let calling_function_params: Vec<(String, ExpressionNode)> = vec![(
-            "x".to_string(),
-            ExpressionNode::Literal(Box::new(LiteralNode {
+        let calling_function_params: Vec<ArgumentNode> = vec![ArgumentNode {
+            name: Some("x".to_string()),
+            value: ExpressionNode::Literal(Box::new(LiteralNode {
                 value: LiteralValue::Integer(1),
             })),
-        )];
+            is_spread: false,
+        }];
 
         // テスト対象の実行
-        let r: Result<Vec<(String, Type, LiteralValue)>, EvaluationError> = validate_params(
-            &mut evaluator,
-            calling_function_name.clone(),
-            calling_function_params.clone(),
-            called_function_params.clone(),
+        let arguments: Vec<PassedArgument> =
+            evaluate_arguments(&mut evaluator, &calling_function_params).unwrap();
+        let r: Result<BoundParams, EvaluationError> = bind_params(
+            &calling_function_name,
+            &arguments,
+            &called_function_params,
+            &evaluator.method_mapper,
+            evaluator.line,
         );
 
         // 結果の検証
         assert_eq!(
             r,
-            Err(EvaluationError::ArgumentLengthError {
+            Err(EvaluationError::MissingArgument {
                 function_name: calling_function_name.clone(),
-                expected: 2,
-                actual: 1,
+                param_name: "y".to_string(),
                 line: 0
             })
         );
     }
 
-    /// validate_params 関数は引数の名前が一致しない場合、エラーを返す
+    /// bind_params 関数は引数の名前が一致しない場合、エラーを返す
     ///
     /// let f: fn = (x: int, y: float): int { return x; };
     /// f(z: 1, y: 1.0); -- 引数の名前が一致しない
     #[test]
-    fn test_validate_params_parameter_not_found() {
+    fn test_bind_params_parameter_not_found() {
         // テスト対象のセットアップ
         let mut evaluator: Evaluator = initialize_evaluator_with_custom_ast(vec![]);
         let calling_function_name: String = "f".to_string();
         // -- let f: fn = (x: int, y: float): int { return x; }; の引数
-        let called_function_params: Vec<(String, Type)> = vec![
-            ("x".to_string(), Type::Integer),
-            ("y".to_string(), Type::Float),
+        let called_function_params: Vec<ParameterNode> = vec![
+            ParameterNode {
+                name: "x".to_string(),
+                param_type: Type::Integer,
+                default_value: None,
+                is_variadic: false,
+            },
+            ParameterNode {
+                name: "y".to_string(),
+                param_type: Type::Float,
+                default_value: None,
+                is_variadic: false,
+            },
         ];
         // -- zは存在しない引数
-        let calling_function_params: Vec<(String, ExpressionNode)> = vec![
-            (
-                "z".to_string(),
-                ExpressionNode::Literal(Box::new(LiteralNode {
+        let calling_function_params: Vec<ArgumentNode> = vec![
+            ArgumentNode {
+                name: Some("z".to_string()),
+                value: ExpressionNode::Literal(Box::new(LiteralNode {
                     value: LiteralValue::Integer(1),
                 })),
-            ),
-            (
-                "y".to_string(),
-                ExpressionNode::Literal(Box::new(LiteralNode {
+                is_spread: false,
+            },
+            ArgumentNode {
+                name: Some("y".to_string()),
+                value: ExpressionNode::Literal(Box::new(LiteralNode {
                     value: LiteralValue::Float(1.0),
                 })),
-            ),
+                is_spread: false,
+            },
         ];
 
         // テスト対象の実行
-        let r: Result<Vec<(String, Type, LiteralValue)>, EvaluationError> = validate_params(
-            &mut evaluator,
-            calling_function_name.clone(),
-            calling_function_params.clone(),
-            called_function_params.clone(),
+        let arguments: Vec<PassedArgument> =
+            evaluate_arguments(&mut evaluator, &calling_function_params).unwrap();
+        let r: Result<BoundParams, EvaluationError> = bind_params(
+            &calling_function_name,
+            &arguments,
+            &called_function_params,
+            &evaluator.method_mapper,
+            evaluator.line,
         );
 
         // 結果の検証
@@ -395,42 +678,57 @@ mod tests {
         );
     }
 
-    /// validate_params 関数は引数の型が一致しない場合、エラーを返す
+    /// bind_params 関数は引数の型が一致しない場合、エラーを返す
     ///
     /// let f: fn = (x: int, y: float): int { return x; };
     /// f(x: 1, y: 1); -- 引数yの型が一致しない
     #[test]
-    fn test_validate_params_parameter_type_mismatch() {
+    fn test_bind_params_parameter_type_mismatch() {
         // テスト対象のセットアップ
         let mut evaluator: Evaluator = initialize_evaluator_with_custom_ast(vec![]);
         let calling_function_name: String = "f".to_string();
         // -- let f: fn = (x: int, y: float): int { return x; }; の引数
-        let called_function_params: Vec<(String, Type)> = vec![
-            ("x".to_string(), Type::Integer),
-            ("y".to_string(), Type::Float),
+        let called_function_params: Vec<ParameterNode> = vec![
+            ParameterNode {
+                name: "x".to_string(),
+                param_type: Type::Integer,
+                default_value: None,
+                is_variadic: false,
+            },
+            ParameterNode {
+                name: "y".to_string(),
+                param_type: Type::Float,
+                default_value: None,
+                is_variadic: false,
+            },
         ];
         // -- yの型が一致しない: Expected: float, Actual: int
-        let calling_function_params: Vec<(String, ExpressionNode)> = vec![
-            (
-                "x".to_string(),
-                ExpressionNode::Literal(Box::new(LiteralNode {
+        let calling_function_params: Vec<ArgumentNode> = vec![
+            ArgumentNode {
+                name: Some("x".to_string()),
+                value: ExpressionNode::Literal(Box::new(LiteralNode {
                     value: LiteralValue::Integer(1),
                 })),
-            ),
-            (
-                "y".to_string(),
-                ExpressionNode::Literal(Box::new(LiteralNode {
+                is_spread: false,
+            },
+            ArgumentNode {
+                name: Some("y".to_string()),
+                value: ExpressionNode::Literal(Box::new(LiteralNode {
                     value: LiteralValue::Integer(1),
                 })),
-            ),
+                is_spread: false,
+            },
         ];
 
         // テスト対象の実行
-        let r: Result<Vec<(String, Type, LiteralValue)>, EvaluationError> = validate_params(
-            &mut evaluator,
-            calling_function_name.clone(),
-            calling_function_params.clone(),
-            called_function_params.clone(),
+        let arguments: Vec<PassedArgument> =
+            evaluate_arguments(&mut evaluator, &calling_function_params).unwrap();
+        let r: Result<BoundParams, EvaluationError> = bind_params(
+            &calling_function_name,
+            &arguments,
+            &called_function_params,
+            &evaluator.method_mapper,
+            evaluator.line,
         );
 
         // 結果の検証
@@ -446,121 +744,1211 @@ mod tests {
         );
     }
 
-    /// evaluate_call_of_function 関数は関数呼び出しを正常に評価する
+    /// bind_params 関数は順不同の名前付き引数をパラメータの宣言順に並べる
     ///
-    /// let f: fn = (): int { return 0; };
-    /// f(); -- 関数呼び出し
+    /// let f: fn = (x: int, y: float): int { return x; };
+    /// f(y: 1.0, x: 1); -- 引数の順番が宣言順と異なる
     #[test]
-    fn test_evaluate_call_of_function() {
+    fn test_bind_params_with_order_independent_arguments() {
         // 期待される値
-        let expected: LiteralValue = LiteralValue::Integer(0);
+        let expected: BoundParams = vec![
+            (
+                "x".to_string(),
+                Type::Integer,
+                Some(LiteralValue::Integer(1)),
+            ),
+            ("y".to_string(), Type::Float, Some(LiteralValue::Float(1.0))),
+        ];
 
         // テスト対象のセットアップ
-        let mut function_mapper: FunctionMapper = FunctionMapper::new();
-        match function_mapper.set(
-            0,
-            // -- let f: fn = (): int { return 0; };
-            FunctionDeclarationNode {
-                name: "f".to_string(),
-                params: vec![],
-                return_type: Type::Integer,
-                body: vec![Statement::Return(Box::new(ExpressionNode::Literal(
-                    Box::new(LiteralNode {
-                        value: LiteralValue::Integer(0),
-                    }),
-                )))],
+        let mut evaluator: Evaluator = initialize_evaluator_with_custom_ast(vec![]);
+        let calling_function_name: String = "f".to_string();
+        let calling_function_params: Vec<ArgumentNode> = vec![
+            ArgumentNode {
+                name: Some("y".to_string()),
+                value: ExpressionNode::Literal(Box::new(LiteralNode {
+                    value: LiteralValue::Float(1.0),
+                })),
+                is_spread: false,
             },
-        ) {
-            Ok(v) => v,
-            Err(_) => panic!("test_evaluate_call_of_function failed: 関数の登録に失敗しました"),
-        };
-        let mut evaluator: Evaluator =
-            Evaluator::new(AST::new(), function_mapper, VariableMapper::new());
-
-        // -- f(); の関数呼び出し
-        let node: FunctionCallNode = FunctionCallNode {
-            name: "f".to_string(),
-            arguments: vec![],
-        };
+            ArgumentNode {
+                name: Some("x".to_string()),
+                value: ExpressionNode::Literal(Box::new(LiteralNode {
+                    value: LiteralValue::Integer(1),
+                })),
+                is_spread: false,
+            },
+        ];
+        let called_function_params: Vec<ParameterNode> = vec![
+            ParameterNode {
+                name: "x".to_string(),
+                param_type: Type::Integer,
+                default_value: None,
+                is_variadic: false,
+            },
+            ParameterNode {
+                name: "y".to_string(),
+                param_type: Type::Float,
+                default_value: None,
+                is_variadic: false,
+            },
+        ];
 
         // テスト対象の実行
-        let r: Result<LiteralValue, EvaluationError> =
-            call_of_function_evaluator(&mut evaluator, node);
-        let returned_value: LiteralValue = match r {
-            Ok(v) => v,
-            Err(_) => panic!("test_evaluate_call_of_function failed: 関数呼び出しに失敗しました"),
-        };
+        let arguments: Vec<PassedArgument> =
+            evaluate_arguments(&mut evaluator, &calling_function_params).unwrap();
+        let r: Result<BoundParams, EvaluationError> = bind_params(
+            &calling_function_name,
+            &arguments,
+            &called_function_params,
+            &evaluator.method_mapper,
+            evaluator.line,
+        );
 
         // 結果の検証
-        assert_eq!(returned_value, expected);
+        assert_eq!(r, Ok(expected));
     }
 
-    /// evaluate_call_of_function 関数は引数を持つ関数呼び出しを正常に評価する
+    /// bind_params 関数は省略された引数のデフォルト値を評価せず、呼び出し元で評価するために残す
     ///
-    /// let f: fn = (x: int, y: float): int { return x; };
-    /// f(x: 1, y: 2.0); -- 引数を持つ関数呼び出し
+    /// let f: fn = (retries: int = 3, verbose: bool = false): int { return retries; };
+    /// f(verbose: true); -- retriesが省略されている
     #[test]
-    fn test_evaluate_call_of_function_with_arguments() {
+    fn test_bind_params_leaves_default_value() {
         // 期待される値
-        let expected: LiteralValue = LiteralValue::Integer(1);
+        let expected: BoundParams = vec![
+            ("retries".to_string(), Type::Integer, None),
+            (
+                "verbose".to_string(),
+                Type::Boolean,
+                Some(LiteralValue::Boolean(true)),
+            ),
+        ];
 
         // テスト対象のセットアップ
-        let mut function_mapper: FunctionMapper = FunctionMapper::new();
-        match function_mapper.set(
-            0,
-            // -- let f: fn = (x: int, y: float): int { return x; };
-            FunctionDeclarationNode {
-                name: "f".to_string(),
-                params: vec![
-                    ("x".to_string(), Type::Integer),
-                    ("y".to_string(), Type::Float),
-                ],
-                return_type: Type::Integer,
-                body: vec![Statement::Return(Box::new(ExpressionNode::CallOfVariable(
-                    Box::new(VariableCallNode {
-                        name: "x".to_string(),
-                    }),
-                )))],
+        let mut evaluator: Evaluator = initialize_evaluator_with_custom_ast(vec![]);
+        let calling_function_name: String = "f".to_string();
+        let calling_function_params: Vec<ArgumentNode> = vec![ArgumentNode {
+            name: Some("verbose".to_string()),
+            value: ExpressionNode::Literal(Box::new(LiteralNode {
+                value: LiteralValue::Boolean(true),
+            })),
+            is_spread: false,
+        }];
+        let called_function_params: Vec<ParameterNode> = vec![
+            ParameterNode {
+                name: "retries".to_string(),
+                param_type: Type::Integer,
+                default_value: Some(Box::new(ExpressionNode::Literal(Box::new(LiteralNode {
+                    value: LiteralValue::Integer(3),
+                })))),
+                is_variadic: false,
             },
-        ) {
-            Ok(v) => v,
-            Err(_) => panic!(
-                "test_evaluate_call_of_function_with_arguments failed: 関数の登録に失敗しました"
-            ),
-        };
-        let mut evaluator: Evaluator =
-            Evaluator::new(AST::new(), function_mapper, VariableMapper::new());
-
-        // -- f(x: 1, y: 2.0); の関数呼び出し
-        let node: FunctionCallNode = FunctionCallNode {
-            name: "f".to_string(),
-            arguments: vec![
-                (
-                    "x".to_string(),
-                    ExpressionNode::Literal(Box::new(LiteralNode {
-                        value: LiteralValue::Integer(1),
-                    })),
-                ),
-                (
-                    "y".to_string(),
-                    ExpressionNode::Literal(Box::new(LiteralNode {
-                        value: LiteralValue::Float(2.0),
-                    })),
-                ),
-            ],
-        };
+            ParameterNode {
+                name: "verbose".to_string(),
+                param_type: Type::Boolean,
+                default_value: Some(Box::new(ExpressionNode::Literal(Box::new(LiteralNode {
+                    value: LiteralValue::Boolean(false),
+                })))),
+                is_variadic: false,
+            },
+        ];
 
         // テスト対象の実行
-        let r: Result<LiteralValue, EvaluationError> =
-            call_of_function_evaluator(&mut evaluator, node);
-        let returned_value: LiteralValue = match r {
-            Ok(v) => v,
-            Err(_) => panic!(
-                "test_evaluate_call_of_function_with_arguments failed: 関数呼び出しに失敗しました"
-            ),
+        let arguments: Vec<PassedArgument> =
+            evaluate_arguments(&mut evaluator, &calling_function_params).unwrap();
+        let r: Result<BoundParams, EvaluationError> = bind_params(
+            &calling_function_name,
+            &arguments,
+            &called_function_params,
+            &evaluator.method_mapper,
+            evaluator.line,
+        );
+
+        // 結果の検証
+        assert_eq!(r, Ok(expected));
+    }
+
+    /// bind_params 関数は同じ名前の引数が複数回渡された場合、エラーを返す
+    ///
+    /// let f: fn = (x: int, y: int = 0): int { return x; };
+    /// f(x: 1, x: 2); -- 引数xが二度渡されている
+    #[test]
+    fn test_bind_params_duplicate_argument() {
+        // テスト対象のセットアップ
+        let mut evaluator: Evaluator = initialize_evaluator_with_custom_ast(vec![]);
+        let calling_function_name: String = "f".to_string();
+        let called_function_params: Vec<ParameterNode> = vec![
+            ParameterNode {
+                name: "x".to_string(),
+                param_type: Type::Integer,
+                default_value: None,
+                is_variadic: false,
+            },
+            ParameterNode {
+                name: "y".to_string(),
+                param_type: Type::Integer,
+                default_value: Some(Box::new(ExpressionNode::Literal(Box::new(LiteralNode {
+                    value: LiteralValue::Integer(0),
+                })))),
+                is_variadic: false,
+            },
+        ];
+        let calling_function_params: Vec<ArgumentNode> = vec![
+            ArgumentNode {
+                name: Some("x".to_string()),
+                value: ExpressionNode::Literal(Box::new(LiteralNode {
+                    value: LiteralValue::Integer(1),
+                })),
+                is_spread: false,
+            },
+            ArgumentNode {
+                name: Some("x".to_string()),
+                value: ExpressionNode::Literal(Box::new(LiteralNode {
+                    value: LiteralValue::Integer(2),
+                })),
+                is_spread: false,
+            },
+        ];
+
+        // テスト対象の実行
+        let arguments: Vec<PassedArgument> =
+            evaluate_arguments(&mut evaluator, &calling_function_params).unwrap();
+        let r: Result<BoundParams, EvaluationError> = bind_params(
+            &calling_function_name,
+            &arguments,
+            &called_function_params,
+            &evaluator.method_mapper,
+            evaluator.line,
+        );
+
+        // 結果の検証
+        assert_eq!(
+            r,
+            Err(EvaluationError::DuplicateArgument {
+                function_name: calling_function_name.clone(),
+                param_name: "x".to_string(),
+                line: 0
+            })
+        );
+    }
+
+    /// bind_params 関数は位置引数をパラメータの宣言順に束縛する
+    ///
+    /// let f: fn = (x: int, y: int): void { return none; };
+    /// f(1, y: 2); -- x = 1, y = 2
+    #[test]
+    fn test_bind_params_with_positional_arguments() {
+        // テスト対象のセットアップ
+        let mut evaluator: Evaluator = initialize_evaluator_with_custom_ast(vec![]);
+        let calling_function_name: String = "f".to_string();
+        let called_function_params: Vec<ParameterNode> = vec![
+            ParameterNode {
+                name: "x".to_string(),
+                param_type: Type::Integer,
+                default_value: None,
+                is_variadic: false,
+            },
+            ParameterNode {
+                name: "y".to_string(),
+                param_type: Type::Integer,
+                default_value: None,
+                is_variadic: false,
+            },
+        ];
+        let calling_function_params: Vec<ArgumentNode> = vec![
+            ArgumentNode {
+                name: None,
+                value: ExpressionNode::Literal(Box::new(LiteralNode {
+                    value: LiteralValue::Integer(1),
+                })),
+                is_spread: false,
+            },
+            ArgumentNode {
+                name: Some("y".to_string()),
+                value: ExpressionNode::Literal(Box::new(LiteralNode {
+                    value: LiteralValue::Integer(2),
+                })),
+                is_spread: false,
+            },
+        ];
+
+        // テスト対象の実行
+        let arguments: Vec<PassedArgument> =
+            evaluate_arguments(&mut evaluator, &calling_function_params).unwrap();
+        let r: Result<BoundParams, EvaluationError> = bind_params(
+            &calling_function_name,
+            &arguments,
+            &called_function_params,
+            &evaluator.method_mapper,
+            evaluator.line,
+        );
+
+        // 結果の検証
+        assert_eq!(
+            r,
+            Ok(vec![
+                (
+                    "x".to_string(),
+                    Type::Integer,
+                    Some(LiteralValue::Integer(1))
+                ),
+                (
+                    "y".to_string(),
+                    Type::Integer,
+                    Some(LiteralValue::Integer(2))
+                ),
+            ])
+        );
+    }
+
+    /// bind_params 関数は位置引数と名前付き引数で同じパラメータに束縛された場合にエラーを返す
+    ///
+    /// let f: fn = (x: int, y: int): void { return none; };
+    /// f(1, x: 2); -- DuplicateArgument
+    #[test]
+    fn test_bind_params_positional_and_named_argument_bound_to_same_parameter() {
+        // テスト対象のセットアップ
+        let mut evaluator: Evaluator = initialize_evaluator_with_custom_ast(vec![]);
+        let calling_function_name: String = "f".to_string();
+        let called_function_params: Vec<ParameterNode> = vec![
+            ParameterNode {
+                name: "x".to_string(),
+                param_type: Type::Integer,
+                default_value: None,
+                is_variadic: false,
+            },
+            ParameterNode {
+                name: "y".to_string(),
+                param_type: Type::Integer,
+                default_value: None,
+                is_variadic: false,
+            },
+        ];
+        let calling_function_params: Vec<ArgumentNode> = vec![
+            ArgumentNode {
+                name: None,
+                value: ExpressionNode::Literal(Box::new(LiteralNode {
+                    value: LiteralValue::Integer(1),
+                })),
+                is_spread: false,
+            },
+            ArgumentNode {
+                name: Some("x".to_string()),
+                value: ExpressionNode::Literal(Box::new(LiteralNode {
+                    value: LiteralValue::Integer(2),
+                })),
+                is_spread: false,
+            },
+        ];
+
+        // テスト対象の実行
+        let arguments: Vec<PassedArgument> =
+            evaluate_arguments(&mut evaluator, &calling_function_params).unwrap();
+        let r: Result<BoundParams, EvaluationError> = bind_params(
+            &calling_function_name,
+            &arguments,
+            &called_function_params,
+            &evaluator.method_mapper,
+            evaluator.line,
+        );
+
+        // 結果の検証
+        assert_eq!(
+            r,
+            Err(EvaluationError::DuplicateArgument {
+                function_name: calling_function_name.clone(),
+                param_name: "x".to_string(),
+                line: 0
+            })
+        );
+    }
+
+    /// bind_params 関数は可変長パラメータへの引数をリストにまとめる
+    ///
+    /// let join: fn = (prefix: string, ...parts: string): string { return prefix; };
+    /// join(prefix: "-", parts: "a", parts: ...["b", "c"]); -- parts = ["a", "b", "c"]
+    #[test]
+    fn test_bind_params_with_variadic_parameter() {
+        // 期待される値
+        let expected: BoundParams = vec![
+            (
+                "prefix".to_string(),
+                Type::String,
+                Some(LiteralValue::String("-".to_string())),
+            ),
+            (
+                "parts".to_string(),
+                Type::List(Box::new(Type::String)),
+                Some(LiteralValue::List(vec![
+                    LiteralValue::String("a".to_string()),
+                    LiteralValue::String("b".to_string()),
+                    LiteralValue::String("c".to_string()),
+                ])),
+            ),
+        ];
+
+        // テスト対象のセットアップ
+        let mut evaluator: Evaluator = initialize_evaluator_with_custom_ast(vec![]);
+        let calling_function_name: String = "join".to_string();
+        let called_function_params: Vec<ParameterNode> = vec![
+            ParameterNode {
+                name: "prefix".to_string(),
+                param_type: Type::String,
+                default_value: None,
+                is_variadic: false,
+            },
+            ParameterNode {
+                name: "parts".to_string(),
+                param_type: Type::String,
+                default_value: None,
+                is_variadic: true,
+            },
+        ];
+        let calling_function_params: Vec<ArgumentNode> = vec![
+            ArgumentNode {
+                name: Some("prefix".to_string()),
+                value: ExpressionNode::Literal(Box::new(LiteralNode {
+                    value: LiteralValue::String("-".to_string()),
+                })),
+                is_spread: false,
+            },
+            ArgumentNode {
+                name: Some("parts".to_string()),
+                value: ExpressionNode::Literal(Box::new(LiteralNode {
+                    value: LiteralValue::String("a".to_string()),
+                })),
+                is_spread: false,
+            },
+            ArgumentNode {
+                name: Some("parts".to_string()),
+                value: ExpressionNode::Literal(Box::new(LiteralNode {
+                    value: LiteralValue::List(vec![
+                        LiteralValue::String("b".to_string()),
+                        LiteralValue::String("c".to_string()),
+                    ]),
+                })),
+                is_spread: true,
+            },
+        ];
+
+        // テスト対象の実行
+        let arguments: Vec<PassedArgument> =
+            evaluate_arguments(&mut evaluator, &calling_function_params).unwrap();
+        let r: Result<BoundParams, EvaluationError> = bind_params(
+            &calling_function_name,
+            &arguments,
+            &called_function_params,
+            &evaluator.method_mapper,
+            evaluator.line,
+        );
+
+        // 結果の検証
+        assert_eq!(r, Ok(expected));
+    }
+
+    /// bind_params 関数は可変長パラメータの要素の型が一致しない場合、エラーを返す
+    ///
+    /// let join: fn = (...parts: string): string { return "";  };
+    /// join(parts: "a", parts: 1); -- 2つ目の要素の型が一致しない
+    #[test]
+    fn test_bind_params_variadic_parameter_type_mismatch() {
+        // テスト対象のセットアップ
+        let mut evaluator: Evaluator = initialize_evaluator_with_custom_ast(vec![]);
+        let calling_function_name: String = "join".to_string();
+        let called_function_params: Vec<ParameterNode> = vec![ParameterNode {
+            name: "parts".to_string(),
+            param_type: Type::String,
+            default_value: None,
+            is_variadic: true,
+        }];
+        let calling_function_params: Vec<ArgumentNode> = vec![
+            ArgumentNode {
+                name: Some("parts".to_string()),
+                value: ExpressionNode::Literal(Box::new(LiteralNode {
+                    value: LiteralValue::String("a".to_string()),
+                })),
+                is_spread: false,
+            },
+            ArgumentNode {
+                name: Some("parts".to_string()),
+                value: ExpressionNode::Literal(Box::new(LiteralNode {
+                    value: LiteralValue::Integer(1),
+                })),
+                is_spread: false,
+            },
+        ];
+
+        // テスト対象の実行
+        let arguments: Vec<PassedArgument> =
+            evaluate_arguments(&mut evaluator, &calling_function_params).unwrap();
+        let r: Result<BoundParams, EvaluationError> = bind_params(
+            &calling_function_name,
+            &arguments,
+            &called_function_params,
+            &evaluator.method_mapper,
+            evaluator.line,
+        );
+
+        // 結果の検証
+        assert_eq!(
+            r,
+            Err(EvaluationError::ParameterTypeMismatch {
+                function_name: calling_function_name.clone(),
+                param_name: "parts".to_string(),
+                line: 0,
+                expected: Type::String.to_string(),
+                actual: Type::Integer.to_string()
+            })
+        );
+    }
+
+    /// bind_params 関数は可変長パラメータ以外に展開した引数が渡された場合、エラーを返す
+    ///
+    /// let f: fn = (x: int): int { return x; };
+    /// f(x: ...[1]); -- xは可変長パラメータではない
+    #[test]
+    fn test_bind_params_invalid_spread_argument() {
+        // テスト対象のセットアップ
+        let mut evaluator: Evaluator = initialize_evaluator_with_custom_ast(vec![]);
+        let calling_function_name: String = "f".to_string();
+        let called_function_params: Vec<ParameterNode> = vec![ParameterNode {
+            name: "x".to_string(),
+            param_type: Type::Integer,
+            default_value: None,
+            is_variadic: false,
+        }];
+        let calling_function_params: Vec<ArgumentNode> = vec![ArgumentNode {
+            name: Some("x".to_string()),
+            value: ExpressionNode::Literal(Box::new(LiteralNode {
+                value: LiteralValue::List(vec![LiteralValue::Integer(1)]),
+            })),
+            is_spread: true,
+        }];
+
+        // テスト対象の実行
+        let arguments: Vec<PassedArgument> =
+            evaluate_arguments(&mut evaluator, &calling_function_params).unwrap();
+        let r: Result<BoundParams, EvaluationError> = bind_params(
+            &calling_function_name,
+            &arguments,
+            &called_function_params,
+            &evaluator.method_mapper,
+            evaluator.line,
+        );
+
+        // 結果の検証
+        assert_eq!(
+            r,
+            Err(EvaluationError::InvalidSpreadArgument {
+                function_name: calling_function_name.clone(),
+                param_name: "x".to_string(),
+                line: 0
+            })
+        );
+    }
+
+    /// evaluate_call_of_function 関数は関数呼び出しを正常に評価する
+    ///
+    /// let f: fn = (): int { return 0; };
+    /// f(); -- 関数呼び出し
+    #[test]
+    fn test_evaluate_call_of_function() {
+        // 期待される値
+        let expected: LiteralValue = LiteralValue::Integer(0);
+
+        // テスト対象のセットアップ
+        let mut function_mapper: FunctionMapper = FunctionMapper::new();
+        match function_mapper.set(
+            0,
+            // -- let f: fn = (): int { return 0; };
+            Rc::new(FunctionDeclarationNode {
+                name: "f".to_string(),
+                params: vec![],
+                return_type: Type::Integer,
+                body: vec![(
+                    1,
+                    Statement::Return(Box::new(ExpressionNode::Literal(Box::new(LiteralNode {
+                        value: LiteralValue::Integer(0),
+                    })))),
+                )],
+            }),
+        ) {
+            Ok(v) => v,
+            Err(_) => panic!("test_evaluate_call_of_function failed: 関数の登録に失敗しました"),
+        };
+        let mut evaluator: Evaluator =
+            Evaluator::new(AST::new(), function_mapper, VariableMapper::new());
+
+        // -- f(); の関数呼び出し
+        let node: FunctionCallNode = FunctionCallNode {
+            name: "f".to_string(),
+            arguments: vec![],
+        };
+
+        // テスト対象の実行
+        let r: Result<LiteralValue, EvaluationError> =
+            call_of_function_evaluator(&mut evaluator, &node);
+        let returned_value: LiteralValue = match r {
+            Ok(v) => v,
+            Err(_) => panic!("test_evaluate_call_of_function failed: 関数呼び出しに失敗しました"),
         };
 
         // 結果の検証
         assert_eq!(returned_value, expected);
     }
+
+    /// evaluate_call_of_function 関数は引数を持つ関数呼び出しを正常に評価する
+    ///
+    /// let f: fn = (x: int, y: float): int { return x; };
+    /// f(x: 1, y: 2.0); -- 引数を持つ関数呼び出し
+    #[test]
+    fn test_evaluate_call_of_function_with_arguments() {
+        // 期待される値
+        let expected: LiteralValue = LiteralValue::Integer(1);
+
+        // テスト対象のセットアップ
+        let mut function_mapper: FunctionMapper = FunctionMapper::new();
+        match function_mapper.set(
+            0,
+            // -- let f: fn = (x: int, y: float): int { return x; };
+            Rc::new(FunctionDeclarationNode {
+                name: "f".to_string(),
+                params: vec![
+                    ParameterNode {
+                        name: "x".to_string(),
+                        param_type: Type::Integer,
+                        default_value: None,
+                        is_variadic: false,
+                    },
+                    ParameterNode {
+                        name: "y".to_string(),
+                        param_type: Type::Float,
+                        default_value: None,
+                        is_variadic: false,
+                    },
+                ],
+                return_type: Type::Integer,
+                body: vec![(
+                    1,
+                    Statement::Return(Box::new(ExpressionNode::CallOfVariable(Box::new(
+                        VariableCallNode {
+                            name: "x".to_string(),
+                        },
+                    )))),
+                )],
+            }),
+        ) {
+            Ok(v) => v,
+            Err(_) => panic!(
+                "test_evaluate_call_of_function_with_arguments failed: 関数の登録に失敗しました"
+            ),
+        };
+        let mut evaluator: Evaluator =
+            Evaluator::new(AST::new(), function_mapper, VariableMapper::new());
+
+        // -- f(x: 1, y: 2.0); の関数呼び出し
+        let node: FunctionCallNode = FunctionCallNode {
+            name: "f".to_string(),
+            arguments: vec![
+                ArgumentNode {
+                    name: Some("x".to_string()),
+                    value: ExpressionNode::Literal(Box::new(LiteralNode {
+                        value: LiteralValue::Integer(1),
+                    })),
+                    is_spread: false,
+                },
+                ArgumentNode {
+                    name: Some("y".to_string()),
+                    value: ExpressionNode::Literal(Box::new(LiteralNode {
+                        value: LiteralValue::Float(2.0),
+                    })),
+                    is_spread: false,
+                },
+            ],
+        };
+
+        // テスト対象の実行
+        let r: Result<LiteralValue, EvaluationError> =
+            call_of_function_evaluator(&mut evaluator, &node);
+        let returned_value: LiteralValue = match r {
+            Ok(v) => v,
+            Err(_) => panic!(
+                "test_evaluate_call_of_function_with_arguments failed: 関数呼び出しに失敗しました"
+            ),
+        };
+
+        // 結果の検証
+        assert_eq!(returned_value, expected);
+    }
+
+    /// 関数の中で宣言された関数は、外側の関数のパラメータを参照できる
+    ///
+    /// let f: fn = (x: int): int {
+    ///     let g: fn = (): int { return x; };
+    ///     return g();
+    /// };
+    /// f(x: 1); -- 1
+    #[test]
+    fn test_evaluate_call_of_nested_function() {
+        let x: ParameterNode = ParameterNode {
+            name: "x".to_string(),
+            param_type: Type::Integer,
+            default_value: None,
+            is_variadic: false,
+        };
+        let return_x: Statement = Statement::Return(Box::new(ExpressionNode::CallOfVariable(
+            Box::new(VariableCallNode {
+                name: "x".to_string(),
+            }),
+        )));
+        let g: FunctionDeclarationNode = FunctionDeclarationNode {
+            name: "g".to_string(),
+            params: vec![],
+            return_type: Type::Integer,
+            body: vec![(1, return_x)],
+        };
+        let f: FunctionDeclarationNode = FunctionDeclarationNode {
+            name: "f".to_string(),
+            params: vec![x],
+            return_type: Type::Integer,
+            body: vec![
+                (1, Statement::DeclarationOfFunction(Rc::new(g))),
+                (
+                    1,
+                    Statement::Return(Box::new(ExpressionNode::CallOfFunction(Box::new(
+                        FunctionCallNode {
+                            name: "g".to_string(),
+                            arguments: vec![],
+                        },
+                    )))),
+                ),
+            ],
+        };
+        let mut function_mapper: FunctionMapper = FunctionMapper::new();
+        assert!(function_mapper.set(0, Rc::new(f)).is_ok());
+        let mut evaluator: Evaluator =
+            Evaluator::new(AST::new(), function_mapper, VariableMapper::new());
+
+        let node: FunctionCallNode = FunctionCallNode {
+            name: "f".to_string(),
+            arguments: vec![ArgumentNode {
+                name: Some("x".to_string()),
+                value: ExpressionNode::Literal(Box::new(LiteralNode {
+                    value: LiteralValue::Integer(1),
+                })),
+                is_spread: false,
+            }],
+        };
+        assert_eq!(
+            call_of_function_evaluator(&mut evaluator, &node),
+            Ok(LiteralValue::Integer(1))
+        );
+    }
+
+    /// グローバルに宣言された関数は、グローバルな変数を参照できない
+    ///
+    /// let x: int = 1;
+    /// let f: fn = (): int { return x; };
+    /// f(); -- VariableNotFound
+    #[test]
+    fn test_evaluate_call_of_global_function_cannot_see_globals() {
+        let mut function_mapper: FunctionMapper = FunctionMapper::new();
+        assert!(function_mapper
+            .set(
+                0,
+                Rc::new(FunctionDeclarationNode {
+                    name: "f".to_string(),
+                    params: vec![],
+                    return_type: Type::Integer,
+                    body: vec![(
+                        2,
+                        Statement::Return(Box::new(ExpressionNode::CallOfVariable(Box::new(
+                            VariableCallNode {
+                                name: "x".to_string(),
+                            }
+                        ))))
+                    )],
+                }),
+            )
+            .is_ok());
+        let mut variable_mapper: VariableMapper = VariableMapper::new();
+        assert!(variable_mapper
+            .set(0, "x", LiteralValue::Integer(1))
+            .is_ok());
+        let mut evaluator: Evaluator = Evaluator::new(AST::new(), function_mapper, variable_mapper);
+
+        let node: FunctionCallNode = FunctionCallNode {
+            name: "f".to_string(),
+            arguments: vec![],
+        };
+        assert_eq!(
+            call_of_function_evaluator(&mut evaluator, &node),
+            Err(EvaluationError::VariableNotFound {
+                name: "x".to_string(),
+                line: 2,
+            })
+        );
+    }
+
+    /// 部分適用は、束縛されなかったパラメータを受け取る関数を返す
+    ///
+    /// let add: fn = (x: int, y: int): int { return x + y; };
+    /// let inc: fn = add(x: 1, ...); -- add の宣言と、束縛された x = 1
+    /// inc(y: 2); -- 3
+    #[test]
+    fn test_evaluate_partial_application() {
+        let param = |name: &str| ParameterNode {
+            name: name.to_string(),
+            param_type: Type::Integer,
+            default_value: None,
+            is_variadic: false,
+        };
+        let add: FunctionDeclarationNode = FunctionDeclarationNode {
+            name: "add".to_string(),
+            params: vec![param("x"), param("y")],
+            return_type: Type::Integer,
+            body: vec![(
+                1,
+                Statement::Return(Box::new(ExpressionNode::CallOfVariable(Box::new(
+                    VariableCallNode {
+                        name: "x".to_string(),
+                    },
+                )))),
+            )],
+        };
+        let mut function_mapper: FunctionMapper = FunctionMapper::new();
+        assert!(function_mapper.set(0, Rc::new(add.clone())).is_ok());
+        let mut evaluator: Evaluator =
+            Evaluator::new(AST::new(), function_mapper, VariableMapper::new());
+
+        let argument = |name: &str, value: i64| ArgumentNode {
+            name: Some(name.to_string()),
+            value: ExpressionNode::Literal(Box::new(LiteralNode {
+                value: LiteralValue::Integer(value),
+            })),
+            is_spread: false,
+        };
+        let inc: LiteralValue = match partial_application_evaluator(
+            &mut evaluator,
+            &FunctionCallNode {
+                name: "add".to_string(),
+                arguments: vec![argument("x", 1)],
+            },
+        ) {
+            Ok(inc) => inc,
+            Err(e) => panic!("test_evaluate_partial_application failed: {}", e),
+        };
+        // 元の関数の宣言は複製せず、束縛された引数はプロローグに置く
+        match &inc {
+            LiteralValue::Function(value) => {
+                assert!(Rc::ptr_eq(
+                    &value.declaration,
+                    &evaluator.function_mapper.get("add", 0).unwrap()[0]
+                ));
+                assert_eq!(value.params, vec![param("y")]);
+                assert_eq!(
+                    value.prologue,
+                    vec![("x".to_string(), Type::Integer, LiteralValue::Integer(1))]
+                );
+            }
+            value => panic!("Expected a function, got {:?}", value),
+        }
+
+        // 部分適用で作られた関数を変数に束縛して呼び出す
+        assert!(evaluator.variable_mapper.set(0, "inc", inc).is_ok());
+        assert_eq!(
+            call_of_function_evaluator(
+                &mut evaluator,
+                &FunctionCallNode {
+                    name: "inc".to_string(),
+                    arguments: vec![argument("y", 2)],
+                },
+            ),
+            Ok(LiteralValue::Integer(1))
+        );
+    }
+
+    /// 関数でない変数を呼び出すとエラーになる
+    ///
+    /// let x: int = 1;
+    /// x(); -- NotCallable
+    #[test]
+    fn test_evaluate_call_of_variable_that_is_not_function() {
+        let mut variable_mapper: VariableMapper = VariableMapper::new();
+        assert!(variable_mapper
+            .set(0, "x", LiteralValue::Integer(1))
+            .is_ok());
+        let mut evaluator: Evaluator =
+            Evaluator::new(AST::new(), FunctionMapper::new(), variable_mapper);
+
+        assert_eq!(
+            call_of_function_evaluator(
+                &mut evaluator,
+                &FunctionCallNode {
+                    name: "x".to_string(),
+                    arguments: vec![],
+                },
+            ),
+            Err(EvaluationError::NotCallable {
+                name: "x".to_string(),
+                actual: "int".to_string(),
+                line: 0,
+            })
+        );
+    }
+
+    /// 引数の名前から、呼び出すオーバーロードが選ばれる
+    ///
+    /// let open: fn = (path: string): int { return 0; };
+    /// let open: fn = (fd: int): int { return fd; };
+    /// open(fd: 3); -- 3
+    /// open(path: "a.txt", fd: 3); -- NoMatchingOverload
+    #[test]
+    fn test_evaluate_call_of_overloaded_function() {
+        let open =
+            |param_name: &str, param_type: Type, value: ExpressionNode| FunctionDeclarationNode {
+                name: "open".to_string(),
+                params: vec![ParameterNode {
+                    name: param_name.to_string(),
+                    param_type,
+                    default_value: None,
+                    is_variadic: false,
+                }],
+                return_type: Type::Integer,
+                body: vec![(1, Statement::Return(Box::new(value)))],
+            };
+        let mut function_mapper: FunctionMapper = FunctionMapper::new();
+        assert!(function_mapper
+            .set(
+                0,
+                Rc::new(open(
+                    "path",
+                    Type::String,
+                    ExpressionNode::Literal(Box::new(LiteralNode {
+                        value: LiteralValue::Integer(0),
+                    })),
+                )),
+            )
+            .is_ok());
+        assert!(function_mapper
+            .set(
+                0,
+                Rc::new(open(
+                    "fd",
+                    Type::Integer,
+                    ExpressionNode::CallOfVariable(Box::new(VariableCallNode {
+                        name: "fd".to_string(),
+                    })),
+                )),
+            )
+            .is_ok());
+        let mut evaluator: Evaluator =
+            Evaluator::new(AST::new(), function_mapper, VariableMapper::new());
+
+        let fd: ArgumentNode = ArgumentNode {
+            name: Some("fd".to_string()),
+            value: ExpressionNode::Literal(Box::new(LiteralNode {
+                value: LiteralValue::Integer(3),
+            })),
+            is_spread: false,
+        };
+        let path: ArgumentNode = ArgumentNode {
+            name: Some("path".to_string()),
+            value: ExpressionNode::Literal(Box::new(LiteralNode {
+                value: LiteralValue::String("a.txt".to_string()),
+            })),
+            is_spread: false,
+        };
+        assert_eq!(
+            call_of_function_evaluator(
+                &mut evaluator,
+                &FunctionCallNode {
+                    name: "open".to_string(),
+                    arguments: vec![fd.clone()],
+                },
+            ),
+            Ok(LiteralValue::Integer(3))
+        );
+        assert_eq!(
+            call_of_function_evaluator(
+                &mut evaluator,
+                &FunctionCallNode {
+                    name: "open".to_string(),
+                    arguments: vec![path, fd],
+                },
+            ),
+            Err(EvaluationError::NoMatchingOverload {
+                name: "open".to_string(),
+                line: 0,
+            })
+        );
+    }
+
+    /// 引数を束縛できるオーバーロードが複数あると、呼び出しが曖昧になる
+    ///
+    /// let f: fn = (a: int): int { return a; };
+    /// let f: fn = (b: int): int { return b; };
+    /// f(1); -- AmbiguousFunctionCall
+    #[test]
+    fn test_evaluate_call_of_ambiguous_overloaded_function() {
+        let f = |param_name: &str| FunctionDeclarationNode {
+            name: "f".to_string(),
+            params: vec![ParameterNode {
+                name: param_name.to_string(),
+                param_type: Type::Integer,
+                default_value: None,
+                is_variadic: false,
+            }],
+            return_type: Type::Integer,
+            body: vec![(
+                1,
+                Statement::Return(Box::new(ExpressionNode::CallOfVariable(Box::new(
+                    VariableCallNode {
+                        name: param_name.to_string(),
+                    },
+                )))),
+            )],
+        };
+        let mut function_mapper: FunctionMapper = FunctionMapper::new();
+        assert!(function_mapper.set(0, Rc::new(f("a"))).is_ok());
+        assert!(function_mapper.set(0, Rc::new(f("b"))).is_ok());
+        let mut evaluator: Evaluator =
+            Evaluator::new(AST::new(), function_mapper, VariableMapper::new());
+
+        assert_eq!(
+            call_of_function_evaluator(
+                &mut evaluator,
+                &FunctionCallNode {
+                    name: "f".to_string(),
+                    arguments: vec![ArgumentNode {
+                        name: None,
+                        value: ExpressionNode::Literal(Box::new(LiteralNode {
+                            value: LiteralValue::Integer(1),
+                        })),
+                        is_spread: false,
+                    }],
+                },
+            ),
+            Err(EvaluationError::AmbiguousFunctionCall {
+                name: "f".to_string(),
+                candidates: 2,
+                line: 0,
+            })
+        );
+    }
+
+    /// impl int { let double: fn = (self): int { return self + self; }; }
+    fn create_method_mapper() -> MethodMapper {
+        let mut method_mapper: MethodMapper = MethodMapper::new();
+        assert!(method_mapper
+            .set(
+                0,
+                "int",
+                Rc::new(FunctionDeclarationNode {
+                    name: "double".to_string(),
+                    params: vec![ParameterNode {
+                        name: "self".to_string(),
+                        param_type: Type::Integer,
+                        default_value: None,
+                        is_variadic: false,
+                    }],
+                    return_type: Type::Integer,
+                    body: vec![(
+                        1,
+                        Statement::Return(Box::new(ExpressionNode::BinaryOperation(Box::new(
+                            BinaryOperationNode {
+                                operator: BinaryOperator::Add,
+                                left: Box::new(ExpressionNode::CallOfVariable(Box::new(
+                                    VariableCallNode {
+                                        name: "self".to_string(),
+                                    },
+                                ))),
+                                right: Box::new(ExpressionNode::CallOfVariable(Box::new(
+                                    VariableCallNode {
+                                        name: "self".to_string(),
+                                    },
+                                ))),
+                            }
+                        ))))
+                    )],
+                }),
+            )
+            .is_ok());
+        method_mapper
+    }
+
+    fn create_method_call(receiver: LiteralValue, name: &str) -> MethodCallNode {
+        MethodCallNode {
+            receiver: Box::new(ExpressionNode::Literal(Box::new(LiteralNode {
+                value: receiver,
+            }))),
+            name: name.to_string(),
+            arguments: vec![],
+        }
+    }
+
+    /// call_of_method_evaluator 関数は呼び出し元の値を self に渡してメソッドを呼び出す
+    ///
+    /// impl int { let double: fn = (self): int { return self + self; }; }
+    /// 2.double(); -- 4
+    #[test]
+    fn test_evaluate_call_of_method() {
+        let mut evaluator: Evaluator =
+            Evaluator::new(AST::new(), FunctionMapper::new(), VariableMapper::new())
+                .with_method_mapper(Rc::new(create_method_mapper()));
+
+        assert_eq!(
+            call_of_method_evaluator(
+                &mut evaluator,
+                &create_method_call(LiteralValue::Integer(2), "double")
+            ),
+            Ok(LiteralValue::Integer(4))
+        );
+    }
+
+    /// call_of_method_evaluator 関数は値の型に実装されていないメソッドの呼び出しでエラーを返す
+    ///
+    /// impl int { let double: fn = (self): int { return self + self; }; }
+    /// "a".double(); -- MethodNotFound
+    #[test]
+    fn test_evaluate_call_of_method_not_implemented() {
+        let mut evaluator: Evaluator =
+            Evaluator::new(AST::new(), FunctionMapper::new(), VariableMapper::new())
+                .with_method_mapper(Rc::new(create_method_mapper()));
+
+        assert_eq!(
+            call_of_method_evaluator(
+                &mut evaluator,
+                &create_method_call(LiteralValue::String("a".to_string()), "double")
+            ),
+            Err(EvaluationError::MethodNotFound {
+                type_name: "string".to_string(),
+                name: "double".to_string(),
+                line: 0,
+            })
+        );
+    }
+
+    /// interface型のパラメータには、interfaceのメソッドを実装した型の値だけを渡せる
+    ///
+    /// interface Doubling { let double: fn = (self): int; }
+    /// let f: fn = (x: Doubling): int { return x.double(); };
+    /// f(x: 2);    -- 4
+    /// f(x: "a");  -- ParameterTypeMismatch
+    #[test]
+    fn test_evaluate_call_of_function_with_interface_parameter() {
+        let interface_type: Type = Type::Interface(
+            "Doubling".to_string(),
+            vec![MethodSignatureNode {
+                name: "double".to_string(),
+                params: vec![],
+                return_type: Type::Integer,
+            }],
+        );
+        let mut function_mapper: FunctionMapper = FunctionMapper::new();
+        assert!(function_mapper
+            .set(
+                0,
+                Rc::new(FunctionDeclarationNode {
+                    name: "f".to_string(),
+                    params: vec![ParameterNode {
+                        name: "x".to_string(),
+                        param_type: interface_type,
+                        default_value: None,
+                        is_variadic: false,
+                    }],
+                    return_type: Type::Integer,
+                    body: vec![(
+                        1,
+                        Statement::Return(Box::new(ExpressionNode::CallOfMethod(Box::new(
+                            MethodCallNode {
+                                receiver: Box::new(ExpressionNode::CallOfVariable(Box::new(
+                                    VariableCallNode {
+                                        name: "x".to_string(),
+                                    },
+                                ))),
+                                name: "double".to_string(),
+                                arguments: vec![],
+                            }
+                        ))))
+                    )],
+                }),
+            )
+            .is_ok());
+        let mut evaluator: Evaluator =
+            Evaluator::new(AST::new(), function_mapper, VariableMapper::new())
+                .with_method_mapper(Rc::new(create_method_mapper()));
+        let call = |value: LiteralValue| FunctionCallNode {
+            name: "f".to_string(),
+            arguments: vec![ArgumentNode {
+                name: Some("x".to_string()),
+                value: ExpressionNode::Literal(Box::new(LiteralNode { value })),
+                is_spread: false,
+            }],
+        };
+
+        assert_eq!(
+            call_of_function_evaluator(&mut evaluator, &call(LiteralValue::Integer(2))),
+            Ok(LiteralValue::Integer(4))
+        );
+        assert_eq!(
+            call_of_function_evaluator(
+                &mut evaluator,
+                &call(LiteralValue::String("a".to_string()))
+            ),
+            Err(EvaluationError::ParameterTypeMismatch {
+                function_name: "f".to_string(),
+                param_name: "x".to_string(),
+                line: 0,
+                expected: "Doubling".to_string(),
+                actual: "string".to_string(),
+            })
+        );
+    }
+
+    /// 戻り値が関数の戻り値の型に当てはまらない場合、エラーを返す
+    ///
+    /// let f: fn = (): int { return "a"; };
+    /// f(); -- ReturnTypeMismatch
+    #[test]
+    fn test_evaluate_call_of_function_return_type_mismatch() {
+        let mut function_mapper: FunctionMapper = FunctionMapper::new();
+        assert!(function_mapper
+            .set(
+                0,
+                Rc::new(FunctionDeclarationNode {
+                    name: "f".to_string(),
+                    params: vec![],
+                    return_type: Type::Integer,
+                    body: vec![(
+                        1,
+                        Statement::Return(Box::new(ExpressionNode::Literal(Box::new(
+                            LiteralNode {
+                                value: LiteralValue::String("a".to_string()),
+                            }
+                        ))))
+                    )],
+                }),
+            )
+            .is_ok());
+        let mut evaluator: Evaluator =
+            Evaluator::new(AST::new(), function_mapper, VariableMapper::new());
+
+        assert_eq!(
+            call_of_function_evaluator(
+                &mut evaluator,
+                &FunctionCallNode {
+                    name: "f".to_string(),
+                    arguments: vec![],
+                },
+            ),
+            Err(EvaluationError::ReturnTypeMismatch {
+                function_name: "f".to_string(),
+                line: 0,
+                expected: "int".to_string(),
+                actual: "string".to_string(),
+            })
+        );
+    }
 }
@@ -0,0 +1,28 @@
+use crate::virtual_machine::ast::{IfNode, LiteralValue};
+use crate::virtual_machine::evaluator::core::literal_to_type;
+use crate::virtual_machine::evaluator::evaluation_error::EvaluationError;
+use crate::virtual_machine::evaluator::statement_evaluator::expression_evaluator::evaluate_expression;
+use crate::virtual_machine::evaluator::Evaluator;
+
+/// 条件分岐を評価する
+///
+/// 条件が真の場合は真の分岐を、偽の場合は偽の分岐を評価します。
+/// 選ばれなかった分岐は評価しません。
+///
+/// ## Raises
+///
+/// * `EvaluationError::InvalidCondition` - 条件の値が `bool` でない場合
+pub(crate) fn evaluate_if(
+    evaluator: &mut Evaluator,
+    node: &IfNode,
+) -> Result<LiteralValue, EvaluationError> {
+    match evaluate_expression(evaluator, &node.condition)? {
+        LiteralValue::Boolean(true) => evaluate_expression(evaluator, &node.then_branch),
+        LiteralValue::Boolean(false) => evaluate_expression(evaluator, &node.else_branch),
+        // if 1 { ... } else { ... }  # InvalidCondition
+        value => Err(EvaluationError::InvalidCondition {
+            line: evaluator.line,
+            actual: literal_to_type(value).to_string(),
+        }),
+    }
+}
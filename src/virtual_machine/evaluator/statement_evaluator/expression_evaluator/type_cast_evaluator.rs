@@ -1,52 +1,135 @@
 use crate::virtual_machine::ast::{LiteralValue, Type, TypeCastNode};
-use crate::virtual_machine::evaluator::core::type_to_string;
+use crate::virtual_machine::evaluator::core::{
+    is_subtype, is_value_of_type, literal_to_type, type_to_string,
+};
 use crate::virtual_machine::evaluator::evaluation_error::EvaluationError;
 use crate::virtual_machine::evaluator::statement_evaluator::expression_evaluator::evaluate_expression;
 use crate::virtual_machine::evaluator::Evaluator;
 
+/// 型キャストを評価する
+///
+/// キャスト元の型は式の値から決まります。
+/// キャスト元の型が明示されている場合は、値がその型に当てはまるかを検査します。
+///
+/// `as?` によるキャストでは、値を変換できなかった場合に `none` を返します。
+///
+/// ## Raises
+///
+/// * `EvaluationError::TypeCastSourceMismatch` - 値の型が明示されたキャスト元の型と異なる場合
+/// * `EvaluationError::InvalidTypeCast` - キャストが定義されていない型の組み合わせの場合
+/// * `EvaluationError::FailedToTypeCast` - 値を変換できなかった場合 (`as?` を除く)
 pub(crate) fn evaluate_type_cast(
     evaluator: &mut Evaluator,
-    node: TypeCastNode,
+    node: &TypeCastNode,
 ) -> Result<LiteralValue, EvaluationError> {
-    let value: LiteralValue = evaluate_expression(evaluator, *node.expression)?;
-    match (node.from_type.clone(), node.to_type.clone()) {
-        (Type::Integer, Type::Float) => {
-            let float_value: f64 = value.to_string().parse().unwrap();
-            Ok(LiteralValue::Float(float_value))
+    let value: LiteralValue = evaluate_expression(evaluator, &node.expression)?;
+    cast_value(
+        value,
+        node.from_type.clone(),
+        node.to_type.clone(),
+        node.is_checked,
+        evaluator.line,
+    )
+}
+
+/// 評価済みの値に型キャストを適用する
+///
+/// `evaluate_type_cast` と同じ規則で、キャスト元の型の検査と `as?` の扱いを行います。
+pub(crate) fn cast_value(
+    value: LiteralValue,
+    from_type: Option<Type>,
+    to_type: Type,
+    is_checked: bool,
+    line: usize,
+) -> Result<LiteralValue, EvaluationError> {
+    let value_type: Type = literal_to_type(value.clone());
+
+    // "a" as int -> float;  # TypeCastSourceMismatch
+    if let Some(from_type) = from_type {
+        if !is_value_of_type(value.clone(), from_type.clone()) {
+            return Err(EvaluationError::TypeCastSourceMismatch {
+                line,
+                expected: type_to_string(from_type),
+                actual: type_to_string(value_type),
+            });
+        }
+    }
+
+    match cast_literal(value, to_type, line) {
+        // "a" as? int;  # none
+        Err(EvaluationError::FailedToTypeCast { .. }) if is_checked => Ok(LiteralValue::None),
+        result => result,
+    }
+}
+
+/// 値を指定した型に変換する
+///
+/// 値の型と同じ型や、`any` のような上位の型へのキャストは、値をそのまま返します。
+///
+/// newtypeで宣言された型へのキャストは、元の型の値をその型で包みます。
+/// newtypeの値から他の型へのキャストは、包まれた値を取り出してからキャストします。
+/// 異なるnewtype同士のキャストはできません。
+pub(crate) fn cast_literal(
+    value: LiteralValue,
+    to_type: Type,
+    line: usize,
+) -> Result<LiteralValue, EvaluationError> {
+    let from_type: Type = literal_to_type(value.clone());
+    let failed_to_type_cast = || EvaluationError::FailedToTypeCast {
+        line,
+        from_type: type_to_string(from_type.clone()),
+        to_type: type_to_string(to_type.clone()),
+        value: value.to_string(),
+    };
+
+    match (value.clone(), to_type.clone()) {
+        (_, to_type) if is_subtype(from_type.clone(), to_type.clone()) => Ok(value.clone()),
+        // newtype UserId = int;
+        // 1 as UserId;  # UserId(1)
+        (value, Type::Newtype(name, underlying_type))
+            if !matches!(value, LiteralValue::Newtype(..))
+                && is_value_of_type(value.clone(), *underlying_type.clone()) =>
+        {
+            Ok(LiteralValue::Newtype(name, Box::new(value)))
+        }
+        // id as int;  # 1
+        (LiteralValue::Newtype(_, inner_value), to_type)
+            if !matches!(to_type, Type::Newtype(..)) =>
+        {
+            cast_literal(*inner_value, to_type, line)
+        }
+        (LiteralValue::Integer(int_value), Type::Float) => {
+            Ok(LiteralValue::Float(int_value as f64))
         }
-        (Type::Integer, Type::String) => Ok(LiteralValue::String(value.to_string())),
-        (Type::Float, Type::Integer) => {
-            let int_value: i64 = value.to_string().parse().unwrap();
-            Ok(LiteralValue::Integer(int_value))
+        (LiteralValue::Integer(int_value), Type::String) => {
+            Ok(LiteralValue::String(int_value.to_string()))
         }
-        (Type::Float, Type::String) => Ok(LiteralValue::String(value.to_string())),
-        (Type::String, Type::Integer) => match value.to_string().parse() {
+        // 小数点以下は切り捨てる。整数で表せない値 (NaN, 範囲外) は変換できない
+        (LiteralValue::Float(float_value), Type::Integer) => {
+            let truncated: f64 = float_value.trunc();
+            if truncated.is_finite() && truncated >= i64::MIN as f64 && truncated < i64::MAX as f64
+            {
+                Ok(LiteralValue::Integer(truncated as i64))
+            } else {
+                Err(failed_to_type_cast())
+            }
+        }
+        (LiteralValue::Float(float_value), Type::String) => {
+            Ok(LiteralValue::String(float_value.to_string()))
+        }
+        (LiteralValue::String(string_value), Type::Integer) => match string_value.parse() {
             Ok(int_value) => Ok(LiteralValue::Integer(int_value)),
-            Err(_) => Err(EvaluationError::FailedToTypeCast {
-                line: evaluator.line,
-                from_type: type_to_string(node.from_type),
-                to_type: type_to_string(node.to_type),
-                value: value.to_string(),
-            }),
+            Err(_) => Err(failed_to_type_cast()),
         },
-        (Type::String, Type::Float) => match value.to_string().parse() {
+        (LiteralValue::String(string_value), Type::Float) => match string_value.parse() {
             Ok(float_value) => Ok(LiteralValue::Float(float_value)),
-            Err(_) => Err(EvaluationError::FailedToTypeCast {
-                line: evaluator.line,
-                from_type: type_to_string(node.from_type),
-                to_type: type_to_string(node.to_type),
-                value: value.to_string(),
-            }),
+            Err(_) => Err(failed_to_type_cast()),
         },
-        (_, _) => {
-            let from_type_name: String = type_to_string(node.from_type);
-            let to_type_name: String = type_to_string(node.to_type);
-            Err(EvaluationError::InvalidTypeCast {
-                line: evaluator.line,
-                from_type: from_type_name,
-                to_type: to_type_name,
-            })
-        }
+        (_, _) => Err(EvaluationError::InvalidTypeCast {
+            line,
+            from_type: type_to_string(from_type.clone()),
+            to_type: type_to_string(to_type),
+        }),
     }
 }
 
@@ -54,7 +137,8 @@ pub(crate) fn evaluate_type_cast(
 mod tests {
     use crate::virtual_machine::ast::{ExpressionNode, LiteralNode, LiteralValue, Statement, Type, TypeCastNode};
     use crate::virtual_machine::evaluator::core::initialize_evaluator_with_custom_ast;
-    use crate::virtual_machine::evaluator::statement_evaluator::expression_evaluator::type_cast_evaluator::evaluate_type_cast;
+    use crate::virtual_machine::evaluator::evaluation_error::EvaluationError;
+    use crate::virtual_machine::evaluator::statement_evaluator::expression_evaluator::type_cast_evaluator::{cast_literal, evaluate_type_cast};
     use crate::virtual_machine::evaluator::Evaluator;
 
     /// 数値表現であるInt型をFloat型にキャストしようとした場合、正常にキャストされることを確認します。
@@ -68,8 +152,9 @@ mod tests {
                 value: LiteralValue::Integer(1),
             })));
         let type_cast_expression: TypeCastNode = TypeCastNode {
-            from_type: Type::Integer,
+            from_type: Some(Type::Integer),
             to_type: Type::Float,
+            is_checked: false,
             expression: literal_integer_expression.clone(),
         };
         let mut evaluator: Evaluator =
@@ -77,7 +162,7 @@ mod tests {
                 *literal_integer_expression.clone(),
             )]);
         let actual: LiteralValue =
-            evaluate_type_cast(&mut evaluator, type_cast_expression).unwrap();
+            evaluate_type_cast(&mut evaluator, &type_cast_expression).unwrap();
         assert_eq!(actual, expected);
     }
 
@@ -92,8 +177,9 @@ mod tests {
                 value: LiteralValue::Integer(1),
             })));
         let type_cast_expression: TypeCastNode = TypeCastNode {
-            from_type: Type::Integer,
+            from_type: Some(Type::Integer),
             to_type: Type::String,
+            is_checked: false,
             expression: literal_integer_expression.clone(),
         };
         let mut evaluator: Evaluator =
@@ -101,7 +187,7 @@ mod tests {
                 *literal_integer_expression.clone(),
             )]);
         let actual: LiteralValue =
-            evaluate_type_cast(&mut evaluator, type_cast_expression).unwrap();
+            evaluate_type_cast(&mut evaluator, &type_cast_expression).unwrap();
         assert_eq!(actual, expected);
     }
 
@@ -116,8 +202,9 @@ mod tests {
                 value: LiteralValue::Float(1.0),
             })));
         let type_cast_expression: TypeCastNode = TypeCastNode {
-            from_type: Type::Float,
+            from_type: Some(Type::Float),
             to_type: Type::Integer,
+            is_checked: false,
             expression: literal_float_expression.clone(),
         };
         let mut evaluator: Evaluator =
@@ -125,7 +212,7 @@ mod tests {
                 *literal_float_expression.clone(),
             )]);
         let actual: LiteralValue =
-            evaluate_type_cast(&mut evaluator, type_cast_expression).unwrap();
+            evaluate_type_cast(&mut evaluator, &type_cast_expression).unwrap();
         assert_eq!(actual, expected);
     }
 
@@ -140,8 +227,9 @@ mod tests {
                 value: LiteralValue::Float(1.0),
             })));
         let type_cast_expression: Box<TypeCastNode> = Box::new(TypeCastNode {
-            from_type: Type::Float,
+            from_type: Some(Type::Float),
             to_type: Type::String,
+            is_checked: false,
             expression: literal_float_expression.clone(),
         });
         let mut evaluator: Evaluator =
@@ -149,7 +237,7 @@ mod tests {
                 *literal_float_expression.clone(),
             )]);
         let actual: LiteralValue =
-            evaluate_type_cast(&mut evaluator, *type_cast_expression).unwrap();
+            evaluate_type_cast(&mut evaluator, &type_cast_expression).unwrap();
         assert_eq!(actual, expected);
     }
 
@@ -164,8 +252,9 @@ mod tests {
                 value: LiteralValue::String("1".to_string()),
             })));
         let type_cast_expression: TypeCastNode = TypeCastNode {
-            from_type: Type::String,
+            from_type: Some(Type::String),
             to_type: Type::Integer,
+            is_checked: false,
             expression: literal_string_expression.clone(),
         };
         let mut evaluator: Evaluator =
@@ -173,7 +262,7 @@ mod tests {
                 *literal_string_expression.clone(),
             )]);
         let actual: LiteralValue =
-            evaluate_type_cast(&mut evaluator, type_cast_expression).unwrap();
+            evaluate_type_cast(&mut evaluator, &type_cast_expression).unwrap();
         assert_eq!(actual, expected);
     }
 
@@ -188,8 +277,9 @@ mod tests {
                 value: LiteralValue::String("1".to_string()),
             })));
         let type_cast_expression: TypeCastNode = TypeCastNode {
-            from_type: Type::String,
+            from_type: Some(Type::String),
             to_type: Type::Float,
+            is_checked: false,
             expression: literal_string_expression.clone(),
         };
         let mut evaluator: Evaluator =
@@ -197,7 +287,7 @@ mod tests {
                 *literal_string_expression.clone(),
             )]);
         let actual: LiteralValue =
-            evaluate_type_cast(&mut evaluator, type_cast_expression).unwrap();
+            evaluate_type_cast(&mut evaluator, &type_cast_expression).unwrap();
         assert_eq!(actual, expected);
     }
 
@@ -211,8 +301,9 @@ mod tests {
                 value: LiteralValue::String("a".to_string()),
             })));
         let type_cast_expression: TypeCastNode = TypeCastNode {
-            from_type: Type::String,
+            from_type: Some(Type::String),
             to_type: Type::Integer,
+            is_checked: false,
             expression: literal_string_expression.clone(),
         };
         let mut evaluator: Evaluator =
@@ -220,7 +311,7 @@ mod tests {
                 *literal_string_expression.clone(),
             )]);
         let actual: Result<LiteralValue, _> =
-            evaluate_type_cast(&mut evaluator, type_cast_expression);
+            evaluate_type_cast(&mut evaluator, &type_cast_expression);
         assert!(actual.is_err());
     }
 
@@ -234,8 +325,9 @@ mod tests {
                 value: LiteralValue::String("a".to_string()),
             })));
         let type_cast_expression: TypeCastNode = TypeCastNode {
-            from_type: Type::String,
+            from_type: Some(Type::String),
             to_type: Type::Float,
+            is_checked: false,
             expression: literal_string_expression.clone(),
         };
         let mut evaluator: Evaluator =
@@ -243,7 +335,7 @@ mod tests {
                 *literal_string_expression.clone(),
             )]);
         let actual: Result<LiteralValue, _> =
-            evaluate_type_cast(&mut evaluator, type_cast_expression);
+            evaluate_type_cast(&mut evaluator, &type_cast_expression);
         assert!(actual.is_err());
     }
 
@@ -257,8 +349,9 @@ mod tests {
                 value: LiteralValue::String("a".to_string()),
             })));
         let type_cast_expression: TypeCastNode = TypeCastNode {
-            from_type: Type::String,
+            from_type: Some(Type::String),
             to_type: Type::Function,
+            is_checked: false,
             expression: literal_string_expression.clone(),
         };
         let mut evaluator: Evaluator =
@@ -266,7 +359,127 @@ mod tests {
                 *literal_string_expression.clone(),
             )]);
         let actual: Result<LiteralValue, _> =
-            evaluate_type_cast(&mut evaluator, type_cast_expression);
+            evaluate_type_cast(&mut evaluator, &type_cast_expression);
         assert!(actual.is_err());
     }
+
+    /// キャスト元の型を省略した場合、値の型からキャスト元の型が決まることを確認します。
+    ///
+    /// 1.5 as int;  # 1
+    #[test]
+    fn test_type_cast_evaluator_infers_from_type() {
+        let expected: LiteralValue = LiteralValue::Integer(1);
+        let literal_float_expression: Box<ExpressionNode> =
+            Box::new(ExpressionNode::Literal(Box::new(LiteralNode {
+                value: LiteralValue::Float(1.5),
+            })));
+        let type_cast_expression: TypeCastNode = TypeCastNode {
+            from_type: None,
+            to_type: Type::Integer,
+            is_checked: false,
+            expression: literal_float_expression.clone(),
+        };
+        let mut evaluator: Evaluator = initialize_evaluator_with_custom_ast(vec![]);
+        let actual: LiteralValue =
+            evaluate_type_cast(&mut evaluator, &type_cast_expression).unwrap();
+        assert_eq!(actual, expected);
+    }
+
+    /// 明示したキャスト元の型と値の型が異なる場合、エラーが返されることを確認します。
+    ///
+    /// "x" as int -> float;  # Error
+    #[test]
+    fn test_type_cast_evaluator_fail_when_from_type_does_not_match_value() {
+        let literal_string_expression: Box<ExpressionNode> =
+            Box::new(ExpressionNode::Literal(Box::new(LiteralNode {
+                value: LiteralValue::String("x".to_string()),
+            })));
+        let type_cast_expression: TypeCastNode = TypeCastNode {
+            from_type: Some(Type::Integer),
+            to_type: Type::Float,
+            is_checked: false,
+            expression: literal_string_expression.clone(),
+        };
+        let mut evaluator: Evaluator = initialize_evaluator_with_custom_ast(vec![]);
+        let actual: Result<LiteralValue, EvaluationError> =
+            evaluate_type_cast(&mut evaluator, &type_cast_expression);
+        assert_eq!(
+            actual,
+            Err(EvaluationError::TypeCastSourceMismatch {
+                line: 0,
+                expected: "int".to_string(),
+                actual: "string".to_string(),
+            })
+        );
+    }
+
+    /// as? で変換できない値をキャストした場合、noneが返されることを確認します。
+    ///
+    /// "a" as? int;  # none
+    #[test]
+    fn test_type_cast_evaluator_checked_cast_returns_none() {
+        let literal_string_expression: Box<ExpressionNode> =
+            Box::new(ExpressionNode::Literal(Box::new(LiteralNode {
+                value: LiteralValue::String("a".to_string()),
+            })));
+        let type_cast_expression: TypeCastNode = TypeCastNode {
+            from_type: None,
+            to_type: Type::Integer,
+            is_checked: true,
+            expression: literal_string_expression.clone(),
+        };
+        let mut evaluator: Evaluator = initialize_evaluator_with_custom_ast(vec![]);
+        let actual: Result<LiteralValue, EvaluationError> =
+            evaluate_type_cast(&mut evaluator, &type_cast_expression);
+        assert_eq!(actual, Ok(LiteralValue::None));
+    }
+
+    /// newtypeの型で包んだ値を、元の型に取り出せることを確認します。
+    ///
+    /// newtype UserId = int;
+    /// (1 as UserId) as int;  # 1
+    #[test]
+    fn test_cast_literal_wraps_and_unwraps_newtype() {
+        let user_id: Type = Type::Newtype("UserId".to_string(), Box::new(Type::Integer));
+
+        let wrapped: LiteralValue =
+            cast_literal(LiteralValue::Integer(1), user_id.clone(), 1).unwrap();
+        assert_eq!(
+            wrapped,
+            LiteralValue::Newtype("UserId".to_string(), Box::new(LiteralValue::Integer(1)))
+        );
+        assert_eq!(
+            cast_literal(wrapped, Type::Integer, 1),
+            Ok(LiteralValue::Integer(1))
+        );
+        assert_eq!(
+            cast_literal(LiteralValue::String("1".to_string()), user_id, 1),
+            Err(EvaluationError::InvalidTypeCast {
+                line: 1,
+                from_type: "string".to_string(),
+                to_type: "UserId".to_string(),
+            })
+        );
+    }
+
+    /// 異なるnewtype同士のキャストがエラーとなることを確認します。
+    ///
+    /// newtype UserId = int;
+    /// newtype Port = int;
+    /// (1 as UserId) as Port;  # InvalidTypeCast
+    #[test]
+    fn test_cast_literal_fails_between_newtypes() {
+        let id: LiteralValue =
+            LiteralValue::Newtype("UserId".to_string(), Box::new(LiteralValue::Integer(1)));
+        let port: Type = Type::Newtype("Port".to_string(), Box::new(Type::Integer));
+
+        assert_eq!(
+            cast_literal(id, port, 1),
+            Err(EvaluationError::InvalidTypeCast {
+                line: 1,
+                from_type: "UserId".to_string(),
+                to_type: "Port".to_string(),
+            })
+        );
+    }
 }
@@ -0,0 +1,21 @@
+use crate::virtual_machine::ast::{LiteralValue, TypeTestNode};
+use crate::virtual_machine::evaluator::core::is_value_of_type_with_methods;
+use crate::virtual_machine::evaluator::evaluation_error::EvaluationError;
+use crate::virtual_machine::evaluator::statement_evaluator::expression_evaluator::evaluate_expression;
+use crate::virtual_machine::evaluator::Evaluator;
+
+/// 型の検査を評価する
+///
+/// 式の値が検査する型に当てはまる場合は `true`、当てはまらない場合は `false` を返します。
+/// interface型の検査では、値の型がinterfaceのメソッドを全て実装しているかを調べます。
+pub(crate) fn evaluate_type_test(
+    evaluator: &mut Evaluator,
+    node: &TypeTestNode,
+) -> Result<LiteralValue, EvaluationError> {
+    let value: LiteralValue = evaluate_expression(evaluator, &node.expression)?;
+    Ok(LiteralValue::Boolean(is_value_of_type_with_methods(
+        value,
+        node.tested_type.clone(),
+        &evaluator.method_mapper,
+    )))
+}
@@ -0,0 +1,143 @@
+use crate::virtual_machine::ast::{
+    BinaryOperator, ExpressionNode, FunctionCallNode, MethodCallNode, Type, TypeCastNode,
+};
+use crate::virtual_machine::evaluator::core::{is_subtype, literal_to_type};
+use std::collections::HashMap;
+
+/// 式の型を求めるために、パスごとに異なる処理を提供する
+///
+/// 型検査と型推論は、同じ規則で式の型を求めます。
+/// 変数と関数の参照、引数の検査、分岐の中のスコープなど、パスごとに異なる処理をこのトレイトで実装します。
+pub(crate) trait ExpressionTyper {
+    /// 変数の型を返す
+    fn variable_type(&self, name: &str) -> Option<Type>;
+
+    /// 関数呼び出しの戻り値の型を返す
+    fn function_call_type(&mut self, node: &FunctionCallNode) -> Option<Type>;
+
+    /// 関数の部分適用を処理する
+    fn partial_application(&mut self, node: &FunctionCallNode);
+
+    /// メソッド呼び出しの戻り値の型を返す
+    ///
+    /// レシーバの型が求められない場合と、レシーバがinterface型の場合は `receiver_type` は `None`
+    fn method_call_type(
+        &mut self,
+        node: &MethodCallNode,
+        receiver_type: Option<Type>,
+    ) -> Option<Type>;
+
+    /// キャスト対象の式の型 `actual` を処理する
+    fn type_cast(&mut self, _node: &TypeCastNode, _actual: Option<Type>) {}
+
+    /// 変数の型を `narrowed_types` で絞り込んだスコープで、式の型を求める
+    fn narrowed_type_of(
+        &mut self,
+        narrowed_types: HashMap<String, Type>,
+        expression: &ExpressionNode,
+    ) -> Option<Type>;
+}
+
+/// 式の型を求める
+///
+/// 型が求められない場合は `None` を返す
+pub(crate) fn type_of_expression(
+    typer: &mut impl ExpressionTyper,
+    expression: &ExpressionNode,
+) -> Option<Type> {
+    match expression {
+        ExpressionNode::Literal(node) => Some(literal_to_type(node.value.clone())),
+        ExpressionNode::BinaryOperation(node) => {
+            let left_type: Option<Type> = type_of_expression(typer, &node.left);
+            let right_type: Option<Type> = type_of_expression(typer, &node.right);
+            binary_operation_type(&node.operator, left_type?, right_type?)
+        }
+        ExpressionNode::CallOfVariable(node) => typer.variable_type(&node.name),
+        ExpressionNode::CallOfFunction(node) => typer.function_call_type(node),
+        ExpressionNode::PartialApplication(node) => {
+            typer.partial_application(node);
+            Some(Type::Function)
+        }
+        ExpressionNode::CallOfMethod(node) => match type_of_expression(typer, &node.receiver) {
+            // interface型の値のメソッド呼び出しは、interfaceで宣言された戻り値の型
+            Some(Type::Interface(_, signatures)) => {
+                typer.method_call_type(node, None);
+                signatures
+                    .into_iter()
+                    .find(|signature| signature.name == node.name)
+                    .map(|signature| signature.return_type)
+            }
+            receiver_type => typer.method_call_type(node, receiver_type),
+        },
+        // "a" as? int の型は int?
+        ExpressionNode::TypeCast(node) => {
+            let actual: Option<Type> = type_of_expression(typer, &node.expression);
+            typer.type_cast(node, actual);
+            match node.is_checked {
+                true => Some(Type::Optional(Box::new(node.to_type.clone()))),
+                false => Some(node.to_type.clone()),
+            }
+        }
+        ExpressionNode::TypeTest(node) => {
+            type_of_expression(typer, &node.expression);
+            Some(Type::Boolean)
+        }
+        // 分岐の型が異なる場合は、分岐の型のUnion型
+        ExpressionNode::If(node) => {
+            type_of_expression(typer, &node.condition);
+            let (then_types, else_types) = narrow(&*typer, &node.condition);
+            let then_type: Option<Type> = typer.narrowed_type_of(then_types, &node.then_branch);
+            let else_type: Option<Type> = typer.narrowed_type_of(else_types, &node.else_branch);
+            Some(Type::union(vec![then_type?, else_type?]))
+        }
+    }
+}
+
+/// 二項演算の結果の型を求める
+///
+/// 両辺の型が同じ数値型の場合と、文字列の連結の場合のみ型が求められる
+fn binary_operation_type(
+    operator: &BinaryOperator,
+    left_type: Type,
+    right_type: Type,
+) -> Option<Type> {
+    match (operator, &left_type) {
+        _ if left_type != right_type => None,
+        (_, Type::Integer) | (_, Type::Float) => Some(left_type),
+        (BinaryOperator::Add, Type::String) => Some(left_type),
+        _ => None,
+    }
+}
+
+/// 条件 `変数 is 型` から、真の分岐と偽の分岐で用いる変数の型を絞り込む
+///
+/// 真の分岐では変数を検査した型とし、偽の分岐ではUnion型から検査した型に当てはまる型を取り除きます。
+/// 条件が `変数 is 型` でない場合は、どちらも空のマップを返します。
+fn narrow(
+    typer: &impl ExpressionTyper,
+    condition: &ExpressionNode,
+) -> (HashMap<String, Type>, HashMap<String, Type>) {
+    let mut then_types: HashMap<String, Type> = HashMap::new();
+    let mut else_types: HashMap<String, Type> = HashMap::new();
+    if let ExpressionNode::TypeTest(node) = condition {
+        if let ExpressionNode::CallOfVariable(variable) = node.expression.as_ref() {
+            if let Some(var_type) = typer.variable_type(&variable.name) {
+                then_types.insert(variable.name.clone(), node.tested_type.clone());
+                // let v: int | string;
+                // if v is int { ... } else { ... }  # 偽の分岐で v は string
+                if let Type::Union(member_types) = var_type {
+                    let remaining_types: Vec<Type> = member_types
+                        .into_iter()
+                        .filter(|member_type| {
+                            !is_subtype(member_type.clone(), node.tested_type.clone())
+                        })
+                        .collect();
+                    if !remaining_types.is_empty() {
+                        else_types.insert(variable.name.clone(), Type::union(remaining_types));
+                    }
+                }
+            }
+        }
+    }
+    (then_types, else_types)
+}
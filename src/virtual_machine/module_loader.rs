@@ -0,0 +1,530 @@
+mod module_error;
+
+use crate::loader::Loader;
+use crate::virtual_machine::ast::{
+    ExpressionNode, FunctionDeclarationNode, ImportNode, LiteralNode, LiteralValue, Statement,
+    VariableDeclarationNode, AST,
+};
+use crate::virtual_machine::bytecode::{compile, Chunk, StackMachine};
+use crate::virtual_machine::evaluator::constant_folder::fold_constants;
+use crate::virtual_machine::evaluator::evaluation_error::EvaluationError;
+use crate::virtual_machine::evaluator::mapper::function_mapper::FunctionMapper;
+use crate::virtual_machine::evaluator::mapper::variable_mapper::VariableMapper;
+use crate::virtual_machine::evaluator::Evaluator;
+use crate::virtual_machine::optimizer::optimize;
+use crate::virtual_machine::parser::Parser;
+use crate::virtual_machine::resolver::resolve;
+use crate::virtual_machine::scanner::Scanner;
+use crate::virtual_machine::token::Token;
+use crate::virtual_machine::type_checker::check_types;
+use crate::virtual_machine::type_inferrer::infer_types;
+use crate::virtual_machine::VirtualMachine;
+use module_error::ModuleError;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+/// モジュールを読み込み、import文をexportされた宣言に置き換える
+///
+/// 読み込んだモジュールはパスごとにキャッシュし、同じモジュールは一度だけ評価します。
+/// 読み込み中のモジュールをスタックに積み、循環したimportを検出します。
+pub(crate) struct ModuleLoader {
+    named_only: bool,
+    infer_types: bool,
+    tree_walk: bool,
+    optimize: bool,
+    cache: HashMap<PathBuf, Module>,
+    loading: Vec<PathBuf>,
+}
+
+/// 評価済みのモジュール
+#[derive(Clone)]
+struct Module {
+    /// exportされた宣言 (変数と定数の値は評価済み)
+    exports: Vec<Statement>,
+    /// モジュールで書かれたimpl文とnewtype文
+    type_declarations: Vec<Statement>,
+    /// モジュールが直接または間接にimportしたモジュールのパス
+    dependencies: Vec<PathBuf>,
+}
+
+impl ModuleLoader {
+    pub(crate) fn new(named_only: bool, infer_types: bool) -> Self {
+        ModuleLoader {
+            named_only,
+            infer_types,
+            tree_walk: false,
+            optimize: false,
+            cache: HashMap::new(),
+            loading: vec![],
+        }
+    }
+
+    /// モジュールを、バイトコードにコンパイルせずにEvaluatorで評価する
+    pub(crate) fn with_tree_walking_evaluator(mut self) -> Self {
+        self.tree_walk = true;
+        self
+    }
+
+    /// モジュールを評価する前に、ASTを最適化する
+    pub(crate) fn with_optimization(mut self) -> Self {
+        self.optimize = true;
+        self
+    }
+
+    /// 実行するファイルを読み込み中のモジュールとして登録する
+    ///
+    /// 実行するファイル自身をimportするモジュールも、循環として検出できるようになります。
+    pub(crate) fn with_entry_file(mut self, file_path: &Path) -> Self {
+        if let Ok(path) = file_path.canonicalize() {
+            self.loading.push(path);
+        }
+        self
+    }
+
+    /// import文を、モジュールからexportされた宣言に置き換える
+    ///
+    /// exportされた宣言の名前は、`strings.pad` のようにimport時の別名で修飾します。
+    /// export文は、中の宣言をそのまま残します。
+    /// モジュールのimpl文とnewtype文は、モジュールが返した値のメソッドを呼べるように修飾せずに加えます。
+    ///
+    /// # Arguments
+    /// - `ast` - import文を含むAST
+    /// - `base_dir` - importのパスを解決する基準のディレクトリ
+    pub(crate) fn link(&mut self, ast: AST, base_dir: &Path) -> Result<AST, ModuleError> {
+        self.link_modules(ast, base_dir)
+            .map(|(linked_ast, _)| linked_ast)
+    }
+
+    /// import文をexportされた宣言に置き換え、読み込んだモジュールのパスとともに返す
+    fn link_modules(
+        &mut self,
+        ast: AST,
+        base_dir: &Path,
+    ) -> Result<(AST, Vec<PathBuf>), ModuleError> {
+        let mut linked_ast: AST = AST::new();
+        let mut linked_modules: Vec<PathBuf> = vec![];
+        for (line, statement) in ast.statements {
+            match statement {
+                Statement::Import(import) => {
+                    let path: PathBuf = self.load_module(&import, base_dir, line)?;
+                    let module: Module = self.cache[&path].clone();
+
+                    // 同じモジュールのimpl文は、別名を変えて何度importしても一度だけ加える
+                    for dependency in module.dependencies.iter().chain(std::iter::once(&path)) {
+                        if linked_modules.contains(dependency) {
+                            continue;
+                        }
+                        for declaration in &self.cache[dependency].type_declarations {
+                            linked_ast.push_statement(line, declaration.clone());
+                        }
+                        linked_modules.push(dependency.clone());
+                    }
+
+                    for export in module.exports {
+                        linked_ast.push_statement(line, qualify(export, &import.alias));
+                    }
+                }
+                statement => linked_ast.push_statement(line, statement),
+            }
+        }
+        Ok((linked_ast, linked_modules))
+    }
+
+    /// モジュールを読み込み、キャッシュしたモジュールのパスを返す
+    fn load_module(
+        &mut self,
+        import: &ImportNode,
+        base_dir: &Path,
+        line: usize,
+    ) -> Result<PathBuf, ModuleError> {
+        // パスはimport文を書いたファイルのディレクトリからの相対パス
+        let path: PathBuf = match base_dir.join(&import.path).canonicalize() {
+            Ok(path) => path,
+            Err(_) => {
+                return Err(ModuleError::ModuleNotFound {
+                    path: import.path.clone(),
+                    line,
+                })
+            }
+        };
+
+        // 評価済みのモジュールはキャッシュを返す
+        if self.cache.contains_key(&path) {
+            return Ok(path);
+        }
+
+        // 読み込み中のモジュールを再びimportした場合は循環している
+        // a.shot -> b.shot -> a.shot
+        if let Some(start) = self.loading.iter().position(|loading| loading == &path) {
+            let cycle: Vec<String> = self.loading[start..]
+                .iter()
+                .chain(std::iter::once(&path))
+                .map(|path| path.display().to_string())
+                .collect();
+            return Err(ModuleError::ImportCycle {
+                cycle: cycle.join(" -> "),
+                line,
+            });
+        }
+
+        self.loading.push(path.clone());
+        let module: Result<Module, ModuleError> = self.evaluate_module(&path);
+        self.loading.pop();
+
+        self.cache.insert(path.clone(), module?);
+        Ok(path)
+    }
+
+    /// モジュールを評価する
+    ///
+    /// モジュールは、実行するファイルと同じくバイトコードにコンパイルしてスタックマシンで評価します。
+    /// exportされた変数と定数は、評価した値のリテラルに置き換えます。
+    fn evaluate_module(&mut self, path: &Path) -> Result<Module, ModuleError> {
+        let display_path: String = path.display().to_string();
+        let failed = |message: String| ModuleError::FailedToLoadModule {
+            path: display_path.clone(),
+            message,
+        };
+
+        let loader: Loader =
+            Loader::load(None, Some(display_path.clone())).map_err(|e| failed(e.to_string()))?;
+
+        // 字句解析
+        let scanner: Scanner = Scanner::new(loader.source_code, loader.source_code_vector.clone());
+        let tokens: Vec<Token> = scanner.scan().map_err(|e| failed(e.to_string()))?;
+
+        // 構文解析
+        let mut parser: Parser = Parser::new(tokens);
+        if self.named_only {
+            parser = parser.with_named_only();
+        }
+        let is_type_inference_enabled: bool =
+            self.infer_types || VirtualMachine::has_infer_types_pragma(&loader.source_code_vector);
+        if is_type_inference_enabled {
+            parser = parser.with_type_inference();
+        }
+        let ast: AST = parser.parse().map_err(|e| failed(e.to_string()))?;
+        let type_declarations: Vec<Statement> = ast
+            .statements
+            .iter()
+            .filter_map(|(_, statement)| match statement {
+                Statement::Impl(_) | Statement::DeclarationOfNewtype(_) => Some(statement.clone()),
+                _ => None,
+            })
+            .collect();
+
+        // モジュールのimportは、モジュールのディレクトリを基準に解決する
+        let module_dir: &Path = path.parent().unwrap_or(Path::new("."));
+        let (ast, dependencies) = self.link_modules(ast, module_dir)?;
+
+        // 名前の検査 (スロットはバイトコードへのコンパイルで割り当てる)
+        if let Err(errors) = resolve(&ast) {
+            let messages: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
+            return Err(failed(messages.join(", ")));
+        }
+
+        // 型推論
+        let ast: AST = if is_type_inference_enabled {
+            infer_types(ast).map_err(|e| failed(e.to_string()))?
+        } else {
+            ast
+        };
+
+        // 型検査
+        // exportされていない宣言も、importするファイルと同じように検査する
+        if let Err(errors) = check_types(&ast) {
+            let messages: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
+            return Err(failed(messages.join(", ")));
+        }
+
+        // 定数の畳み込み
+        let ast: AST = fold_constants(ast).map_err(|e| failed(e.to_string()))?;
+
+        // 最適化
+        let ast: AST = if self.optimize { optimize(ast) } else { ast };
+
+        let exports: Vec<Statement> = ast
+            .statements
+            .iter()
+            .filter_map(|(_, statement)| match statement {
+                Statement::Export(declaration) => Some(*declaration.clone()),
+                _ => None,
+            })
+            .collect();
+
+        // 評価
+        // exportされた変数と定数の値は、評価を終えた後に名前で求める
+        let mut evaluated_exports: Vec<Statement> = vec![];
+        if self.tree_walk {
+            let mut evaluator: Evaluator =
+                Evaluator::new(ast, FunctionMapper::new(), VariableMapper::new());
+            evaluator.evaluate().map_err(|e| failed(e.to_string()))?;
+            for export in exports {
+                evaluated_exports.push(
+                    evaluate_export(export, |name| evaluator.evaluate_variable(name))
+                        .map_err(|e| failed(e.to_string()))?,
+                );
+            }
+        } else {
+            let chunk: Chunk = compile(&ast);
+            let mut stack_machine: StackMachine = StackMachine::new();
+            stack_machine
+                .run(&chunk)
+                .map_err(|e| failed(e.to_string()))?;
+            for export in exports {
+                evaluated_exports.push(
+                    evaluate_export(export, |name| stack_machine.evaluate_variable(&chunk, name))
+                        .map_err(|e| failed(e.to_string()))?,
+                );
+            }
+        }
+        Ok(Module {
+            exports: evaluated_exports,
+            type_declarations,
+            dependencies,
+        })
+    }
+}
+
+/// exportされた変数と定数の宣言の値を、評価した値のリテラルに置き換える
+///
+/// 関数の宣言はそのまま返します。
+fn evaluate_export(
+    export: Statement,
+    mut evaluate_variable: impl FnMut(&str) -> Result<LiteralValue, EvaluationError>,
+) -> Result<Statement, EvaluationError> {
+    let with_value =
+        |node: VariableDeclarationNode, value: LiteralValue| -> Box<VariableDeclarationNode> {
+            Box::new(VariableDeclarationNode {
+                value: Box::new(ExpressionNode::Literal(Box::new(LiteralNode { value }))),
+                ..node
+            })
+        };
+    Ok(match export {
+        Statement::DeclarationOfVariable(node) => {
+            let value: LiteralValue = evaluate_variable(&node.name)?;
+            Statement::DeclarationOfVariable(with_value(*node, value))
+        }
+        Statement::DeclarationOfConstant(node) => {
+            let value: LiteralValue = evaluate_variable(&node.name)?;
+            Statement::DeclarationOfConstant(with_value(*node, value))
+        }
+        export => export,
+    })
+}
+
+/// exportされた宣言の名前を、モジュールの別名で修飾する
+fn qualify(statement: Statement, alias: &str) -> Statement {
+    match statement {
+        Statement::DeclarationOfFunction(node) => {
+            Statement::DeclarationOfFunction(Rc::new(FunctionDeclarationNode {
+                name: format!("{}.{}", alias, node.name),
+                ..Rc::unwrap_or_clone(node)
+            }))
+        }
+        Statement::DeclarationOfVariable(node) => {
+            Statement::DeclarationOfVariable(Box::new(VariableDeclarationNode {
+                name: format!("{}.{}", alias, node.name),
+                ..*node
+            }))
+        }
+        Statement::DeclarationOfConstant(node) => {
+            Statement::DeclarationOfConstant(Box::new(VariableDeclarationNode {
+                name: format!("{}.{}", alias, node.name),
+                ..*node
+            }))
+        }
+        statement => statement,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::virtual_machine::ast::{LiteralValue, Type};
+    use crate::virtual_machine::evaluator::core::parse_source;
+
+    fn import(path: &str, alias: &str) -> Statement {
+        Statement::Import(Box::new(ImportNode {
+            path: path.to_string(),
+            alias: alias.to_string(),
+        }))
+    }
+
+    fn link_statements(statements: Vec<Statement>) -> Result<AST, ModuleError> {
+        let mut ast: AST = AST::new();
+        for statement in statements {
+            ast.push_statement(1, statement);
+        }
+        ModuleLoader::new(false, false).link(ast, Path::new("tests/modules"))
+    }
+
+    /// exportされた宣言だけが、別名で修飾されて読み込まれるか確認するテスト
+    #[test]
+    fn link_qualifies_exported_declarations() {
+        let ast: AST = link_statements(vec![import("lib/strings.shot", "strings")]).unwrap();
+
+        let names: Vec<String> = ast
+            .statements
+            .iter()
+            .map(|(_, statement)| match statement {
+                Statement::DeclarationOfFunction(node) => node.name.clone(),
+                Statement::DeclarationOfVariable(node) => node.name.clone(),
+                Statement::DeclarationOfConstant(node) => node.name.clone(),
+                _ => panic!("Expected a declaration"),
+            })
+            .collect();
+        assert_eq!(
+            names,
+            vec!["strings.WIDTH", "strings.greeting", "strings.pad"]
+        );
+    }
+
+    /// exportされた変数が、評価済みの値に置き換えられるか確認するテスト
+    #[test]
+    fn link_evaluates_exported_variables() {
+        let ast: AST = link_statements(vec![import("lib/strings.shot", "strings")]).unwrap();
+
+        assert_eq!(
+            ast.statements[1].1,
+            Statement::DeclarationOfVariable(Box::new(VariableDeclarationNode {
+                name: "strings.greeting".to_string(),
+                var_type: Type::String,
+                value: Box::new(ExpressionNode::Literal(Box::new(LiteralNode {
+                    value: LiteralValue::String("Hello, shot".to_string()),
+                }))),
+            }))
+        );
+    }
+
+    /// モジュールの関数を呼び出して求めた値が、評価の方法によらず同じになるか確認するテスト
+    #[test]
+    fn link_evaluates_modules_same_as_evaluator() {
+        let link = |loader: ModuleLoader| {
+            let mut loader: ModuleLoader = loader;
+            let mut ast: AST = AST::new();
+            ast.push_statement(1, import("lib/numbers.shot", "numbers"));
+            loader.link(ast, Path::new("tests/modules")).unwrap()
+        };
+        let expected: Statement =
+            Statement::DeclarationOfVariable(Box::new(VariableDeclarationNode {
+                name: "numbers.answer".to_string(),
+                var_type: Type::Integer,
+                value: Box::new(ExpressionNode::Literal(Box::new(LiteralNode {
+                    value: LiteralValue::Integer(42),
+                }))),
+            }));
+
+        for loader in [
+            ModuleLoader::new(false, false),
+            ModuleLoader::new(false, false).with_optimization(),
+            ModuleLoader::new(false, false).with_tree_walking_evaluator(),
+        ] {
+            assert_eq!(link(loader).statements[0].1, expected);
+        }
+    }
+
+    /// 同じモジュールを複数回importしても、一度だけ評価されるか確認するテスト
+    #[test]
+    fn link_caches_modules() {
+        let mut loader: ModuleLoader = ModuleLoader::new(false, false);
+        let mut ast: AST = AST::new();
+        ast.push_statement(1, import("lib/strings.shot", "strings"));
+        ast.push_statement(2, import("lib/../lib/strings.shot", "again"));
+
+        let linked: AST = loader.link(ast, Path::new("tests/modules")).unwrap();
+        assert_eq!(linked.statements.len(), 6);
+        assert_eq!(loader.cache.len(), 1);
+    }
+
+    /// モジュールのimpl文が一度だけ加えられ、モジュールが返した値のメソッドを呼べるか確認するテスト
+    #[test]
+    fn link_carries_impls_of_modules() {
+        let ast: AST = parse_source(
+            r#"
+            import "lib/ids.shot" as ids;
+            import "lib/ids.shot" as again;
+            let shown: string = ids.mk(value: 7).show() + again.mk(value: 8).show();
+            "#,
+        );
+        let linked: AST = ModuleLoader::new(false, false)
+            .link(ast, Path::new("tests/modules"))
+            .unwrap();
+        let impls: usize = linked
+            .statements
+            .iter()
+            .filter(|(_, statement)| matches!(statement, Statement::Impl(_)))
+            .count();
+        assert_eq!(impls, 1);
+
+        let mut evaluator: Evaluator =
+            Evaluator::new(linked, FunctionMapper::new(), VariableMapper::new());
+        evaluator.evaluate().unwrap();
+        assert_eq!(
+            evaluator.evaluate_variable("shown").unwrap(),
+            LiteralValue::String("#7#8".to_string())
+        );
+    }
+
+    /// 循環したimportがエラーになるか確認するテスト
+    #[test]
+    fn fail_import_cycle() {
+        match link_statements(vec![import("cycle_a.shot", "a")]) {
+            Err(ModuleError::ImportCycle { cycle, line }) => {
+                assert!(cycle.ends_with("cycle_a.shot"));
+                assert!(cycle.contains("cycle_b.shot"));
+                assert_eq!(line, 1);
+            }
+            result => panic!("Expected ImportCycle, got {:?}", result),
+        }
+    }
+
+    /// exportされていない宣言の型エラーで、モジュールの読み込みが失敗するか確認するテスト
+    #[test]
+    fn fail_type_error_in_module() {
+        match link_statements(vec![import("type_error.shot", "bad")]) {
+            Err(ModuleError::FailedToLoadModule { path, message }) => {
+                assert!(path.ends_with("type_error.shot"));
+                assert!(
+                    message.contains("at line 1. bad returns int"),
+                    "{}",
+                    message
+                );
+            }
+            result => panic!("Expected FailedToLoadModule, got {:?}", result),
+        }
+    }
+
+    /// 存在しないモジュールのimportがエラーになるか確認するテスト
+    #[test]
+    fn fail_module_not_found() {
+        assert_eq!(
+            link_statements(vec![import("missing.shot", "missing")]).unwrap_err(),
+            ModuleError::ModuleNotFound {
+                path: "missing.shot".to_string(),
+                line: 1,
+            }
+        );
+    }
+
+    /// 宣言を実行する前にreturnした変数のexportが、評価の方法によらずエラーになるか確認するテスト
+    #[test]
+    fn fail_export_of_variable_not_declared() {
+        for loader in [
+            ModuleLoader::new(false, false),
+            ModuleLoader::new(false, false).with_tree_walking_evaluator(),
+        ] {
+            let mut loader: ModuleLoader = loader;
+            let mut ast: AST = AST::new();
+            ast.push_statement(1, import("early_return.shot", "early"));
+            match loader.link(ast, Path::new("tests/modules")) {
+                Err(ModuleError::FailedToLoadModule { path, message }) => {
+                    assert!(path.ends_with("early_return.shot"));
+                    assert!(message.contains("Variable x not found"), "{}", message);
+                }
+                result => panic!("Expected FailedToLoadModule, got {:?}", result),
+            }
+        }
+    }
+}
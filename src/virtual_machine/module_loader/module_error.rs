@@ -0,0 +1,11 @@
+use thiserror::Error;
+
+#[derive(Debug, Clone, Error, PartialEq)]
+pub enum ModuleError {
+    #[error("Module {path} not found at line {line}")]
+    ModuleNotFound { path: String, line: usize },
+    #[error("Import cycle detected: {cycle} at line {line}")]
+    ImportCycle { cycle: String, line: usize },
+    #[error("Failed to load module {path}: {message}")]
+    FailedToLoadModule { path: String, message: String },
+}
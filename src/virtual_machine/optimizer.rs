@@ -0,0 +1,483 @@
+use crate::virtual_machine::ast::{
+    ArgumentNode, BinaryOperationNode, BlockNode, ExpressionNode, FunctionCallNode,
+    FunctionDeclarationNode, IfNode, ImplNode, LiteralNode, LiteralValue, MethodCallNode,
+    ParameterNode, Statement, Type, TypeCastNode, TypeTestNode, VariableDeclarationNode, AST,
+};
+use crate::virtual_machine::evaluator::statement_evaluator::expression_evaluator::binary_operation_evaluator::calculate_binary_operation;
+use crate::virtual_machine::evaluator::statement_evaluator::expression_evaluator::type_cast_evaluator::cast_value;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+/// 値が定数の変数のスコープのスタック
+///
+/// 値が定数でない変数も、外側の同名の変数をシャドーイングするために `None` として登録します。
+type ConstantScopes = Vec<HashMap<String, Option<LiteralValue>>>;
+
+/// 評価の前にASTを最適化する
+///
+/// 最適化は以下の3つです。どの最適化も、最適化しない場合と同じ値とエラーになるように行います。
+///
+/// - リテラル同士の二項演算と、リテラルの基本型同士の型キャストを畳み込む: `(1 + 2) * 3` → `9`
+/// - 関数の本体で、return文より後の文を取り除く (構文解析を経ずに組み立てたASTなど)
+/// - 初期化式がリテラルになる変数と定数の参照を、その値に置き換える
+///
+/// 評価するとエラーになる式 (`1 / 0` など) は畳み込まず、実行時にエラーとなります。
+/// 条件がリテラルの条件分岐は、評価される側の式に置き換えます。
+///
+/// 変数の参照の置き換えは、同じ関数の本体 (またはトップレベル) で宣言された変数に限ります。
+/// 関数の中で宣言された関数は、呼び出された時点の外側のスコープを参照するため、置き換えの対象にしません。
+pub(crate) fn optimize(ast: AST) -> AST {
+    let mut optimized_ast: AST = AST::new();
+    let mut constants: ConstantScopes = vec![HashMap::new()];
+    for (line, statement) in ast.statements {
+        let optimized_statement: Statement = optimize_statement(statement, &mut constants, line);
+        optimized_ast.push_statement(line, optimized_statement);
+    }
+    optimized_ast
+}
+
+fn optimize_statement(
+    statement: Statement,
+    constants: &mut ConstantScopes,
+    line: usize,
+) -> Statement {
+    match statement {
+        Statement::Expression(expression) => {
+            Statement::Expression(optimize_expression(expression, constants, line))
+        }
+        Statement::DeclarationOfVariable(node) => Statement::DeclarationOfVariable(Box::new(
+            optimize_variable_declaration(*node, constants, line),
+        )),
+        Statement::DeclarationOfConstant(node) => Statement::DeclarationOfConstant(Box::new(
+            optimize_variable_declaration(*node, constants, line),
+        )),
+        Statement::DeclarationOfFunction(node) => Statement::DeclarationOfFunction(Rc::new(
+            optimize_function(Rc::unwrap_or_clone(node), line),
+        )),
+        Statement::Return(expression) => {
+            Statement::Return(Box::new(optimize_expression(*expression, constants, line)))
+        }
+        Statement::Block(block) => {
+            // ブロックは新しいスコープとして最適化する
+            constants.push(HashMap::new());
+            let statements: Vec<(usize, Statement)> = block
+                .statements
+                .into_iter()
+                .map(|(line, statement)| (line, optimize_statement(statement, constants, line)))
+                .collect();
+            constants.pop();
+            Statement::Block(Box::new(BlockNode { statements }))
+        }
+        Statement::Impl(node) => Statement::Impl(Box::new(ImplNode {
+            target: node.target,
+            methods: node
+                .methods
+                .into_iter()
+                .map(|method| Rc::new(optimize_function(Rc::unwrap_or_clone(method), line)))
+                .collect(),
+        })),
+        Statement::Export(declaration) => {
+            Statement::Export(Box::new(optimize_statement(*declaration, constants, line)))
+        }
+        Statement::Import(_)
+        | Statement::DeclarationOfNewtype(_)
+        | Statement::DeclarationOfInterface(_) => statement,
+    }
+}
+
+/// 変数の初期化式を最適化し、値がリテラルになった場合は定数として登録する
+fn optimize_variable_declaration(
+    node: VariableDeclarationNode,
+    constants: &mut ConstantScopes,
+    line: usize,
+) -> VariableDeclarationNode {
+    // 初期化式は、宣言する変数を登録する前のスコープで評価される
+    let value: ExpressionNode = optimize_expression(*node.value, constants, line);
+    let constant: Option<LiteralValue> = match &value {
+        ExpressionNode::Literal(literal) if is_inlinable(&literal.value) => {
+            Some(literal.value.clone())
+        }
+        _ => None,
+    };
+    constants
+        .last_mut()
+        .expect("ConstantScopes always has the innermost scope")
+        .insert(node.name.clone(), constant);
+    VariableDeclarationNode {
+        name: node.name,
+        var_type: node.var_type,
+        value: Box::new(value),
+    }
+}
+
+/// 関数の本体を新しいスコープとして最適化し、return文より後の文を取り除く
+fn optimize_function(node: FunctionDeclarationNode, line: usize) -> FunctionDeclarationNode {
+    // パラメータは外側の同名の変数をシャドーイングする
+    let mut constants: ConstantScopes = vec![node
+        .params
+        .iter()
+        .map(|param| (param.name.clone(), None))
+        .collect()];
+    let mut body: Vec<(usize, Statement)> = vec![];
+    for (line, statement) in node.body {
+        let is_return: bool = matches!(statement, Statement::Return(_));
+        body.push((line, optimize_statement(statement, &mut constants, line)));
+        if is_return {
+            break;
+        }
+    }
+
+    // デフォルト値は宣言された位置のスコープで評価されるため、リテラルの畳み込みのみ行う
+    let params: Vec<ParameterNode> = node
+        .params
+        .into_iter()
+        .map(|param| ParameterNode {
+            default_value: param.default_value.map(|default_value| {
+                Box::new(optimize_expression(
+                    *default_value,
+                    &vec![HashMap::new()],
+                    line,
+                ))
+            }),
+            ..param
+        })
+        .collect();
+
+    FunctionDeclarationNode {
+        name: node.name,
+        params,
+        return_type: node.return_type,
+        body,
+    }
+}
+
+fn optimize_expression(
+    expression: ExpressionNode,
+    constants: &ConstantScopes,
+    line: usize,
+) -> ExpressionNode {
+    match expression {
+        ExpressionNode::Literal(_) => expression,
+        ExpressionNode::CallOfVariable(node) => match constants
+            .iter()
+            .rev()
+            .find_map(|scope| scope.get(&node.name))
+        {
+            Some(Some(value)) => literal(value.clone()),
+            _ => ExpressionNode::CallOfVariable(node),
+        },
+        ExpressionNode::BinaryOperation(node) => {
+            let left: ExpressionNode = optimize_expression(*node.left, constants, line);
+            let right: ExpressionNode = optimize_expression(*node.right, constants, line);
+            if let (ExpressionNode::Literal(l), ExpressionNode::Literal(r)) = (&left, &right) {
+                // 1 / 0;  # 実行時に DivisionByZero となるように畳み込まない
+                if let Ok(value) = calculate_binary_operation(
+                    node.operator.clone(),
+                    l.value.clone(),
+                    r.value.clone(),
+                    line,
+                ) {
+                    return literal(value);
+                }
+            }
+            ExpressionNode::BinaryOperation(Box::new(BinaryOperationNode {
+                left: Box::new(left),
+                operator: node.operator,
+                right: Box::new(right),
+            }))
+        }
+        ExpressionNode::TypeCast(node) => {
+            let expression: ExpressionNode = optimize_expression(*node.expression, constants, line);
+            // interfaceへのキャストは実装されたメソッドに依存するため、基本型同士のキャストのみ畳み込む
+            let is_foldable: bool =
+                is_primitive(&node.to_type) && node.from_type.as_ref().is_none_or(is_primitive);
+            if let (true, ExpressionNode::Literal(value)) = (is_foldable, &expression) {
+                if let Ok(value) = cast_value(
+                    value.value.clone(),
+                    node.from_type.clone(),
+                    node.to_type.clone(),
+                    node.is_checked,
+                    line,
+                ) {
+                    return literal(value);
+                }
+            }
+            ExpressionNode::TypeCast(Box::new(TypeCastNode {
+                expression: Box::new(expression),
+                ..*node
+            }))
+        }
+        ExpressionNode::TypeTest(node) => ExpressionNode::TypeTest(Box::new(TypeTestNode {
+            expression: Box::new(optimize_expression(*node.expression, constants, line)),
+            tested_type: node.tested_type,
+        })),
+        ExpressionNode::If(node) => {
+            let condition: ExpressionNode = optimize_expression(*node.condition, constants, line);
+            match condition {
+                ExpressionNode::Literal(value) if value.value == LiteralValue::Boolean(true) => {
+                    optimize_expression(*node.then_branch, constants, line)
+                }
+                ExpressionNode::Literal(value) if value.value == LiteralValue::Boolean(false) => {
+                    optimize_expression(*node.else_branch, constants, line)
+                }
+                condition => ExpressionNode::If(Box::new(IfNode {
+                    condition: Box::new(condition),
+                    then_branch: Box::new(optimize_expression(*node.then_branch, constants, line)),
+                    else_branch: Box::new(optimize_expression(*node.else_branch, constants, line)),
+                })),
+            }
+        }
+        ExpressionNode::CallOfFunction(node) => {
+            ExpressionNode::CallOfFunction(Box::new(FunctionCallNode {
+                name: node.name,
+                arguments: optimize_arguments(node.arguments, constants, line),
+            }))
+        }
+        ExpressionNode::PartialApplication(node) => {
+            ExpressionNode::PartialApplication(Box::new(FunctionCallNode {
+                name: node.name,
+                arguments: optimize_arguments(node.arguments, constants, line),
+            }))
+        }
+        ExpressionNode::CallOfMethod(node) => {
+            ExpressionNode::CallOfMethod(Box::new(MethodCallNode {
+                receiver: Box::new(optimize_expression(*node.receiver, constants, line)),
+                name: node.name,
+                arguments: optimize_arguments(node.arguments, constants, line),
+            }))
+        }
+    }
+}
+
+fn optimize_arguments(
+    arguments: Vec<ArgumentNode>,
+    constants: &ConstantScopes,
+    line: usize,
+) -> Vec<ArgumentNode> {
+    arguments
+        .into_iter()
+        .map(|argument| ArgumentNode {
+            value: optimize_expression(argument.value, constants, line),
+            ..argument
+        })
+        .collect()
+}
+
+fn literal(value: LiteralValue) -> ExpressionNode {
+    ExpressionNode::Literal(Box::new(LiteralNode { value }))
+}
+
+/// 参照を置き換えられる値か判定する
+///
+/// 関数の値は宣言されたスコープを参照するため、置き換えの対象にしません。
+fn is_inlinable(value: &LiteralValue) -> bool {
+    matches!(
+        value,
+        LiteralValue::Integer(_)
+            | LiteralValue::Float(_)
+            | LiteralValue::String(_)
+            | LiteralValue::Boolean(_)
+            | LiteralValue::None
+    )
+}
+
+fn is_primitive(t: &Type) -> bool {
+    matches!(
+        t,
+        Type::Integer | Type::Float | Type::String | Type::Boolean
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::virtual_machine::ast::{FunctionDeclarationNode, LiteralValue, Statement, AST};
+    use crate::virtual_machine::bytecode::{compile, StackMachine};
+    use crate::virtual_machine::evaluator::core::parse_source;
+    use crate::virtual_machine::evaluator::evaluation_error::EvaluationError;
+    use crate::virtual_machine::evaluator::mapper::function_mapper::FunctionMapper;
+    use crate::virtual_machine::evaluator::mapper::variable_mapper::VariableMapper;
+    use crate::virtual_machine::evaluator::Evaluator;
+    use crate::virtual_machine::optimizer::optimize;
+    use std::rc::Rc;
+
+    fn optimize_source(source_code: &str) -> Vec<(usize, Statement)> {
+        optimize(parse_source(source_code)).statements
+    }
+
+    /// 最適化の前後で、EvaluatorとStackMachineが同じ結果になることを確認し、その結果を返す
+    fn run_with_and_without_optimization(
+        source_code: &str,
+    ) -> Result<LiteralValue, EvaluationError> {
+        let ast: AST = parse_source(source_code);
+        let expected: Result<LiteralValue, EvaluationError> =
+            Evaluator::new(ast.clone(), FunctionMapper::new(), VariableMapper::new()).evaluate();
+        let optimized_ast: AST = optimize(ast.clone());
+        for ast in [ast, optimized_ast] {
+            let evaluated: Result<LiteralValue, EvaluationError> =
+                Evaluator::new(ast.clone(), FunctionMapper::new(), VariableMapper::new())
+                    .evaluate();
+            assert_eq!(evaluated, expected, "source code:{}", source_code);
+            let executed: Result<LiteralValue, EvaluationError> =
+                StackMachine::new().run(&compile(&ast));
+            assert_eq!(executed, expected, "source code:{}", source_code);
+        }
+        expected
+    }
+
+    /// リテラル同士の二項演算と型キャストが畳み込まれることを確認するテスト
+    #[test]
+    fn test_fold_constant_expressions() {
+        assert_eq!(
+            optimize_source(
+                r#"
+let a: int = (1 + 2) * 3;
+let b: float = 1 as int -> float;
+let c: string = "shot" + " " + ((4 / 2) as string);
+let d: int? = "a" as? int;
+"#
+            ),
+            parse_source(
+                r#"
+let a: int = 9;
+let b: float = 1.0;
+let c: string = "shot 2";
+let d: int? = none;
+"#
+            )
+            .statements
+        );
+    }
+
+    /// 評価するとエラーになる式は畳み込まれないことを確認するテスト
+    ///
+    /// let a: int = 1 / 0;  # 実行時に DivisionByZero
+    #[test]
+    fn test_keep_expression_that_fails() {
+        let source_code: &str = r#"
+let a: int = 1 / 0;
+let b: int = "a" as int;
+let c: int = 1 + "a";
+"#;
+        assert_eq!(
+            optimize_source(source_code),
+            parse_source(source_code).statements
+        );
+    }
+
+    /// 関数の本体でreturn文より後の文が取り除かれ、条件がリテラルの条件分岐が畳み込まれることを確認するテスト
+    ///
+    /// 構文解析ではreturn文より後に文を書けないため、ASTを直接組み立てます。
+    ///
+    /// let f: fn = (x: int): int { return if true { x } else { 0 }; let y: int = 1 / 0; };
+    #[test]
+    fn test_remove_dead_code() {
+        let mut function: FunctionDeclarationNode =
+            match parse_source("let f: fn = (x: int): int { return if true { x } else { 0 }; };")
+                .statements
+                .remove(0)
+                .1
+            {
+                Statement::DeclarationOfFunction(node) => Rc::unwrap_or_clone(node),
+                statement => panic!("unexpected statement: {:?}", statement),
+            };
+        function.body.push((
+            1,
+            parse_source("let y: int = 1 / 0;").statements.remove(0).1,
+        ));
+        let mut ast: AST = AST::new();
+        ast.push_statement(1, Statement::DeclarationOfFunction(Rc::new(function)));
+
+        assert_eq!(
+            optimize(ast).statements,
+            parse_source("let f: fn = (x: int): int { return x; };").statements
+        );
+    }
+
+    /// 値が定数の変数の参照がリテラルに置き換えられることを確認するテスト
+    ///
+    /// ブロックの中ではシャドーイングした変数の値に置き換え、パラメータと関数の外側の変数は置き換えません。
+    #[test]
+    fn test_inline_constant_variables() {
+        assert_eq!(
+            optimize_source(
+                r#"
+let a: int = 2;
+let b: int = a * 3;
+{
+    let c: int = a + b;
+    let a: string = "x";
+    let d: string = a + "y";
+}
+let f: fn = (a: int): int {
+    let g: fn = (): int { return a + b; };
+    return g() + b;
+};
+let e: int = f(a: b) + a;
+"#
+            ),
+            parse_source(
+                r#"
+let a: int = 2;
+let b: int = 6;
+{
+    let c: int = 8;
+    let a: string = "x";
+    let d: string = "xy";
+}
+let f: fn = (a: int): int {
+    let g: fn = (): int { return a + b; };
+    return g() + b;
+};
+let e: int = f(a: 6) + 2;
+"#
+            )
+            .statements
+        );
+    }
+
+    /// 最適化の前後で、実行結果が同じになることを確認するテスト
+    #[test]
+    fn test_run_same_with_and_without_optimization() {
+        assert_eq!(
+            run_with_and_without_optimization(
+                r#"
+const SCALE: int = 10;
+let base: int = 2 * SCALE;
+let top: fn = (x: int = base + 1): int { return x; };
+let sum_to: fn = (n: int): int {
+    let step: int = 1;
+    let count: fn = (k: int, acc: int): int {
+        let is_zero: bool = ((k as string) + "0000000000000000000") as? int is int;
+        return if is_zero { acc } else { count(k: k - step, acc: acc + k) };
+    };
+    let probe: fn = (): int {
+        let step: int = 100;
+        return step * 2;
+    };
+    return count(k: n, acc: 0) + probe();
+};
+{
+    let base: string = "x";
+    let label: string = base + ((1.5 * 2.0) as float -> string);
+}
+return sum_to(n: 20) + top() + (if false { 0 } else { base });
+"#
+            ),
+            Ok(LiteralValue::Integer(451))
+        );
+    }
+
+    /// 最適化の前後で、実行時のエラーが同じになることを確認するテスト
+    #[test]
+    fn test_run_errors_same_with_and_without_optimization() {
+        let source_codes: Vec<&str> = vec![
+            "let zero: int = 0;\nreturn 10 / zero;",
+            "let max: int = 9223372036854775807;\nlet overflow: int = max + 1;",
+            "let f: fn = (x: int): int { return x; };\nreturn f(x: 1 as int -> float);",
+            "let a: string = \"a\";\nreturn a as int;",
+        ];
+        for source_code in source_codes {
+            assert!(run_with_and_without_optimization(source_code).is_err());
+        }
+    }
+}
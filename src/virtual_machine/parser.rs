@@ -4,20 +4,33 @@ pub mod expression_parser;
 pub mod parser_error;
 mod statement_parser;
 
-use crate::virtual_machine::ast::{Statement, AST};
+use crate::virtual_machine::ast::{MethodSignatureNode, Statement, Type, AST};
 use crate::virtual_machine::token::token_type::TokenType;
 use crate::virtual_machine::token::Token;
 use parser_error::ParserError;
-use statement_parser::parse_statement;
+use statement_parser::parse_top_level_statement;
+use std::collections::{HashMap, HashSet};
 
 /// Token列をASTに変換するパーサー
 ///
 /// # Fields
 /// - `tokens`: トークン列
 /// - `current`: 現在のトークンのインデックス
+/// - `named_only`: 関数呼び出しで名前付き引数のみを許可するか
+/// - `infer_types`: 変数宣言の型注釈と関数の戻り値の型の省略を許可するか
+/// - `newtypes`: トップレベルのnewtype文で宣言された型名と元の型
+/// - `interfaces`: interface文で宣言された型名とメソッドのシグネチャ
+/// - `module_aliases`: import文で付けられたモジュールの別名
+/// - `self_type`: パース中のimplやinterfaceで、`self` パラメータが持つ型
 pub struct Parser {
     tokens: Vec<Token>,
     current: usize,
+    named_only: bool,
+    infer_types: bool,
+    newtypes: HashMap<String, Type>,
+    interfaces: HashMap<String, Vec<MethodSignatureNode>>,
+    module_aliases: HashSet<String>,
+    self_type: Option<Type>,
 }
 
 impl Parser {
@@ -26,7 +39,34 @@ impl Parser {
     /// # Arguments
     /// - `tokens`: トークン列
     pub fn new(tokens: Vec<Token>) -> Self {
-        Self { tokens, current: 0 }
+        Self {
+            tokens,
+            current: 0,
+            named_only: false,
+            infer_types: false,
+            newtypes: HashMap::new(),
+            interfaces: HashMap::new(),
+            module_aliases: HashSet::new(),
+            self_type: None,
+        }
+    }
+
+    /// 名前付き引数のみを許可する
+    ///
+    /// 位置引数を用いた関数呼び出しは `ParserError::PositionalArgumentNotAllowed` となる
+    pub fn with_named_only(mut self) -> Self {
+        self.named_only = true;
+        self
+    }
+
+    /// 型注釈の省略を許可する
+    ///
+    /// `let a = 1;` のように型注釈を省略した変数宣言や、戻り値の型を省略した関数宣言は、
+    /// 型が `Type::Infer` のノードとなり、型推論で型が決まる。
+    /// 関数のパラメータの型注釈は省略できない。
+    pub fn with_type_inference(mut self) -> Self {
+        self.infer_types = true;
+        self
     }
 
     /// トークン列をASTに変換する
@@ -38,7 +78,7 @@ impl Parser {
 
         while !self.check(TokenType::Eof) {
             let line: usize = self.peek().line;
-            let statement: Statement = parse_statement(self)?;
+            let statement: Statement = parse_top_level_statement(self)?;
 
             ast.push_statement(line, statement)
         }
@@ -58,7 +98,6 @@ impl Parser {
     ///
     /// # Returns
     /// - `&Token`: 次のトークン
-    #[allow(dead_code)]
     fn peek_next(&self) -> &Token {
         &self.tokens[self.current + 1]
     }
@@ -71,6 +110,19 @@ impl Parser {
         &self.tokens[self.current + 2]
     }
 
+    /// 現在からn個先のトークンを確認する
+    ///
+    /// トークン列の終わりを越える場合は、最後のトークン (EOF) を返す。
+    ///
+    /// # Returns
+    /// - `&Token`: n個先のトークン
+    fn peek_nth(&self, n: usize) -> &Token {
+        match self.tokens.get(self.current + n) {
+            Some(token) => token,
+            None => &self.tokens[self.tokens.len() - 1],
+        }
+    }
+
     /// 次のトークンに進む
     ///
     /// # Feature
@@ -100,6 +152,11 @@ impl Parser {
         self.peek().token_type == token_type
     }
 
+    /// 型名が既にnewtypeかinterfaceとして宣言されているか
+    fn is_declared_type(&self, name: &str) -> bool {
+        self.newtypes.contains_key(name) || self.interfaces.contains_key(name)
+    }
+
     /// 次のトークンが指定したトークンタイプか確認し、一致しない場合はエラーを返す
     ///
     /// # Arguments
@@ -24,8 +24,19 @@ pub fn get_type_from_current_token(parser: &mut Parser) -> Result<Type, ParserEr
         TokenType::IntType => Ok(Type::Integer),
         TokenType::FloatType => Ok(Type::Float),
         TokenType::StringType => Ok(Type::String),
+        TokenType::BoolType => Ok(Type::Boolean),
         TokenType::VoidType => Ok(Type::Void),
         TokenType::Fn => Ok(Type::Function),
+        TokenType::AnyType => Ok(Type::Any),
+        // newtype文で宣言された型名
+        TokenType::Identifier(name) if parser.newtypes.contains_key(&name) => Ok(Type::Newtype(
+            name.clone(),
+            Box::new(parser.newtypes[&name].clone()),
+        )),
+        // interface文で宣言された型名
+        TokenType::Identifier(name) if parser.interfaces.contains_key(&name) => Ok(
+            Type::Interface(name.clone(), parser.interfaces[&name].clone()),
+        ),
         _ => Err(ParserError::TypeNotFound {
             found: current_token.token_type.clone(),
             line: current_token.line,
@@ -34,6 +45,42 @@ pub fn get_type_from_current_token(parser: &mut Parser) -> Result<Type, ParserEr
     }
 }
 
+/// 型をパースする
+///
+/// 現在のトークンを型として読み取り、その後ろに `?` があればOptional型とする。
+/// `int | string` のように `|` で区切られた型はUnion型とする。
+/// 型を表すトークンは全て読み進められる。
+///
+/// # Syntax
+/// ```BNF
+/// Type ::= OptionalType { "|" OptionalType }
+/// OptionalType ::= BaseType [ "?" ]
+/// BaseType ::= "int" | "float" | "string" | "bool" | "void" | "fn" | "any" | NewtypeName | InterfaceName
+/// ```
+///
+/// # Raises
+/// - `ParserError::TypeNotFound`: 型が見つからなかった場合
+pub fn parse_type(parser: &mut Parser) -> Result<Type, ParserError> {
+    let mut member_types: Vec<Type> = vec![parse_optional_type(parser)?];
+    while parser.peek().token_type == TokenType::Pipe {
+        parser.advance();
+        member_types.push(parse_optional_type(parser)?);
+    }
+    Ok(Type::union(member_types))
+}
+
+fn parse_optional_type(parser: &mut Parser) -> Result<Type, ParserError> {
+    let base_type: Type = get_type_from_current_token(parser)?;
+    parser.advance();
+
+    // int? のように ? が続く場合はOptional型
+    if parser.peek().token_type == TokenType::Question {
+        parser.advance();
+        return Ok(Type::Optional(Box::new(base_type)));
+    }
+    Ok(base_type)
+}
+
 /// Parserを指定のトークン列で初期化するテスト用の関数
 ///
 /// # Arguments
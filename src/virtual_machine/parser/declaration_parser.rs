@@ -1,5 +1,8 @@
+pub mod parse_declaration_of_constant;
 mod parse_declaration_of_function;
+pub mod parse_declaration_of_interface;
 pub mod parse_declaration_of_variable;
+pub mod parse_impl;
 
 use crate::virtual_machine::ast::Statement;
 use crate::virtual_machine::parser::Parser;
@@ -12,11 +15,25 @@ pub(crate) fn parse_declaration(parser: &mut Parser) -> Result<Statement, Parser
     // 変数宣言と関数宣言をRoute
     // let f # 次の次が `type` なら変数宣言
     // let f # 次の次が `fn` なら関数宣言
+    // let f = # 型推論が有効で、型注釈が省略されていれば変数宣言
+    if parser.infer_types && parser.peek_next().token_type == TokenType::Equal {
+        return parse_declaration_of_variable(parser);
+    }
+    // let f: fn = add(x: 1, ...); # fn型でも、初期化式が関数リテラルでなければ変数宣言
+    if parser.peek_next_next().token_type == TokenType::Fn
+        && parser.peek_nth(4).token_type != TokenType::LeftParen
+    {
+        return parse_declaration_of_variable(parser);
+    }
     match parser.peek_next_next().token_type {
         TokenType::StringType => Ok(parse_declaration_of_variable(parser)?),
         TokenType::IntType => Ok(parse_declaration_of_variable(parser)?),
         TokenType::FloatType => Ok(parse_declaration_of_variable(parser)?),
+        TokenType::BoolType => Ok(parse_declaration_of_variable(parser)?),
         TokenType::VoidType => Ok(parse_declaration_of_variable(parser)?),
+        TokenType::AnyType => Ok(parse_declaration_of_variable(parser)?),
+        // let id: UserId = 1 as UserId;  # newtypeで宣言された型
+        TokenType::Identifier(_) => Ok(parse_declaration_of_variable(parser)?),
         TokenType::Fn => Ok(parse_declaration_of_function(parser)?),
         _ => Err(ParserError::MismatchedToken {
             expected: TokenType::Equal,
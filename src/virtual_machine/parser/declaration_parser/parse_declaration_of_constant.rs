@@ -0,0 +1,127 @@
+use crate::virtual_machine::ast::{Statement, VariableDeclarationNode};
+use crate::virtual_machine::parser::declaration_parser::parse_declaration_of_variable::parse_variable_declaration_node;
+use crate::virtual_machine::parser::Parser;
+use crate::virtual_machine::parser::ParserError;
+
+/// 定数宣言文をパースする
+///
+/// # Returns
+/// - `Result<Statement, ParserError>`: パース結果
+///   - `Statement`: パース結果のASTノード
+///   - `ParserError`: エラー情報
+///
+/// # Syntax
+/// 定数宣言文は、プログラムで用いる定数を宣言する文の一つ。
+///
+/// - 定数宣言文`constant_declaration = "const" identifier ":" type "=" expression ";"`
+///
+/// # Examples
+/// - int型の定数MAX_RETRIESを宣言: `const MAX_RETRIES: int = 5;`
+/// - 他の定数を用いた宣言: `const TIMEOUT: int = MAX_RETRIES * 10;`
+///
+/// 初期化式が定数式であるかは、構文解析ではなく定数の畳み込みの際に検査される。
+pub fn parse_declaration_of_constant(parser: &mut Parser) -> Result<Statement, ParserError> {
+    // 変数宣言と同じ構文なので、変数宣言のノードとしてパースする
+    let node: VariableDeclarationNode = parse_variable_declaration_node(parser)?;
+    Ok(Statement::DeclarationOfConstant(Box::new(node)))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::virtual_machine::ast::{
+        BinaryOperationNode, BinaryOperator, ExpressionNode, LiteralNode, LiteralValue, Statement,
+        Type, VariableCallNode, VariableDeclarationNode,
+    };
+    use crate::virtual_machine::parser::core::create_parser_with_tokens;
+    use crate::virtual_machine::parser::declaration_parser::parse_declaration_of_constant::parse_declaration_of_constant;
+    use crate::virtual_machine::parser::parser_error::ParserError;
+    use crate::virtual_machine::parser::{Parser, TokenType};
+    use crate::virtual_machine::token::Token;
+
+    // 正常系テスト
+
+    /// int型の定数の定数宣言のテスト
+    /// const MAX_RETRIES: int = 5;
+    #[test]
+    fn parses_integer_constant_declaration() {
+        // 生成されるAST Node
+        let expected = Box::new(VariableDeclarationNode {
+            name: "MAX_RETRIES".to_string(),
+            var_type: Type::Integer,
+            value: Box::new(ExpressionNode::Literal(Box::new(LiteralNode {
+                value: LiteralValue::Integer(5),
+            }))),
+        });
+
+        // テストする関数の入力である、Token列, Parserの生成
+        // MAX_RETRIES: int = 5;
+        // Const token は Const文の処理 で消費されていることに注意
+        let tokens: Vec<Token> = vec![
+            Token::new(1, 7, TokenType::Identifier("MAX_RETRIES".to_string())),
+            Token::new(1, 18, TokenType::Colon),
+            Token::new(1, 20, TokenType::IntType),
+            Token::new(1, 24, TokenType::Equal),
+            Token::new(1, 26, TokenType::IntegerLiteral(5)),
+            Token::new(1, 27, TokenType::Semicolon),
+        ];
+        let mut parser: Parser = create_parser_with_tokens(tokens);
+
+        // テストしたい関数の出力 (エラーが出ていないことを確認)
+        let result: Result<Statement, ParserError> = parse_declaration_of_constant(&mut parser);
+        assert!(result.is_ok());
+
+        // テストしたい関数の出力と期待値を比較
+        let constant_declaration_node: Box<VariableDeclarationNode> = match result.unwrap() {
+            Statement::DeclarationOfConstant(node) => node,
+            _ => panic!("Expected a DeclarationOfConstant"),
+        };
+        assert_eq!(constant_declaration_node, expected);
+    }
+
+    /// 他の定数を参照する定数宣言のテスト
+    /// const TIMEOUT: int = MAX_RETRIES * 10;
+    #[test]
+    fn parses_constant_declaration_with_other_constant() {
+        // 生成されるAST Node
+        let expected = Box::new(VariableDeclarationNode {
+            name: "TIMEOUT".to_string(),
+            var_type: Type::Integer,
+            value: Box::new(ExpressionNode::BinaryOperation(Box::new(
+                BinaryOperationNode {
+                    left: Box::new(ExpressionNode::CallOfVariable(Box::new(VariableCallNode {
+                        name: "MAX_RETRIES".to_string(),
+                    }))),
+                    operator: BinaryOperator::Multiply,
+                    right: Box::new(ExpressionNode::Literal(Box::new(LiteralNode {
+                        value: LiteralValue::Integer(10),
+                    }))),
+                },
+            ))),
+        });
+
+        // テストする関数の入力である、Token列, Parserの生成
+        // TIMEOUT: int = MAX_RETRIES * 10;
+        let tokens: Vec<Token> = vec![
+            Token::new(1, 7, TokenType::Identifier("TIMEOUT".to_string())),
+            Token::new(1, 14, TokenType::Colon),
+            Token::new(1, 16, TokenType::IntType),
+            Token::new(1, 20, TokenType::Equal),
+            Token::new(1, 22, TokenType::Identifier("MAX_RETRIES".to_string())),
+            Token::new(1, 34, TokenType::Asterisk),
+            Token::new(1, 36, TokenType::IntegerLiteral(10)),
+            Token::new(1, 38, TokenType::Semicolon),
+        ];
+        let mut parser: Parser = create_parser_with_tokens(tokens);
+
+        // テストしたい関数の出力 (エラーが出ていないことを確認)
+        let result: Result<Statement, ParserError> = parse_declaration_of_constant(&mut parser);
+        assert!(result.is_ok());
+
+        // テストしたい関数の出力と期待値を比較
+        let constant_declaration_node: Box<VariableDeclarationNode> = match result.unwrap() {
+            Statement::DeclarationOfConstant(node) => node,
+            _ => panic!("Expected a DeclarationOfConstant"),
+        };
+        assert_eq!(constant_declaration_node, expected);
+    }
+}
@@ -1,11 +1,15 @@
-use crate::virtual_machine::ast::FunctionDeclarationNode;
-use crate::virtual_machine::ast::{Statement, Type};
-use crate::virtual_machine::parser::core::get_type_from_current_token;
+use crate::virtual_machine::ast::{ExpressionNode, Statement, Type};
+use crate::virtual_machine::ast::{FunctionDeclarationNode, ParameterNode};
+use crate::virtual_machine::parser::core::parse_type;
+use crate::virtual_machine::parser::expression_parser::parse_expression;
 use crate::virtual_machine::parser::parser_error::ParserError;
-use crate::virtual_machine::parser::parser_error::ParserError::{MismatchedToken, UnexpectedEof};
+use crate::virtual_machine::parser::parser_error::ParserError::{
+    MismatchedToken, MissingParameterType, UnexpectedEof,
+};
 use crate::virtual_machine::parser::statement_parser::parse_statement;
 use crate::virtual_machine::parser::Parser;
 use crate::virtual_machine::token::token_type::TokenType;
+use std::rc::Rc;
 
 /// 関数宣言をパースする関数
 ///
@@ -19,15 +23,20 @@ use crate::virtual_machine::token::token_type::TokenType;
 ///
 /// ```BNF
 /// FunctionDeclaration ::= Identifier ":" "fn" "=" "(" Parameters ")" ":" Type "{" Statements "}"
-/// Parameters ::= Parameter { "," Parameter }
-/// Parameter ::= Identifier ":" Type
-/// Type ::= "int" | "float" | "string" | "void" | "fn"
+///                       | Identifier ":" "fn" "=" "(" Parameters ")" "{" Statements "}"  (型推論が有効な場合)
+/// Parameters ::= Parameter { "," Parameter } [ "," VariadicParameter ] | VariadicParameter
+/// Parameter ::= Identifier ":" Type [ "=" Expression ]
+///             | "self"  (implとinterfaceの先頭のパラメータのみ)
+/// VariadicParameter ::= "..." Identifier ":" Type
+/// Type ::= ( "int" | "float" | "string" | "bool" | "void" | "fn" ) [ "?" ]
 /// ```
 ///
 /// なお、Statementは `statement_parser.rs` を参照。
 ///
 /// # Example
 /// let f: fn = (): type => { *Statements* };
+/// let f: fn = (retries: int = 3): type => { *Statements* };
+/// let f: fn = (prefix: string, ...parts: string): type => { *Statements* };
 ///
 /// # Note
 /// let tokenは上流の `declaration_parser.rs` の `parse_declaration` で消費されている
@@ -58,27 +67,8 @@ pub(crate) fn parse_declaration_of_function(parser: &mut Parser) -> Result<State
     // Equalがあることを確認して読み飛ばす: let f: fn =
     parser.check_advance(TokenType::Equal)?;
 
-    // LeftParenがあることを確認して読み飛ばす: let f: fn = (
-    parser.check_advance(TokenType::LeftParen)?;
-
-    // 引数を確認する: let f: fn = (x: int, y: string
-    let params: Vec<(String, Type)> = match parser.peek().token_type.clone() {
-        TokenType::RightParen => vec![],
-        _ => parse_parameters(parser)?,
-    };
-
-    // RightParenがあることを確認して読み飛ばす
-    // let f: fn = (x: int, y: float)
-    parser.check_advance(TokenType::RightParen)?;
-
-    // Colonがあることを確認して読み飛ばす
-    // let f: fn = (x: int, y: float):
-    parser.check_advance(TokenType::Colon)?;
-
-    // 戻り値の型を確認する
-    // let f: fn = (x: int, y: float): string
-    let return_type: Type = get_type_from_current_token(parser)?;
-    parser.advance();
+    // パラメータと戻り値の型を読み取る: let f: fn = (x: int, y: float): string
+    let (params, return_type): (Vec<ParameterNode>, Type) = parse_signature(parser)?;
 
     // 左波括弧があることを確認して読み飛ばす
     // let f: fn = (x: int, y: float): string {
@@ -86,13 +76,17 @@ pub(crate) fn parse_declaration_of_function(parser: &mut Parser) -> Result<State
 
     // 関数の中身をパースする
     // let f: fn = (x: int, y: float): string { ...
-    let body: Vec<Statement> = parse_function_body(parser)?;
+    // メソッドの本体で宣言された関数の self は、通常のパラメータとして扱う
+    let self_type: Option<Type> = parser.self_type.take();
+    let body: Result<Vec<(usize, Statement)>, ParserError> = parse_function_body(parser);
+    parser.self_type = self_type;
+    let body: Vec<(usize, Statement)> = body?;
 
     // 右波括弧があることを確認して読み飛ばす
     // let f: fn = (x: int, y: float): string { ... }
     parser.check_advance(TokenType::RightBrace)?;
 
-    Ok(Statement::DeclarationOfFunction(Box::new(
+    Ok(Statement::DeclarationOfFunction(Rc::new(
         FunctionDeclarationNode {
             name,
             params,
@@ -102,9 +96,52 @@ pub(crate) fn parse_declaration_of_function(parser: &mut Parser) -> Result<State
     )))
 }
 
-fn parse_parameters(parser: &mut Parser) -> Result<Vec<(String, Type)>, ParserError> {
-    let mut parameters: Vec<(String, Type)> = vec![];
+/// 関数のパラメータと戻り値の型をパースする
+///
+/// `(x: int, y: float): string` の部分をパースします。
+/// interfaceのメソッドのシグネチャのパースにも用います。
+pub(crate) fn parse_signature(
+    parser: &mut Parser,
+) -> Result<(Vec<ParameterNode>, Type), ParserError> {
+    // LeftParenがあることを確認して読み飛ばす: let f: fn = (
+    parser.check_advance(TokenType::LeftParen)?;
+
+    // 引数を確認する: let f: fn = (x: int, y: string
+    let params: Vec<ParameterNode> = match parser.peek().token_type.clone() {
+        TokenType::RightParen => vec![],
+        _ => parse_parameters(parser)?,
+    };
+
+    // RightParenがあることを確認して読み飛ばす
+    // let f: fn = (x: int, y: float)
+    parser.check_advance(TokenType::RightParen)?;
+
+    // 型推論が有効で、戻り値の型が省略されている場合は型推論で型を決める
+    // let f: fn = (x: int, y: float) {
+    let return_type: Type = if parser.infer_types && parser.check(TokenType::LeftBrace) {
+        Type::Infer
+    } else {
+        // Colonがあることを確認して読み飛ばす
+        // let f: fn = (x: int, y: float):
+        parser.check_advance(TokenType::Colon)?;
+
+        // 戻り値の型を確認する
+        // let f: fn = (x: int, y: float): string
+        parse_type(parser)?
+    };
+    Ok((params, return_type))
+}
+
+fn parse_parameters(parser: &mut Parser) -> Result<Vec<ParameterNode>, ParserError> {
+    let mut parameters: Vec<ParameterNode> = vec![];
     loop {
+        // 引数名の前に `...` があれば可変長パラメータ
+        // f(...parts: string
+        let is_variadic: bool = parser.check(TokenType::Ellipsis);
+        if is_variadic {
+            parser.advance();
+        }
+
         let parameter_name: String = match parser.peek().token_type.clone() {
             TokenType::Identifier(parameter_name) => parameter_name,
             token => {
@@ -118,24 +155,62 @@ fn parse_parameters(parser: &mut Parser) -> Result<Vec<(String, Type)>, ParserEr
         };
         parser.advance();
 
-        // 次のTokenTypeがRightParenならLoopを抜ける
-        if parser.peek().token_type.clone() == TokenType::RightParen {
-            break;
+        // implとinterfaceの中では、型注釈のない先頭の self はimplの対象の型を持つ
+        // impl Host { let url: fn = (self): string { ... }; }
+        if let Some(self_type) = parser.self_type.clone() {
+            if parameters.is_empty() && parameter_name == "self" && !parser.check(TokenType::Colon)
+            {
+                parameters.push(ParameterNode {
+                    name: parameter_name,
+                    param_type: self_type,
+                    default_value: None,
+                    is_variadic: false,
+                });
+                if parser.check(TokenType::RightParen) {
+                    break;
+                }
+                parser.check_advance(TokenType::Comma)?;
+                continue;
+            }
         }
 
         // 引数の後にはコロンと型情報が入る
+        // 型推論が有効な場合も、パラメータの型注釈は省略できない
         // f(x: int ...
-        parser.check_advance(TokenType::Colon)?;
+        if !parser.check(TokenType::Colon) {
+            return Err(MissingParameterType {
+                name: parameter_name,
+                line: parser.peek().line,
+                char_pos: parser.peek().char_pos,
+            });
+        }
+        parser.advance();
 
         // 型情報を取得
-        let parameter_type: Type = get_type_from_current_token(parser)?;
-        parser.advance();
+        let parameter_type: Type = parse_type(parser)?;
+
+        // 型情報の後にイコールがあればデフォルト値をパースする
+        // f(x: int = 0 ...
+        // 可変長パラメータはデフォルト値を持たない
+        let default_value: Option<Box<ExpressionNode>> = match parser.peek().token_type {
+            TokenType::Equal if !is_variadic => {
+                parser.advance();
+                Some(Box::new(parse_expression(parser)?))
+            }
+            _ => None,
+        };
 
-        // 引数と型の組みをpush
-        parameters.push((parameter_name, parameter_type));
+        // パラメータをpush
+        parameters.push(ParameterNode {
+            name: parameter_name,
+            param_type: parameter_type,
+            default_value,
+            is_variadic,
+        });
 
         // 次のTokenTypeがRightParenならLoopを抜ける
-        if parser.peek().token_type.clone() == TokenType::RightParen {
+        // 可変長パラメータは最後のパラメータなので、RightParenが来なければ上流でエラーとなる
+        if parser.peek().token_type.clone() == TokenType::RightParen || is_variadic {
             break;
         }
 
@@ -146,10 +221,12 @@ fn parse_parameters(parser: &mut Parser) -> Result<Vec<(String, Type)>, ParserEr
     Ok(parameters)
 }
 
-fn parse_function_body(parser: &mut Parser) -> Result<Vec<Statement>, ParserError> {
-    let mut statements: Vec<Statement> = vec![];
+/// 関数の本体の文を、行番号とともにパースする
+fn parse_function_body(parser: &mut Parser) -> Result<Vec<(usize, Statement)>, ParserError> {
+    let mut statements: Vec<(usize, Statement)> = vec![];
     loop {
         let token: TokenType = parser.peek().token_type.clone();
+        let line: usize = parser.peek().line;
         match token {
             TokenType::RightBrace => {
                 return Err(MismatchedToken {
@@ -168,14 +245,14 @@ fn parse_function_body(parser: &mut Parser) -> Result<Vec<Statement>, ParserErro
             }
             TokenType::Return => {
                 let statement: Statement = parse_statement(parser)?;
-                statements.push(statement);
+                statements.push((line, statement));
 
                 // Return文で関数は終わる
                 break;
             }
             _ => {
                 let statement: Statement = parse_statement(parser)?;
-                statements.push(statement);
+                statements.push((line, statement));
             }
         }
     }
@@ -184,12 +261,14 @@ fn parse_function_body(parser: &mut Parser) -> Result<Vec<Statement>, ParserErro
 
 #[cfg(test)]
 mod tests {
-    use crate::virtual_machine::ast::{FunctionDeclarationNode, Statement, Type};
+    use crate::virtual_machine::ast::{ExpressionNode, LiteralNode, LiteralValue};
+    use crate::virtual_machine::ast::{FunctionDeclarationNode, ParameterNode, Statement, Type};
     use crate::virtual_machine::parser::core::create_parser_with_tokens;
     use crate::virtual_machine::parser::declaration_parser::parse_declaration_of_function::parse_declaration_of_function;
     use crate::virtual_machine::parser::parser_error::ParserError;
     use crate::virtual_machine::parser::{Parser, TokenType};
     use crate::virtual_machine::token::Token;
+    use std::rc::Rc;
 
     /// 引数のない関数宣言のテスト
     /// f: fn = (): void { return none; };
@@ -229,7 +308,7 @@ mod tests {
         assert!(result.is_ok());
 
         // テストしたい関数の出力と期待値を比較
-        let variable_declaration_node: Box<FunctionDeclarationNode> = match result.unwrap() {
+        let variable_declaration_node: Rc<FunctionDeclarationNode> = match result.unwrap() {
             Statement::DeclarationOfFunction(node) => node,
             _ => panic!("Expected a DeclarationOfFunction"),
         };
@@ -238,6 +317,74 @@ mod tests {
         assert_eq!(variable_declaration_node.return_type, expected.return_type);
     }
 
+    /// 型推論が有効な場合に戻り値の型を省略した関数宣言のテスト
+    /// f: fn = () { return none; };
+    #[test]
+    fn parse_function_declaration_without_return_type_with_type_inference() {
+        // テストする関数の入力である、Token列, Parserの生成
+        // let f: fn = () { return none; };
+        // Let token は Let文の処理 で消費されていることに注意
+        let tokens: Vec<Token> = vec![
+            Token::new(1, 2, TokenType::Identifier("f".to_string())),
+            Token::new(1, 3, TokenType::Colon),
+            Token::new(1, 4, TokenType::Fn),
+            Token::new(1, 5, TokenType::Equal),
+            Token::new(1, 5, TokenType::LeftParen),
+            Token::new(1, 5, TokenType::RightParen),
+            Token::new(1, 5, TokenType::LeftBrace),
+            Token::new(1, 5, TokenType::Return),
+            Token::new(1, 4, TokenType::NoneLiteral),
+            Token::new(1, 7, TokenType::Semicolon),
+            Token::new(1, 5, TokenType::RightBrace),
+            Token::new(1, 7, TokenType::Semicolon),
+        ];
+        let mut parser: Parser = create_parser_with_tokens(tokens).with_type_inference();
+
+        // テストしたい関数の出力 (エラーが出ていないことを確認)
+        let result: Result<Statement, ParserError> = parse_declaration_of_function(&mut parser);
+        assert!(result.is_ok());
+
+        // 戻り値の型は型推論で決まる
+        let function_declaration_node: Rc<FunctionDeclarationNode> = match result.unwrap() {
+            Statement::DeclarationOfFunction(node) => node,
+            _ => panic!("Expected a DeclarationOfFunction"),
+        };
+        assert_eq!(function_declaration_node.return_type, Type::Infer);
+    }
+
+    /// 型注釈のないパラメータは、型推論が有効な場合もエラーになることを確認するテスト
+    /// f: fn = (x) { return x; };
+    #[test]
+    fn parse_function_declaration_with_untyped_parameter() {
+        // let f: fn = (x) { return x; };
+        // Let token は Let文の処理 で消費されていることに注意
+        let tokens: Vec<Token> = vec![
+            Token::new(1, 2, TokenType::Identifier("f".to_string())),
+            Token::new(1, 3, TokenType::Colon),
+            Token::new(1, 4, TokenType::Fn),
+            Token::new(1, 5, TokenType::Equal),
+            Token::new(1, 6, TokenType::LeftParen),
+            Token::new(1, 7, TokenType::Identifier("x".to_string())),
+            Token::new(1, 8, TokenType::RightParen),
+            Token::new(1, 9, TokenType::LeftBrace),
+            Token::new(1, 10, TokenType::Return),
+            Token::new(1, 11, TokenType::Identifier("x".to_string())),
+            Token::new(1, 12, TokenType::Semicolon),
+            Token::new(1, 13, TokenType::RightBrace),
+            Token::new(1, 14, TokenType::Semicolon),
+        ];
+        let mut parser: Parser = create_parser_with_tokens(tokens).with_type_inference();
+
+        assert_eq!(
+            parse_declaration_of_function(&mut parser),
+            Err(ParserError::MissingParameterType {
+                name: "x".to_string(),
+                line: 1,
+                char_pos: 8,
+            })
+        );
+    }
+
     /// 引数が一つの関数宣言のテスト
     /// f: fn = (x: string): void { return none; };
     #[test]
@@ -245,7 +392,12 @@ mod tests {
         // 生成されるAST Node
         let expected = Box::new(FunctionDeclarationNode {
             name: "f".to_string(),
-            params: vec![(String::from("x"), Type::String)],
+            params: vec![ParameterNode {
+                name: String::from("x"),
+                param_type: Type::String,
+                default_value: None,
+                is_variadic: false,
+            }],
             return_type: Type::Void,
             body: vec![],
         });
@@ -279,7 +431,7 @@ mod tests {
         assert!(result.is_ok());
 
         // テストしたい関数の出力と期待値を比較
-        let variable_declaration_node: Box<FunctionDeclarationNode> = match result.unwrap() {
+        let variable_declaration_node: Rc<FunctionDeclarationNode> = match result.unwrap() {
             Statement::DeclarationOfFunction(node) => node,
             _ => panic!("Expected a DeclarationOfFunction"),
         };
@@ -296,8 +448,18 @@ mod tests {
         let expected = Box::new(FunctionDeclarationNode {
             name: "f".to_string(),
             params: vec![
-                (String::from("x"), Type::Integer),
-                (String::from("y"), Type::Float),
+                ParameterNode {
+                    name: String::from("x"),
+                    param_type: Type::Integer,
+                    default_value: None,
+                    is_variadic: false,
+                },
+                ParameterNode {
+                    name: String::from("y"),
+                    param_type: Type::Float,
+                    default_value: None,
+                    is_variadic: false,
+                },
             ],
             return_type: Type::Void,
             body: vec![],
@@ -336,7 +498,7 @@ mod tests {
         assert!(result.is_ok());
 
         // テストしたい関数の出力と期待値を比較
-        let variable_declaration_node: Box<FunctionDeclarationNode> = match result.unwrap() {
+        let variable_declaration_node: Rc<FunctionDeclarationNode> = match result.unwrap() {
             Statement::DeclarationOfFunction(node) => node,
             _ => panic!("Expected a DeclarationOfFunction"),
         };
@@ -383,7 +545,7 @@ mod tests {
         assert!(result.is_ok());
 
         // テストしたい関数の出力と期待値を比較
-        let variable_declaration_node: Box<FunctionDeclarationNode> = match result.unwrap() {
+        let variable_declaration_node: Rc<FunctionDeclarationNode> = match result.unwrap() {
             Statement::DeclarationOfFunction(node) => node,
             _ => panic!("Expected a DeclarationOfFunction"),
         };
@@ -391,4 +553,193 @@ mod tests {
         assert_eq!(variable_declaration_node.params, expected.params);
         assert_eq!(variable_declaration_node.return_type, expected.return_type);
     }
+
+    /// デフォルト値を持つパラメータの関数宣言のテスト
+    /// f: fn = (retries: int = 3, verbose: bool = false): void { return none; };
+    #[test]
+    fn parse_function_declaration_with_default_values() {
+        // 生成されるAST Node
+        let expected = Box::new(FunctionDeclarationNode {
+            name: "f".to_string(),
+            params: vec![
+                ParameterNode {
+                    name: String::from("retries"),
+                    param_type: Type::Integer,
+                    default_value: Some(Box::new(ExpressionNode::Literal(Box::new(LiteralNode {
+                        value: LiteralValue::Integer(3),
+                    })))),
+                    is_variadic: false,
+                },
+                ParameterNode {
+                    name: String::from("verbose"),
+                    param_type: Type::Boolean,
+                    default_value: Some(Box::new(ExpressionNode::Literal(Box::new(LiteralNode {
+                        value: LiteralValue::Boolean(false),
+                    })))),
+                    is_variadic: false,
+                },
+            ],
+            return_type: Type::Void,
+            body: vec![],
+        });
+
+        // テストする関数の入力である、Token列, Parserの生成
+        // let f: fn = (retries: int = 3, verbose: bool = false): void { return none; };
+        // Let token は Let文の処理 で消費されていることに注意
+        let tokens: Vec<Token> = vec![
+            Token::new(1, 2, TokenType::Identifier("f".to_string())),
+            Token::new(1, 3, TokenType::Colon),
+            Token::new(1, 4, TokenType::Fn),
+            Token::new(1, 5, TokenType::Equal),
+            Token::new(1, 5, TokenType::LeftParen),
+            Token::new(1, 5, TokenType::Identifier("retries".to_string())),
+            Token::new(1, 3, TokenType::Colon),
+            Token::new(1, 4, TokenType::IntType),
+            Token::new(1, 5, TokenType::Equal),
+            Token::new(1, 5, TokenType::IntegerLiteral(3)),
+            Token::new(1, 5, TokenType::Comma),
+            Token::new(1, 5, TokenType::Identifier("verbose".to_string())),
+            Token::new(1, 3, TokenType::Colon),
+            Token::new(1, 4, TokenType::BoolType),
+            Token::new(1, 5, TokenType::Equal),
+            Token::new(1, 5, TokenType::BooleanLiteral(false)),
+            Token::new(1, 5, TokenType::RightParen),
+            Token::new(1, 3, TokenType::Colon),
+            Token::new(1, 4, TokenType::VoidType),
+            Token::new(1, 5, TokenType::LeftBrace),
+            Token::new(1, 5, TokenType::Return),
+            Token::new(1, 4, TokenType::NoneLiteral),
+            Token::new(1, 7, TokenType::Semicolon),
+            Token::new(1, 5, TokenType::RightBrace),
+            Token::new(1, 7, TokenType::Semicolon),
+        ];
+        let mut parser: Parser = create_parser_with_tokens(tokens);
+
+        // テストしたい関数の出力 (エラーが出ていないことを確認)
+        let result: Result<Statement, ParserError> = parse_declaration_of_function(&mut parser);
+        assert!(result.is_ok());
+
+        // テストしたい関数の出力と期待値を比較
+        let variable_declaration_node: Rc<FunctionDeclarationNode> = match result.unwrap() {
+            Statement::DeclarationOfFunction(node) => node,
+            _ => panic!("Expected a DeclarationOfFunction"),
+        };
+        assert_eq!(variable_declaration_node.name, expected.name);
+        assert_eq!(variable_declaration_node.params, expected.params);
+        assert_eq!(variable_declaration_node.return_type, expected.return_type);
+    }
+
+    /// 可変長パラメータを持つ関数宣言のテスト
+    /// f: fn = (prefix: string, ...parts: string): void { return none; };
+    #[test]
+    fn parse_function_declaration_with_variadic_parameter() {
+        // 生成されるAST Node
+        let expected = Box::new(FunctionDeclarationNode {
+            name: "f".to_string(),
+            params: vec![
+                ParameterNode {
+                    name: String::from("prefix"),
+                    param_type: Type::String,
+                    default_value: None,
+                    is_variadic: false,
+                },
+                ParameterNode {
+                    name: String::from("parts"),
+                    param_type: Type::String,
+                    default_value: None,
+                    is_variadic: true,
+                },
+            ],
+            return_type: Type::Void,
+            body: vec![],
+        });
+
+        // テストする関数の入力である、Token列, Parserの生成
+        // let f: fn = (prefix: string, ...parts: string): void { return none; };
+        // Let token は Let文の処理 で消費されていることに注意
+        let tokens: Vec<Token> = vec![
+            Token::new(1, 2, TokenType::Identifier("f".to_string())),
+            Token::new(1, 3, TokenType::Colon),
+            Token::new(1, 4, TokenType::Fn),
+            Token::new(1, 5, TokenType::Equal),
+            Token::new(1, 5, TokenType::LeftParen),
+            Token::new(1, 5, TokenType::Identifier("prefix".to_string())),
+            Token::new(1, 3, TokenType::Colon),
+            Token::new(1, 4, TokenType::StringType),
+            Token::new(1, 5, TokenType::Comma),
+            Token::new(1, 5, TokenType::Ellipsis),
+            Token::new(1, 5, TokenType::Identifier("parts".to_string())),
+            Token::new(1, 3, TokenType::Colon),
+            Token::new(1, 4, TokenType::StringType),
+            Token::new(1, 5, TokenType::RightParen),
+            Token::new(1, 3, TokenType::Colon),
+            Token::new(1, 4, TokenType::VoidType),
+            Token::new(1, 5, TokenType::LeftBrace),
+            Token::new(1, 5, TokenType::Return),
+            Token::new(1, 4, TokenType::NoneLiteral),
+            Token::new(1, 7, TokenType::Semicolon),
+            Token::new(1, 5, TokenType::RightBrace),
+            Token::new(1, 7, TokenType::Semicolon),
+        ];
+        let mut parser: Parser = create_parser_with_tokens(tokens);
+
+        // テストしたい関数の出力 (エラーが出ていないことを確認)
+        let result: Result<Statement, ParserError> = parse_declaration_of_function(&mut parser);
+        assert!(result.is_ok());
+
+        // テストしたい関数の出力と期待値を比較
+        let variable_declaration_node: Rc<FunctionDeclarationNode> = match result.unwrap() {
+            Statement::DeclarationOfFunction(node) => node,
+            _ => panic!("Expected a DeclarationOfFunction"),
+        };
+        assert_eq!(variable_declaration_node.name, expected.name);
+        assert_eq!(variable_declaration_node.params, expected.params);
+        assert_eq!(variable_declaration_node.return_type, expected.return_type);
+    }
+
+    /// 可変長パラメータが最後のパラメータでない場合のテスト
+    /// f: fn = (...parts: string, prefix: string): void { return none; };
+    #[test]
+    fn raise_error_with_variadic_parameter_not_at_last() {
+        // テストする関数の入力である、Token列, Parserの生成
+        // let f: fn = (...parts: string, prefix: string): void { return none; };
+        // Let token は Let文の処理 で消費されていることに注意
+        let tokens: Vec<Token> = vec![
+            Token::new(1, 2, TokenType::Identifier("f".to_string())),
+            Token::new(1, 3, TokenType::Colon),
+            Token::new(1, 4, TokenType::Fn),
+            Token::new(1, 5, TokenType::Equal),
+            Token::new(1, 5, TokenType::LeftParen),
+            Token::new(1, 5, TokenType::Ellipsis),
+            Token::new(1, 5, TokenType::Identifier("parts".to_string())),
+            Token::new(1, 3, TokenType::Colon),
+            Token::new(1, 4, TokenType::StringType),
+            Token::new(1, 5, TokenType::Comma),
+            Token::new(1, 5, TokenType::Identifier("prefix".to_string())),
+            Token::new(1, 3, TokenType::Colon),
+            Token::new(1, 4, TokenType::StringType),
+            Token::new(1, 5, TokenType::RightParen),
+            Token::new(1, 3, TokenType::Colon),
+            Token::new(1, 4, TokenType::VoidType),
+            Token::new(1, 5, TokenType::LeftBrace),
+            Token::new(1, 5, TokenType::Return),
+            Token::new(1, 4, TokenType::NoneLiteral),
+            Token::new(1, 7, TokenType::Semicolon),
+            Token::new(1, 5, TokenType::RightBrace),
+            Token::new(1, 7, TokenType::Semicolon),
+        ];
+        let mut parser: Parser = create_parser_with_tokens(tokens);
+
+        // テストしたい関数の出力 (可変長パラメータの後にRightParenがないためエラー)
+        let result: Result<Statement, ParserError> = parse_declaration_of_function(&mut parser);
+        match result {
+            Err(ParserError::MismatchedToken {
+                expected, found, ..
+            }) => {
+                assert_eq!(expected, TokenType::RightParen);
+                assert_eq!(found, TokenType::Comma);
+            }
+            _ => panic!("Expected MismatchedToken error"),
+        }
+    }
 }
@@ -0,0 +1,213 @@
+use crate::virtual_machine::ast::{
+    InterfaceDeclarationNode, MethodSignatureNode, ParameterNode, Statement, Type,
+};
+use crate::virtual_machine::parser::declaration_parser::parse_declaration_of_function::parse_signature;
+use crate::virtual_machine::parser::parser_error::ParserError;
+use crate::virtual_machine::parser::Parser;
+use crate::virtual_machine::token::token_type::TokenType;
+use crate::virtual_machine::token::Token;
+
+/// interface宣言をパースする関数
+///
+/// # Returns
+/// - `Result<Statement, ParserError>`: パース結果
+///   - Statement::DeclarationOfInterface: interface宣言のAST Node
+///   - ParserError: エラーの種類
+///
+/// # Syntax
+/// interface宣言の構文は以下の通り。
+/// メソッドのシグネチャは、関数宣言から本体を取り除いたものです。
+///
+/// ```BNF
+/// InterfaceDeclaration ::= "interface" Identifier "{" { MethodSignature } "}"
+/// MethodSignature ::= "let" Identifier ":" "fn" "=" "(" "self" [ "," Parameters ] ")" ":" Type ";"
+/// ```
+///
+/// # Example
+/// ```shot
+/// interface Addressable {
+///     let url: fn = (self): string;
+///     let port: fn = (self, secure: bool): int;
+/// }
+/// ```
+///
+/// # Raises
+/// - `ParserError::DuplicateType`: 同じ型名が既に宣言されている場合
+/// - `ParserError::MissingSelfParameter`: メソッドの先頭のパラメータが self でない場合
+pub fn parse_declaration_of_interface(parser: &mut Parser) -> Result<Statement, ParserError> {
+    // interfaceキーワードを読み飛ばす
+    parser.check_advance(TokenType::Interface)?;
+
+    // 型名を読み取る
+    let name: String = parse_type_name(parser)?;
+
+    parser.check_advance(TokenType::LeftBrace)?;
+
+    // シグネチャの self は型を持たないため、any型として読み取って取り除く
+    let self_type: Option<Type> = parser.self_type.replace(Type::Any);
+    let methods: Result<Vec<MethodSignatureNode>, ParserError> = parse_method_signatures(parser);
+    parser.self_type = self_type;
+    let methods: Vec<MethodSignatureNode> = methods?;
+
+    parser.check_advance(TokenType::RightBrace)?;
+
+    parser.interfaces.insert(name.clone(), methods.clone());
+    Ok(Statement::DeclarationOfInterface(Box::new(
+        InterfaceDeclarationNode { name, methods },
+    )))
+}
+
+/// 宣言する型名を読み取る
+fn parse_type_name(parser: &mut Parser) -> Result<String, ParserError> {
+    let token: Token = parser.peek().clone();
+    let name: String = match token.token_type {
+        TokenType::Identifier(name) => name,
+        token_type => {
+            return Err(ParserError::MismatchedToken {
+                expected: TokenType::Identifier("type name".to_string()),
+                found: token_type,
+                line: token.line,
+                char_pos: token.char_pos,
+            })
+        }
+    };
+    if parser.is_declared_type(&name) {
+        return Err(ParserError::DuplicateType {
+            name,
+            line: token.line,
+            char_pos: token.char_pos,
+        });
+    }
+    parser.advance();
+    Ok(name)
+}
+
+fn parse_method_signatures(parser: &mut Parser) -> Result<Vec<MethodSignatureNode>, ParserError> {
+    let mut methods: Vec<MethodSignatureNode> = vec![];
+    while !parser.check(TokenType::RightBrace) {
+        // let url: fn = (self): string;
+        parser.check_advance(TokenType::Let)?;
+        let token: Token = parser.peek().clone();
+        let name: String = match token.token_type {
+            TokenType::Identifier(name) => name,
+            token_type => {
+                return Err(ParserError::MismatchedToken {
+                    expected: TokenType::Identifier("method name".to_string()),
+                    found: token_type,
+                    line: token.line,
+                    char_pos: token.char_pos,
+                })
+            }
+        };
+        parser.advance();
+        parser.check_advance(TokenType::Colon)?;
+        parser.check_advance(TokenType::Fn)?;
+        parser.check_advance(TokenType::Equal)?;
+        let (mut params, return_type): (Vec<ParameterNode>, Type) = parse_signature(parser)?;
+        parser.check_advance(TokenType::Semicolon)?;
+
+        // 先頭のパラメータは self でなければならない
+        if params.first().map(|param| param.name.as_str()) != Some("self") {
+            return Err(ParserError::MissingSelfParameter {
+                method: name,
+                line: token.line,
+                char_pos: token.char_pos,
+            });
+        }
+        params.remove(0);
+
+        methods.push(MethodSignatureNode {
+            name,
+            params,
+            return_type,
+        });
+    }
+    Ok(methods)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::virtual_machine::parser::core::create_parser_with_tokens;
+
+    /// interface宣言をパースし、selfを除いたシグネチャを得られるか確認するテスト
+    /// interface Addressable { let url: fn = (self, secure: bool): string; }
+    #[test]
+    fn parse_interface_declaration() {
+        let tokens: Vec<Token> = vec![
+            Token::new(1, 1, TokenType::Interface),
+            Token::new(1, 11, TokenType::Identifier("Addressable".to_string())),
+            Token::new(1, 23, TokenType::LeftBrace),
+            Token::new(1, 25, TokenType::Let),
+            Token::new(1, 29, TokenType::Identifier("url".to_string())),
+            Token::new(1, 32, TokenType::Colon),
+            Token::new(1, 34, TokenType::Fn),
+            Token::new(1, 37, TokenType::Equal),
+            Token::new(1, 39, TokenType::LeftParen),
+            Token::new(1, 40, TokenType::Identifier("self".to_string())),
+            Token::new(1, 44, TokenType::Comma),
+            Token::new(1, 46, TokenType::Identifier("secure".to_string())),
+            Token::new(1, 52, TokenType::Colon),
+            Token::new(1, 54, TokenType::BoolType),
+            Token::new(1, 58, TokenType::RightParen),
+            Token::new(1, 59, TokenType::Colon),
+            Token::new(1, 61, TokenType::StringType),
+            Token::new(1, 67, TokenType::Semicolon),
+            Token::new(1, 69, TokenType::RightBrace),
+        ];
+        let mut parser: Parser = create_parser_with_tokens(tokens);
+
+        let methods: Vec<MethodSignatureNode> = vec![MethodSignatureNode {
+            name: "url".to_string(),
+            params: vec![ParameterNode {
+                name: "secure".to_string(),
+                param_type: Type::Boolean,
+                default_value: None,
+                is_variadic: false,
+            }],
+            return_type: Type::String,
+        }];
+        assert_eq!(
+            parse_declaration_of_interface(&mut parser),
+            Ok(Statement::DeclarationOfInterface(Box::new(
+                InterfaceDeclarationNode {
+                    name: "Addressable".to_string(),
+                    methods: methods.clone(),
+                }
+            )))
+        );
+        assert_eq!(parser.interfaces.get("Addressable"), Some(&methods));
+    }
+
+    /// selfを受け取らないメソッドのシグネチャがエラーになるか確認するテスト
+    /// interface Addressable { let url: fn = (): string; }
+    #[test]
+    fn fail_interface_method_without_self() {
+        let tokens: Vec<Token> = vec![
+            Token::new(1, 1, TokenType::Interface),
+            Token::new(1, 11, TokenType::Identifier("Addressable".to_string())),
+            Token::new(1, 23, TokenType::LeftBrace),
+            Token::new(1, 25, TokenType::Let),
+            Token::new(1, 29, TokenType::Identifier("url".to_string())),
+            Token::new(1, 32, TokenType::Colon),
+            Token::new(1, 34, TokenType::Fn),
+            Token::new(1, 37, TokenType::Equal),
+            Token::new(1, 39, TokenType::LeftParen),
+            Token::new(1, 40, TokenType::RightParen),
+            Token::new(1, 41, TokenType::Colon),
+            Token::new(1, 43, TokenType::StringType),
+            Token::new(1, 49, TokenType::Semicolon),
+            Token::new(1, 51, TokenType::RightBrace),
+        ];
+        let mut parser: Parser = create_parser_with_tokens(tokens);
+
+        assert_eq!(
+            parse_declaration_of_interface(&mut parser),
+            Err(ParserError::MissingSelfParameter {
+                method: "url".to_string(),
+                line: 1,
+                char_pos: 29,
+            })
+        );
+    }
+}
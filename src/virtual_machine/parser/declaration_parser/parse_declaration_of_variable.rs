@@ -1,6 +1,6 @@
 use crate::virtual_machine::ast::VariableDeclarationNode;
 use crate::virtual_machine::ast::{ExpressionNode, Statement, Type};
-use crate::virtual_machine::parser::core::get_type_from_current_token;
+use crate::virtual_machine::parser::core::parse_type;
 use crate::virtual_machine::parser::expression_parser::parse_expression;
 use crate::virtual_machine::parser::Parser;
 use crate::virtual_machine::parser::ParserError;
@@ -17,6 +17,7 @@ use crate::virtual_machine::token::token_type::TokenType;
 /// 変数宣言文は、プログラムで用いる変数を宣言する文の一つ。
 ///
 /// - 変数宣言文`variable_declaration = "let" identifier ":" type "=" expression ";"`
+/// - 型推論が有効な場合は型注釈を省略できる`variable_declaration = "let" identifier [ ":" type ] "=" expression ";"`
 ///
 /// # Examples
 /// - string型の変数nameを宣言: `let name: string = "shunsock";`
@@ -26,6 +27,21 @@ use crate::virtual_machine::token::token_type::TokenType;
 ///
 /// - BinaryOperationが式である事例: `let num: int = 1 + 2;`
 pub fn parse_declaration_of_variable(parser: &mut Parser) -> Result<Statement, ParserError> {
+    let node: VariableDeclarationNode = parse_variable_declaration_node(parser)?;
+    Ok(Statement::DeclarationOfVariable(Box::new(node)))
+}
+
+/// 変数宣言の名前、型、初期化式をパースする
+///
+/// 定数宣言も同じ構文を持つため、`parse_declaration_of_constant` からも利用される。
+///
+/// # Returns
+/// - `Result<VariableDeclarationNode, ParserError>`: パース結果
+///   - `VariableDeclarationNode`: 変数宣言のASTノード
+///   - `ParserError`: エラー情報
+pub(crate) fn parse_variable_declaration_node(
+    parser: &mut Parser,
+) -> Result<VariableDeclarationNode, ParserError> {
     // 名前を読み取る
     let name: String = match parser.peek().token_type.clone() {
         TokenType::Identifier(name) => name,
@@ -40,12 +56,17 @@ pub fn parse_declaration_of_variable(parser: &mut Parser) -> Result<Statement, P
     };
     parser.advance();
 
-    // colonを読み飛ばす
-    parser.check_advance(TokenType::Colon)?;
+    // 型推論が有効で、型注釈が省略されている場合は型推論で型を決める
+    // let num = 0;
+    let variable_type: Type = if parser.infer_types && parser.check(TokenType::Equal) {
+        Type::Infer
+    } else {
+        // colonを読み飛ばす
+        parser.check_advance(TokenType::Colon)?;
 
-    // 型を読み取る
-    let variable_type: Type = get_type_from_current_token(parser)?;
-    parser.advance();
+        // 型を読み取る
+        parse_type(parser)?
+    };
 
     // イコールを読み飛ばす
     parser.check_advance(TokenType::Equal)?;
@@ -53,13 +74,11 @@ pub fn parse_declaration_of_variable(parser: &mut Parser) -> Result<Statement, P
     // 式をパース
     let expr: ExpressionNode = parse_expression(parser)?;
 
-    Ok(Statement::DeclarationOfVariable(Box::new(
-        VariableDeclarationNode {
-            name,
-            var_type: variable_type,
-            value: Box::new(expr),
-        },
-    )))
+    Ok(VariableDeclarationNode {
+        name,
+        var_type: variable_type,
+        value: Box::new(expr),
+    })
 }
 
 #[cfg(test)]
@@ -312,4 +331,40 @@ mod tests {
         let result: Result<Statement, ParserError> = parse_declaration_of_variable(&mut parser);
         assert!(result.is_err());
     }
+
+    /// 型推論が有効な場合に型注釈を省略した変数宣言のテスト
+    /// let num = 0;
+    #[test]
+    fn parses_variable_declaration_without_type_annotation_with_type_inference() {
+        // 生成されるAST Node
+        let expected = Box::new(VariableDeclarationNode {
+            name: "num".to_string(),
+            var_type: Type::Infer,
+            value: Box::new(ExpressionNode::Literal(Box::new(LiteralNode {
+                value: LiteralValue::Integer(0),
+            }))),
+        });
+
+        // テストする関数の入力である、Token列, Parserの生成
+        // num = 0;
+        // Let token は Let文の処理 で消費されていることに注意
+        let tokens: Vec<Token> = vec![
+            Token::new(1, 2, TokenType::Identifier("num".to_string())),
+            Token::new(1, 3, TokenType::Equal),
+            Token::new(1, 4, TokenType::IntegerLiteral(0)),
+            Token::new(1, 5, TokenType::Semicolon),
+        ];
+        let mut parser: Parser = create_parser_with_tokens(tokens).with_type_inference();
+
+        // テストしたい関数の出力 (エラーが出ていないことを確認)
+        let result: Result<Statement, ParserError> = parse_declaration_of_variable(&mut parser);
+        assert!(result.is_ok());
+
+        // テストしたい関数の出力と期待値を比較
+        let variable_declaration_node: Box<VariableDeclarationNode> = match result.unwrap() {
+            Statement::DeclarationOfVariable(node) => node,
+            _ => panic!("Expected a DeclarationOfVariable"),
+        };
+        assert_eq!(variable_declaration_node, expected);
+    }
 }
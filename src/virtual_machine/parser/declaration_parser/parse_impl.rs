@@ -0,0 +1,156 @@
+use crate::virtual_machine::ast::{FunctionDeclarationNode, ImplNode, Statement, Type};
+use crate::virtual_machine::parser::core::parse_type;
+use crate::virtual_machine::parser::declaration_parser::parse_declaration_of_function::parse_declaration_of_function;
+use crate::virtual_machine::parser::parser_error::ParserError;
+use crate::virtual_machine::parser::Parser;
+use crate::virtual_machine::token::token_type::TokenType;
+use crate::virtual_machine::token::Token;
+use std::rc::Rc;
+
+/// impl文をパースする関数
+///
+/// # Returns
+/// - `Result<Statement, ParserError>`: パース結果
+///   - Statement::Impl: impl文のAST Node
+///   - ParserError: エラーの種類
+///
+/// # Syntax
+/// impl文の構文は以下の通り。
+/// メソッドは関数宣言と同じ構文で、先頭のパラメータは型注釈のない `self` です。
+///
+/// ```BNF
+/// Impl ::= "impl" Type "{" { "let" FunctionDeclaration ";" } "}"
+/// ```
+///
+/// # Example
+/// ```shot
+/// impl Host {
+///     let url: fn = (self): string { return "https://" + (self as string); };
+/// }
+/// ```
+///
+/// # Raises
+/// - `ParserError::MissingSelfParameter`: メソッドの先頭のパラメータが self でない場合
+pub fn parse_impl(parser: &mut Parser) -> Result<Statement, ParserError> {
+    // implキーワードを読み飛ばす
+    parser.check_advance(TokenType::Impl)?;
+
+    // メソッドを実装する型を読み取る
+    let target: Type = parse_type(parser)?;
+
+    parser.check_advance(TokenType::LeftBrace)?;
+
+    let self_type: Option<Type> = parser.self_type.replace(target.clone());
+    let methods: Result<Vec<Rc<FunctionDeclarationNode>>, ParserError> = parse_methods(parser);
+    parser.self_type = self_type;
+    let methods: Vec<Rc<FunctionDeclarationNode>> = methods?;
+
+    parser.check_advance(TokenType::RightBrace)?;
+
+    Ok(Statement::Impl(Box::new(ImplNode { target, methods })))
+}
+
+fn parse_methods(parser: &mut Parser) -> Result<Vec<Rc<FunctionDeclarationNode>>, ParserError> {
+    let mut methods: Vec<Rc<FunctionDeclarationNode>> = vec![];
+    while !parser.check(TokenType::RightBrace) {
+        // let url: fn = (self): string { ... };
+        parser.check_advance(TokenType::Let)?;
+        let token: Token = parser.peek().clone();
+        let method: Rc<FunctionDeclarationNode> = match parse_declaration_of_function(parser)? {
+            Statement::DeclarationOfFunction(method) => method,
+            _ => {
+                return Err(ParserError::MismatchedToken {
+                    expected: TokenType::Fn,
+                    found: token.token_type,
+                    line: token.line,
+                    char_pos: token.char_pos,
+                })
+            }
+        };
+        parser.check_advance(TokenType::Semicolon)?;
+
+        // 先頭のパラメータは self でなければならない
+        if method.params.first().map(|param| param.name.as_str()) != Some("self") {
+            return Err(ParserError::MissingSelfParameter {
+                method: method.name.clone(),
+                line: token.line,
+                char_pos: token.char_pos,
+            });
+        }
+        methods.push(method);
+    }
+    Ok(methods)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::virtual_machine::ast::{
+        ExpressionNode, ParameterNode, TypeCastNode, VariableCallNode,
+    };
+    use crate::virtual_machine::parser::core::create_parser_with_tokens;
+
+    /// impl文をパースし、selfがimplの対象の型を持つか確認するテスト
+    /// impl int { let twice: fn = (self): int { return self; }; }
+    #[test]
+    fn parse_impl_with_self_parameter() {
+        let tokens: Vec<Token> = vec![
+            Token::new(1, 1, TokenType::Impl),
+            Token::new(1, 6, TokenType::IntType),
+            Token::new(1, 10, TokenType::LeftBrace),
+            Token::new(1, 12, TokenType::Let),
+            Token::new(1, 16, TokenType::Identifier("twice".to_string())),
+            Token::new(1, 21, TokenType::Colon),
+            Token::new(1, 23, TokenType::Fn),
+            Token::new(1, 26, TokenType::Equal),
+            Token::new(1, 28, TokenType::LeftParen),
+            Token::new(1, 29, TokenType::Identifier("self".to_string())),
+            Token::new(1, 33, TokenType::RightParen),
+            Token::new(1, 34, TokenType::Colon),
+            Token::new(1, 36, TokenType::IntType),
+            Token::new(1, 40, TokenType::LeftBrace),
+            Token::new(1, 42, TokenType::Return),
+            Token::new(1, 49, TokenType::Identifier("self".to_string())),
+            Token::new(1, 53, TokenType::As),
+            Token::new(1, 56, TokenType::IntType),
+            Token::new(1, 59, TokenType::Semicolon),
+            Token::new(1, 61, TokenType::RightBrace),
+            Token::new(1, 62, TokenType::Semicolon),
+            Token::new(1, 64, TokenType::RightBrace),
+        ];
+        let mut parser: Parser = create_parser_with_tokens(tokens);
+
+        assert_eq!(
+            parse_impl(&mut parser),
+            Ok(Statement::Impl(Box::new(ImplNode {
+                target: Type::Integer,
+                methods: vec![Rc::new(FunctionDeclarationNode {
+                    name: "twice".to_string(),
+                    params: vec![ParameterNode {
+                        name: "self".to_string(),
+                        param_type: Type::Integer,
+                        default_value: None,
+                        is_variadic: false,
+                    }],
+                    return_type: Type::Integer,
+                    body: vec![(
+                        1,
+                        Statement::Return(Box::new(ExpressionNode::TypeCast(Box::new(
+                            TypeCastNode {
+                                from_type: None,
+                                to_type: Type::Integer,
+                                is_checked: false,
+                                expression: Box::new(ExpressionNode::CallOfVariable(Box::new(
+                                    VariableCallNode {
+                                        name: "self".to_string(),
+                                    }
+                                ))),
+                            }
+                        ))))
+                    )],
+                })],
+            })))
+        );
+        assert_eq!(parser.self_type, None);
+    }
+}
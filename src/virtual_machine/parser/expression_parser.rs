@@ -1,11 +1,14 @@
 pub mod parse_binary;
 pub mod parse_call_of_variable_and_function;
+mod parse_if;
 pub mod parse_parenthesized;
 pub mod parse_primary;
 mod parse_type_cast;
+mod parse_type_test;
 
 use crate::virtual_machine::ast::ExpressionNode;
 use crate::virtual_machine::parser::expression_parser::parse_type_cast::parse_type_cast;
+use crate::virtual_machine::parser::expression_parser::parse_type_test::parse_type_test;
 use crate::virtual_machine::parser::parser_error::ParserError;
 use crate::virtual_machine::parser::Parser;
 use crate::virtual_machine::token::token_type::TokenType;
@@ -25,6 +28,8 @@ use parse_binary::parse_binary;
 /// - 二項演算式: `1 + 2`
 /// - 関数呼び出し: `f()`
 /// - 変数参照: `x`
+/// - 型検査: `x is int`
+/// - 条件分岐: `if x is int { x } else { 0 }`
 pub fn parse_expression(parser: &mut Parser) -> Result<ExpressionNode, ParserError> {
     let expr: ExpressionNode = parse_binary(parser)?;
 
@@ -33,6 +38,9 @@ pub fn parse_expression(parser: &mut Parser) -> Result<ExpressionNode, ParserErr
         // ExpressionNode::TypeCastNode { from_type, to_type, expression } に変換する
         // ex: `Expression as from_type -> to_type;`
         TokenType::As => parse_type_cast(parser, expr),
+        // 型検査の対象となっている場合は、ExpressionNode::TypeTest に変換する
+        // ex: `Expression is type;`
+        TokenType::Is => parse_type_test(parser, expr),
         // それ以外はそのまま返す
         _ => Ok(expr),
     }
@@ -1,4 +1,6 @@
-use crate::virtual_machine::ast::{ExpressionNode, FunctionCallNode, VariableCallNode};
+use crate::virtual_machine::ast::{
+    ArgumentNode, ExpressionNode, FunctionCallNode, VariableCallNode,
+};
 use crate::virtual_machine::parser::expression_parser::parse_expression;
 use crate::virtual_machine::parser::parser_error::ParserError;
 use crate::virtual_machine::parser::Parser;
@@ -13,16 +15,24 @@ use crate::virtual_machine::token::token_type::TokenType;
 ///
 /// # Syntax
 /// 関数呼び出しは、expressionの一種で、以下のように定義される
-/// - 関数呼び出し: `name ( [arguments] )` where `arguments` is `name: expression [, name: expression]*`
+/// - 関数呼び出し: `name ( [arguments] )` where `arguments` is `argument [, argument]*`
+/// - 引数: `[name:] expression` または `[name:] ...expression` (リストの展開)
+///
+/// 引数名を省略した位置引数は、名前付き引数よりも前に置く必要がある
 ///
 /// 変数参照は、expressionの一種で、以下のように定義される
 /// - 変数参照: `name`
 ///
+/// importしたモジュールの関数や変数は、モジュールの別名で修飾して参照する
+/// - 修飾された名前: `alias.name`
+///
 /// # Example
-/// - 関数呼び出し: `f()`, `f(x: 0)`, `f(x: 0, y: "shunsock")`
+/// - 関数呼び出し: `f()`, `f(x: 0)`, `f(x: 0, y: "shunsock")`, `f(parts: ...parts)`, `f(0, y: "shunsock")`
 /// - 変数参照: `x`
+/// - モジュールの関数呼び出し: `strings.pad(value: "a")`
+/// - 関数の部分適用: `add(x: 1, ...)`
 pub fn parse_identifier_or_call(parser: &mut Parser) -> Result<ExpressionNode, ParserError> {
-    let name: String = match parser.peek().token_type.clone() {
+    let mut name: String = match parser.peek().token_type.clone() {
         TokenType::Identifier(name) => name,
         _ => {
             return Err(ParserError::UnexpectedTokenType {
@@ -38,6 +48,25 @@ pub fn parse_identifier_or_call(parser: &mut Parser) -> Result<ExpressionNode, P
     // Variable Declaration: let x: int = 0; のx
     parser.advance();
 
+    // モジュールの別名に "." が続く場合は、別名で修飾された名前
+    // strings.pad
+    // それ以外の "." はメソッド呼び出しとして parse_primary でパースする
+    if parser.module_aliases.contains(&name) && parser.check(TokenType::Dot) {
+        parser.advance();
+        match parser.peek().token_type.clone() {
+            TokenType::Identifier(member) => name = format!("{}.{}", name, member),
+            token_type => {
+                return Err(ParserError::MismatchedToken {
+                    expected: TokenType::Identifier("member name".to_string()),
+                    found: token_type,
+                    line: parser.peek().line,
+                    char_pos: parser.peek().char_pos,
+                })
+            }
+        }
+        parser.advance();
+    }
+
     match parser.peek().token_type {
         // 次のトークンが左括弧なら関数呼び出し
         TokenType::LeftParen => parse_call_of_function(name.clone(), parser),
@@ -46,7 +75,7 @@ pub fn parse_identifier_or_call(parser: &mut Parser) -> Result<ExpressionNode, P
     }
 }
 
-fn parse_call_of_function(
+pub(crate) fn parse_call_of_function(
     name: String,
     parser: &mut Parser,
 ) -> Result<ExpressionNode, ParserError> {
@@ -64,26 +93,65 @@ fn parse_call_of_function(
     }
 
     // 引数がある場合の処理
-    let mut args: Vec<(String, ExpressionNode)> = Vec::new();
+    let mut args: Vec<ArgumentNode> = Vec::new();
+    let mut is_partial: bool = false;
     loop {
-        let argument_name = match parser.peek().token_type.clone() {
-            TokenType::Identifier(name) => name,
+        // 引数リストの最後の "..." は部分適用
+        // f(x: 0, ...)
+        if parser.check(TokenType::Ellipsis)
+            && parser.peek_next().token_type == TokenType::RightParen
+        {
+            parser.advance();
+            is_partial = true;
+            break;
+        }
+
+        // 識別子の次が ":" なら名前付き引数、そうでなければ位置引数
+        // f(x: 0 <- 名前付き引数
+        // f(0    <- 位置引数
+        let argument_name: Option<String> = match (
+            parser.peek().token_type.clone(),
+            parser.peek_next().token_type.clone(),
+        ) {
+            (TokenType::Identifier(name), TokenType::Colon) => {
+                // 引数名と":"を読み飛ばす
+                parser.advance();
+                parser.advance();
+                Some(name)
+            }
             _ => {
-                return Err(ParserError::UnexpectedTokenType {
-                    token: parser.peek().token_type.clone(),
-                    line: parser.peek().line,
-                    char_pos: parser.peek().char_pos,
-                })
+                // 位置引数は名前付き引数よりも前に置く必要がある
+                // f(x: 0, 1) <- エラー
+                if args.iter().any(|arg| arg.name.is_some()) {
+                    return Err(ParserError::PositionalArgumentAfterNamedArgument {
+                        line: parser.peek().line,
+                        char_pos: parser.peek().char_pos,
+                    });
+                }
+                // 名前付き引数のみを許可している場合は位置引数を受け付けない
+                if parser.named_only {
+                    return Err(ParserError::PositionalArgumentNotAllowed {
+                        function_name: name.clone(),
+                        line: parser.peek().line,
+                        char_pos: parser.peek().char_pos,
+                    });
+                }
+                None
             }
         };
-        // 引数名を読み飛ばす
-        parser.advance();
 
-        // ":"を読み飛ばす
-        parser.check_advance(TokenType::Colon)?;
+        // "..."があればリストを展開して渡す
+        let is_spread: bool = parser.check(TokenType::Ellipsis);
+        if is_spread {
+            parser.advance();
+        }
 
         // 引数を読み込む
-        args.push((argument_name, parse_expression(parser)?));
+        args.push(ArgumentNode {
+            name: argument_name,
+            value: parse_expression(parser)?,
+            is_spread,
+        });
 
         // 次が ")" なら処理終了
         if parser.check(TokenType::RightParen) {
@@ -99,10 +167,14 @@ fn parse_call_of_function(
     // 右括弧があることを確認して読み飛ばす
     parser.check_advance(TokenType::RightParen)?;
 
-    Ok(ExpressionNode::CallOfFunction(Box::new(FunctionCallNode {
+    let node: Box<FunctionCallNode> = Box::new(FunctionCallNode {
         name: name.clone(),
         arguments: args,
-    })))
+    });
+    match is_partial {
+        true => Ok(ExpressionNode::PartialApplication(node)),
+        false => Ok(ExpressionNode::CallOfFunction(node)),
+    }
 }
 
 fn parse_call_of_variable(name: String) -> Result<ExpressionNode, ParserError> {
@@ -121,6 +193,66 @@ mod tests {
 
     // 正常系テスト
 
+    /// 部分適用のパースが可能か確認するテスト
+    /// f(x: 0, ...);
+    #[test]
+    fn parse_partial_application() {
+        let tokens: Vec<Token> = vec![
+            Token::new(1, 1, TokenType::Identifier("f".to_string())),
+            Token::new(1, 2, TokenType::LeftParen),
+            Token::new(1, 3, TokenType::Identifier("x".to_string())),
+            Token::new(1, 4, TokenType::Colon),
+            Token::new(1, 6, TokenType::IntegerLiteral(0)),
+            Token::new(1, 7, TokenType::Comma),
+            Token::new(1, 9, TokenType::Ellipsis),
+            Token::new(1, 12, TokenType::RightParen),
+            Token::new(1, 13, TokenType::Semicolon),
+        ];
+        let mut parser: Parser = create_parser_with_tokens(tokens);
+
+        let result: Result<ExpressionNode, ParserError> = parse_identifier_or_call(&mut parser);
+        assert_eq!(
+            result,
+            Ok(ExpressionNode::PartialApplication(Box::new(
+                FunctionCallNode {
+                    name: "f".to_string(),
+                    arguments: vec![ArgumentNode {
+                        name: Some("x".to_string()),
+                        value: ExpressionNode::Literal(Box::new(LiteralNode {
+                            value: LiteralValue::Integer(0),
+                        })),
+                        is_spread: false,
+                    }],
+                }
+            )))
+        );
+    }
+
+    /// モジュールの別名で修飾された関数呼び出しのパースが可能か確認するテスト
+    /// strings.pad();
+    #[test]
+    fn parse_qualified_function_call() {
+        let tokens: Vec<Token> = vec![
+            Token::new(1, 1, TokenType::Identifier("strings".to_string())),
+            Token::new(1, 8, TokenType::Dot),
+            Token::new(1, 9, TokenType::Identifier("pad".to_string())),
+            Token::new(1, 12, TokenType::LeftParen),
+            Token::new(1, 13, TokenType::RightParen),
+            Token::new(1, 14, TokenType::Semicolon),
+        ];
+        let mut parser: Parser = create_parser_with_tokens(tokens);
+        parser.module_aliases.insert("strings".to_string());
+
+        let result: Result<ExpressionNode, ParserError> = parse_identifier_or_call(&mut parser);
+        assert_eq!(
+            result,
+            Ok(ExpressionNode::CallOfFunction(Box::new(FunctionCallNode {
+                name: "strings.pad".to_string(),
+                arguments: vec![],
+            })))
+        );
+    }
+
     /// 引数が存在しない関数のパースが可能か確認するテスト
     /// f();
     #[test]
@@ -161,12 +293,13 @@ mod tests {
         // 生成されるAST Node
         let expected: Box<FunctionCallNode> = Box::new(FunctionCallNode {
             name: "f".to_string(),
-            arguments: vec![(
-                "v".to_string(),
-                ExpressionNode::Literal(Box::new(LiteralNode {
+            arguments: vec![ArgumentNode {
+                name: Some("v".to_string()),
+                value: ExpressionNode::Literal(Box::new(LiteralNode {
                     value: LiteralValue::Integer(0),
                 })),
-            )],
+                is_spread: false,
+            }],
         });
 
         // テストする関数の入力である、Token列, Parserの生成
@@ -202,18 +335,20 @@ mod tests {
         let expected: Box<FunctionCallNode> = Box::new(FunctionCallNode {
             name: "f".to_string(),
             arguments: vec![
-                (
-                    "x".to_string(),
-                    ExpressionNode::Literal(Box::new(LiteralNode {
+                ArgumentNode {
+                    name: Some("x".to_string()),
+                    value: ExpressionNode::Literal(Box::new(LiteralNode {
                         value: LiteralValue::Integer(0),
                     })),
-                ),
-                (
-                    "y".to_string(),
-                    ExpressionNode::Literal(Box::new(LiteralNode {
+                    is_spread: false,
+                },
+                ArgumentNode {
+                    name: Some("y".to_string()),
+                    value: ExpressionNode::Literal(Box::new(LiteralNode {
                         value: LiteralValue::String("shunsock".to_string()),
                     })),
-                ),
+                    is_spread: false,
+                },
             ],
         });
 
@@ -246,6 +381,100 @@ mod tests {
         assert_eq!(call_of_function, expected);
     }
 
+    /// 展開した引数を持つ関数のパースが可能か確認するテスト
+    /// f(parts: ...parts);
+    #[test]
+    fn parse_function_with_spread_argument() {
+        // 生成されるAST Node
+        let expected: Box<FunctionCallNode> = Box::new(FunctionCallNode {
+            name: "f".to_string(),
+            arguments: vec![ArgumentNode {
+                name: Some("parts".to_string()),
+                value: ExpressionNode::CallOfVariable(Box::new(VariableCallNode {
+                    name: "parts".to_string(),
+                })),
+                is_spread: true,
+            }],
+        });
+
+        // テストする関数の入力である、Token列, Parserの生成
+        // f(parts: ...parts);
+        let tokens: Vec<Token> = vec![
+            Token::new(1, 1, TokenType::Identifier("f".to_string())),
+            Token::new(1, 2, TokenType::LeftParen),
+            Token::new(1, 1, TokenType::Identifier("parts".to_string())),
+            Token::new(1, 2, TokenType::Colon),
+            Token::new(1, 3, TokenType::Ellipsis),
+            Token::new(1, 3, TokenType::Identifier("parts".to_string())),
+            Token::new(1, 3, TokenType::RightParen),
+            Token::new(1, 4, TokenType::Semicolon),
+        ];
+        let mut parser: Parser = create_parser_with_tokens(tokens);
+
+        // テストしたい関数の出力 (エラーが出ていないことを確認)
+        let result: Result<ExpressionNode, ParserError> = parse_identifier_or_call(&mut parser);
+        assert!(result.is_ok());
+
+        // テストしたい関数の出力と期待値を比較
+        let call_of_function: Box<FunctionCallNode> = match result.clone().unwrap() {
+            ExpressionNode::CallOfFunction(call_of_function) => call_of_function,
+            _ => panic!("ExpectedNode::CallOfFunction"),
+        };
+        assert_eq!(call_of_function, expected);
+    }
+
+    /// 位置引数と名前付き引数を持つ関数のパースが可能か確認するテスト
+    /// f(0, y: "shunsock");
+    #[test]
+    fn parse_function_with_positional_and_named_arguments() {
+        // 生成されるAST Node
+        let expected: Box<FunctionCallNode> = Box::new(FunctionCallNode {
+            name: "f".to_string(),
+            arguments: vec![
+                ArgumentNode {
+                    name: None,
+                    value: ExpressionNode::Literal(Box::new(LiteralNode {
+                        value: LiteralValue::Integer(0),
+                    })),
+                    is_spread: false,
+                },
+                ArgumentNode {
+                    name: Some("y".to_string()),
+                    value: ExpressionNode::Literal(Box::new(LiteralNode {
+                        value: LiteralValue::String("shunsock".to_string()),
+                    })),
+                    is_spread: false,
+                },
+            ],
+        });
+
+        // テストする関数の入力である、Token列, Parserの生成
+        // f(0, y: "shunsock");
+        let tokens: Vec<Token> = vec![
+            Token::new(1, 1, TokenType::Identifier("f".to_string())),
+            Token::new(1, 2, TokenType::LeftParen),
+            Token::new(1, 3, TokenType::IntegerLiteral(0)),
+            Token::new(1, 4, TokenType::Comma),
+            Token::new(1, 6, TokenType::Identifier("y".to_string())),
+            Token::new(1, 7, TokenType::Colon),
+            Token::new(1, 9, TokenType::StringLiteral("shunsock".to_string())),
+            Token::new(1, 19, TokenType::RightParen),
+            Token::new(1, 20, TokenType::Semicolon),
+        ];
+        let mut parser: Parser = create_parser_with_tokens(tokens);
+
+        // テストしたい関数の出力 (エラーが出ていないことを確認)
+        let result: Result<ExpressionNode, ParserError> = parse_identifier_or_call(&mut parser);
+        assert!(result.is_ok());
+
+        // テストしたい関数の出力と期待値を比較
+        let call_of_function: Box<FunctionCallNode> = match result.clone().unwrap() {
+            ExpressionNode::CallOfFunction(call_of_function) => call_of_function,
+            _ => panic!("ExpectedNode::CallOfFunction"),
+        };
+        assert_eq!(call_of_function, expected);
+    }
+
     /// 変数呼び出しが可能か確認するテスト
     /// x;
     #[test]
@@ -302,4 +531,61 @@ mod tests {
         let result: Result<ExpressionNode, ParserError> = parse_identifier_or_call(&mut parser);
         assert!(result.is_err());
     }
+
+    /// 名前付き引数の後に位置引数がある時にエラーを出力するか確認するテスト
+    /// f(x: 0, "shunsock");
+    #[test]
+    fn parse_function_with_positional_argument_after_named_argument() {
+        // テストする関数の入力である、Token列, Parserの生成
+        // f(x: 0, "shunsock");
+        let tokens: Vec<Token> = vec![
+            Token::new(1, 1, TokenType::Identifier("f".to_string())),
+            Token::new(1, 2, TokenType::LeftParen),
+            Token::new(1, 3, TokenType::Identifier("x".to_string())),
+            Token::new(1, 4, TokenType::Colon),
+            Token::new(1, 6, TokenType::IntegerLiteral(0)),
+            Token::new(1, 7, TokenType::Comma),
+            Token::new(1, 9, TokenType::StringLiteral("shunsock".to_string())),
+            Token::new(1, 19, TokenType::RightParen),
+            Token::new(1, 20, TokenType::Semicolon),
+        ];
+        let mut parser: Parser = create_parser_with_tokens(tokens);
+
+        // テストしたい関数の出力 (エラーが出ることを確認)
+        let result: Result<ExpressionNode, ParserError> = parse_identifier_or_call(&mut parser);
+        assert_eq!(
+            result,
+            Err(ParserError::PositionalArgumentAfterNamedArgument {
+                line: 1,
+                char_pos: 9
+            })
+        );
+    }
+
+    /// 名前付き引数のみを許可する場合に位置引数がある時にエラーを出力するか確認するテスト
+    /// f(0);
+    #[test]
+    fn parse_function_with_positional_argument_in_named_only_mode() {
+        // テストする関数の入力である、Token列, Parserの生成
+        // f(0);
+        let tokens: Vec<Token> = vec![
+            Token::new(1, 1, TokenType::Identifier("f".to_string())),
+            Token::new(1, 2, TokenType::LeftParen),
+            Token::new(1, 3, TokenType::IntegerLiteral(0)),
+            Token::new(1, 4, TokenType::RightParen),
+            Token::new(1, 5, TokenType::Semicolon),
+        ];
+        let mut parser: Parser = create_parser_with_tokens(tokens).with_named_only();
+
+        // テストしたい関数の出力 (エラーが出ることを確認)
+        let result: Result<ExpressionNode, ParserError> = parse_identifier_or_call(&mut parser);
+        assert_eq!(
+            result,
+            Err(ParserError::PositionalArgumentNotAllowed {
+                function_name: "f".to_string(),
+                line: 1,
+                char_pos: 3
+            })
+        );
+    }
 }
@@ -0,0 +1,130 @@
+use crate::virtual_machine::ast::{ExpressionNode, IfNode};
+use crate::virtual_machine::parser::expression_parser::parse_expression;
+use crate::virtual_machine::parser::parser_error::ParserError;
+use crate::virtual_machine::parser::Parser;
+use crate::virtual_machine::token::token_type::TokenType;
+
+/// 条件分岐表現をパースする
+///
+/// ## Syntax
+/// 条件分岐表現は、表現の一種で、以下の構文を持ちます。
+/// 条件分岐は値を返すため、`else` は省略できません。
+/// - `if condition { expr } else { expr }`
+/// - `if condition { expr } else if condition { expr } else { expr }`
+///
+/// ## Example
+/// - `if x is int { x + 1 } else { 0 }`
+/// - `if flag { "a" } else if other { "b" } else { "c" }`
+pub fn parse_if(parser: &mut Parser) -> Result<ExpressionNode, ParserError> {
+    parser.check_advance(TokenType::If)?;
+    let condition: ExpressionNode = parse_expression(parser)?;
+    let then_branch: ExpressionNode = parse_branch(parser)?;
+
+    parser.check_advance(TokenType::Else)?;
+    // else if は、偽の分岐に条件分岐を入れ子にする
+    let else_branch: ExpressionNode = match parser.peek().token_type {
+        TokenType::If => parse_if(parser)?,
+        _ => parse_branch(parser)?,
+    };
+
+    Ok(ExpressionNode::If(Box::new(IfNode {
+        condition: Box::new(condition),
+        then_branch: Box::new(then_branch),
+        else_branch: Box::new(else_branch),
+    })))
+}
+
+/// `{ expr }` の形の分岐をパースする
+fn parse_branch(parser: &mut Parser) -> Result<ExpressionNode, ParserError> {
+    parser.check_advance(TokenType::LeftBrace)?;
+    let expr: ExpressionNode = parse_expression(parser)?;
+    parser.check_advance(TokenType::RightBrace)?;
+    Ok(expr)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::virtual_machine::ast::{
+        ExpressionNode, IfNode, LiteralNode, LiteralValue, Type, TypeTestNode, VariableCallNode,
+    };
+    use crate::virtual_machine::parser::core::create_parser_with_tokens;
+    use crate::virtual_machine::parser::expression_parser::parse_if::parse_if;
+    use crate::virtual_machine::parser::parser_error::ParserError;
+    use crate::virtual_machine::parser::{Parser, TokenType};
+    use crate::virtual_machine::token::Token;
+
+    fn literal(value: i64) -> ExpressionNode {
+        ExpressionNode::Literal(Box::new(LiteralNode {
+            value: LiteralValue::Integer(value),
+        }))
+    }
+
+    /// else if を含む条件分岐のテスト
+    /// if x is int { 1 } else if true { 2 } else { 3 }
+    #[test]
+    fn parse_if_with_else_if() {
+        let expected = ExpressionNode::If(Box::new(IfNode {
+            condition: Box::new(ExpressionNode::TypeTest(Box::new(TypeTestNode {
+                expression: Box::new(ExpressionNode::CallOfVariable(Box::new(VariableCallNode {
+                    name: "x".to_string(),
+                }))),
+                tested_type: Type::Integer,
+            }))),
+            then_branch: Box::new(literal(1)),
+            else_branch: Box::new(ExpressionNode::If(Box::new(IfNode {
+                condition: Box::new(ExpressionNode::Literal(Box::new(LiteralNode {
+                    value: LiteralValue::Boolean(true),
+                }))),
+                then_branch: Box::new(literal(2)),
+                else_branch: Box::new(literal(3)),
+            }))),
+        }));
+
+        let tokens: Vec<Token> = vec![
+            Token::new(1, 1, TokenType::If),
+            Token::new(1, 4, TokenType::Identifier("x".to_string())),
+            Token::new(1, 6, TokenType::Is),
+            Token::new(1, 9, TokenType::IntType),
+            Token::new(1, 13, TokenType::LeftBrace),
+            Token::new(1, 15, TokenType::IntegerLiteral(1)),
+            Token::new(1, 17, TokenType::RightBrace),
+            Token::new(1, 19, TokenType::Else),
+            Token::new(1, 24, TokenType::If),
+            Token::new(1, 27, TokenType::BooleanLiteral(true)),
+            Token::new(1, 32, TokenType::LeftBrace),
+            Token::new(1, 34, TokenType::IntegerLiteral(2)),
+            Token::new(1, 36, TokenType::RightBrace),
+            Token::new(1, 38, TokenType::Else),
+            Token::new(1, 43, TokenType::LeftBrace),
+            Token::new(1, 45, TokenType::IntegerLiteral(3)),
+            Token::new(1, 47, TokenType::RightBrace),
+        ];
+        let mut parser: Parser = create_parser_with_tokens(tokens);
+
+        assert_eq!(parse_if(&mut parser), Ok(expected));
+    }
+
+    /// else のない条件分岐はエラーとなることを確認するテスト
+    /// if true { 1 }
+    #[test]
+    fn fail_parse_if_without_else() {
+        let tokens: Vec<Token> = vec![
+            Token::new(1, 1, TokenType::If),
+            Token::new(1, 4, TokenType::BooleanLiteral(true)),
+            Token::new(1, 9, TokenType::LeftBrace),
+            Token::new(1, 11, TokenType::IntegerLiteral(1)),
+            Token::new(1, 13, TokenType::RightBrace),
+        ];
+        let mut parser: Parser = create_parser_with_tokens(tokens);
+
+        assert_eq!(
+            parse_if(&mut parser),
+            Err(ParserError::MismatchedToken {
+                expected: TokenType::Else,
+                found: TokenType::Eof,
+                line: 1,
+                char_pos: 1,
+            })
+        );
+    }
+}
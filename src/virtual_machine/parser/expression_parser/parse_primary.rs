@@ -1,7 +1,11 @@
-use crate::virtual_machine::ast::{ExpressionNode, LiteralNode, LiteralValue};
-use crate::virtual_machine::parser::expression_parser::parse_call_of_variable_and_function::parse_identifier_or_call;
+use crate::virtual_machine::ast::{ExpressionNode, LiteralNode, LiteralValue, MethodCallNode};
+use crate::virtual_machine::parser::expression_parser::parse_call_of_variable_and_function::{
+    parse_call_of_function, parse_identifier_or_call,
+};
+use crate::virtual_machine::parser::expression_parser::parse_if::parse_if;
 use crate::virtual_machine::parser::expression_parser::parse_parenthesized::parse_parenthesized;
 use crate::virtual_machine::parser::expression_parser::parse_type_cast::parse_type_cast;
+use crate::virtual_machine::parser::expression_parser::parse_type_test::parse_type_test;
 use crate::virtual_machine::parser::parser_error::ParserError;
 use crate::virtual_machine::parser::Parser;
 use crate::virtual_machine::token::token_type::TokenType;
@@ -14,16 +18,19 @@ use crate::virtual_machine::token::token_type::TokenType;
 ///  - `ParserError` - パースエラー
 ///
 /// # Syntax
-/// `integer_literal | float_literal | string_literal | none_literal | variable_call | function_call | parenthesized`
+/// `(integer_literal | float_literal | string_literal | boolean_literal | none_literal | variable_call | function_call | parenthesized | if) { "." method_call }`
 ///
 /// # Example
 /// - `42`
 /// - `3.14`
 /// - `"Hello"`
+/// - `true`
 /// - `None`
 /// - `a`
 /// - `add(1, 2)`
 /// - `(1 + 2)`
+/// - `if flag { 1 } else { 2 }`
+/// - `h.url()`
 pub fn parse_primary(parser: &mut Parser) -> Result<ExpressionNode, ParserError> {
     let expr = match parser.peek().token_type.clone() {
         TokenType::LeftParen => {
@@ -51,6 +58,13 @@ pub fn parse_primary(parser: &mut Parser) -> Result<ExpressionNode, ParserError>
                 value: LiteralValue::String(value.clone()),
             }))
         }
+        // 真偽値リテラル
+        TokenType::BooleanLiteral(value) => {
+            parser.advance();
+            ExpressionNode::Literal(Box::new(LiteralNode {
+                value: LiteralValue::Boolean(value),
+            }))
+        }
         // Noneリテラル
         TokenType::NoneLiteral => {
             parser.advance();
@@ -63,6 +77,8 @@ pub fn parse_primary(parser: &mut Parser) -> Result<ExpressionNode, ParserError>
             // 識別子または関数呼び出しのパース
             parse_identifier_or_call(parser)?
         }
+        // 条件分岐
+        TokenType::If => parse_if(parser)?,
         // 他のリテラルが必要な場合に追加
         _ => {
             return Err(ParserError::UnexpectedTokenType {
@@ -73,17 +89,73 @@ pub fn parse_primary(parser: &mut Parser) -> Result<ExpressionNode, ParserError>
         }
     };
 
+    let expr: ExpressionNode = parse_method_calls(parser, expr)?;
+
     match parser.peek().token_type.clone() {
         TokenType::As => parse_type_cast(parser, expr),
+        TokenType::Is => parse_type_test(parser, expr),
         _ => Ok(expr),
     }
 }
 
+/// 式の後ろに続くメソッド呼び出しをパースする
+///
+/// `h.url().len()` のように、メソッド呼び出しは続けて書くことができます。
+/// メソッドの部分適用はできません。
+fn parse_method_calls(
+    parser: &mut Parser,
+    receiver: ExpressionNode,
+) -> Result<ExpressionNode, ParserError> {
+    let mut expr: ExpressionNode = receiver;
+    while parser.check(TokenType::Dot) {
+        parser.advance();
+        let name: String = match parser.peek().token_type.clone() {
+            TokenType::Identifier(name) => name,
+            token_type => {
+                return Err(ParserError::MismatchedToken {
+                    expected: TokenType::Identifier("method name".to_string()),
+                    found: token_type,
+                    line: parser.peek().line,
+                    char_pos: parser.peek().char_pos,
+                })
+            }
+        };
+        parser.advance();
+        if !parser.check(TokenType::LeftParen) {
+            return Err(ParserError::MismatchedToken {
+                expected: TokenType::LeftParen,
+                found: parser.peek().token_type.clone(),
+                line: parser.peek().line,
+                char_pos: parser.peek().char_pos,
+            });
+        }
+        let line: usize = parser.peek().line;
+        let char_pos: usize = parser.peek().char_pos;
+        let call = match parse_call_of_function(name, parser)? {
+            ExpressionNode::CallOfFunction(call) => call,
+            // h.url(scheme: "https", ...)  # メソッドは部分適用できない
+            _ => {
+                return Err(ParserError::UnexpectedTokenType {
+                    token: TokenType::Ellipsis,
+                    line,
+                    char_pos,
+                })
+            }
+        };
+        expr = ExpressionNode::CallOfMethod(Box::new(MethodCallNode {
+            receiver: Box::new(expr),
+            name: call.name,
+            arguments: call.arguments,
+        }));
+    }
+    Ok(expr)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::virtual_machine::ast::{
-        ExpressionNode, FunctionCallNode, LiteralValue, VariableCallNode,
+        ArgumentNode, ExpressionNode, FunctionCallNode, LiteralValue, VariableCallNode,
     };
     use crate::virtual_machine::parser::core::create_parser_with_tokens;
     use crate::virtual_machine::parser::Parser;
@@ -118,6 +190,7 @@ mod tests {
     /// 浮動小数点リテラルをパース可能か確認するテスト
     /// 3.14;
     #[test]
+    #[allow(clippy::approx_constant)]
     fn test_parse_float_literal() {
         // 生成されるAST Node
         let expected = Box::new(LiteralNode {
@@ -231,18 +304,20 @@ mod tests {
         let expected = Box::new(FunctionCallNode {
             name: "add".to_string(),
             arguments: vec![
-                (
-                    "left".to_string(),
-                    ExpressionNode::Literal(Box::new(LiteralNode {
+                ArgumentNode {
+                    name: Some("left".to_string()),
+                    value: ExpressionNode::Literal(Box::new(LiteralNode {
                         value: LiteralValue::Integer(1),
                     })),
-                ),
-                (
-                    "right".to_string(),
-                    ExpressionNode::Literal(Box::new(LiteralNode {
+                    is_spread: false,
+                },
+                ArgumentNode {
+                    name: Some("right".to_string()),
+                    value: ExpressionNode::Literal(Box::new(LiteralNode {
                         value: LiteralValue::Integer(2),
                     })),
-                ),
+                    is_spread: false,
+                },
             ],
         });
 
@@ -304,6 +379,47 @@ mod tests {
         assert_eq!(actual, expected);
     }
 
+    /// 続けて書かれたメソッド呼び出しをパース可能か確認するテスト
+    /// h.url(secure: true).len();
+    #[test]
+    fn test_parse_chained_method_calls() {
+        let expected = ExpressionNode::CallOfMethod(Box::new(MethodCallNode {
+            receiver: Box::new(ExpressionNode::CallOfMethod(Box::new(MethodCallNode {
+                receiver: Box::new(ExpressionNode::CallOfVariable(Box::new(VariableCallNode {
+                    name: "h".to_string(),
+                }))),
+                name: "url".to_string(),
+                arguments: vec![ArgumentNode {
+                    name: Some("secure".to_string()),
+                    value: ExpressionNode::Literal(Box::new(LiteralNode {
+                        value: LiteralValue::Boolean(true),
+                    })),
+                    is_spread: false,
+                }],
+            }))),
+            name: "len".to_string(),
+            arguments: vec![],
+        }));
+
+        let tokens: Vec<Token> = vec![
+            Token::new(1, 1, TokenType::Identifier("h".to_string())),
+            Token::new(1, 2, TokenType::Dot),
+            Token::new(1, 3, TokenType::Identifier("url".to_string())),
+            Token::new(1, 6, TokenType::LeftParen),
+            Token::new(1, 7, TokenType::Identifier("secure".to_string())),
+            Token::new(1, 13, TokenType::Colon),
+            Token::new(1, 15, TokenType::BooleanLiteral(true)),
+            Token::new(1, 19, TokenType::RightParen),
+            Token::new(1, 20, TokenType::Dot),
+            Token::new(1, 21, TokenType::Identifier("len".to_string())),
+            Token::new(1, 24, TokenType::LeftParen),
+            Token::new(1, 25, TokenType::RightParen),
+        ];
+        let mut parser: Parser = create_parser_with_tokens(tokens);
+
+        assert_eq!(parse_primary(&mut parser), Ok(expected));
+    }
+
     /// 予期しないトークンが出現した際にエラーを返すか確認するテスト
     /// +;
     #[test]
@@ -1,5 +1,5 @@
 use crate::virtual_machine::ast::{ExpressionNode, Type, TypeCastNode};
-use crate::virtual_machine::parser::core::get_type_from_current_token;
+use crate::virtual_machine::parser::core::parse_type;
 use crate::virtual_machine::parser::parser_error::ParserError;
 use crate::virtual_machine::parser::Parser;
 use crate::virtual_machine::token::token_type::TokenType;
@@ -9,10 +9,14 @@ use crate::virtual_machine::token::token_type::TokenType;
 /// ## Syntax
 /// 型キャスト表現は、表現の一種で、以下の構文を持ちます。
 /// - `expr as from_type -> to_type`
+/// - `expr as to_type` (キャスト元の型は値から決まる)
+/// - `expr as? to_type` (変換に失敗した場合は none を返す)
 ///
 /// ## Example
 /// - Literalの型キャストをする `1 as int -> string;`
 /// - BinaryExpressionの結果を型キャストする `1 + 0 as int -> float;`
+/// - キャスト元の型を省略する `1 as float;`
+/// - 失敗しうる型キャストをする `"a" as? int;`
 pub fn parse_type_cast(
     parser: &mut Parser,
     expr: ExpressionNode,
@@ -20,21 +24,29 @@ pub fn parse_type_cast(
     // asを確認して読み飛ばす
     parser.check_advance(TokenType::As)?;
 
-    // 型を読み取る
-    let from_type: Type = get_type_from_current_token(parser)?;
-    parser.advance();
-
-    // -> を確認して読み飛ばす
-    parser.check_advance(TokenType::TypeCastArrow)?;
+    // as? であれば失敗しうるキャスト
+    let is_checked: bool = parser.peek().token_type == TokenType::Question;
+    if is_checked {
+        parser.advance();
+    }
 
     // 型を読み取る
-    let to_type: Type = get_type_from_current_token(parser)?;
-    parser.advance();
+    let first_type: Type = parse_type(parser)?;
+
+    // -> が続く場合は、最初の型がキャスト元の型
+    let (from_type, to_type): (Option<Type>, Type) =
+        if parser.peek().token_type == TokenType::TypeCastArrow {
+            parser.advance();
+            (Some(first_type), parse_type(parser)?)
+        } else {
+            (None, first_type)
+        };
 
     // 式を返す
     Ok(ExpressionNode::TypeCast(Box::new(TypeCastNode {
         from_type,
         to_type,
+        is_checked,
         expression: Box::new(expr),
     })))
 }
@@ -57,8 +69,9 @@ mod tests {
     fn parse_cast_from_literal() {
         // 期待される出力
         let expected = ExpressionNode::TypeCast(Box::new(TypeCastNode {
-            from_type: Type::Integer,
+            from_type: Some(Type::Integer),
             to_type: Type::String,
+            is_checked: false,
             expression: Box::new(ExpressionNode::Literal(Box::new(LiteralNode {
                 value: LiteralValue::Integer(1),
             }))),
@@ -86,22 +99,89 @@ mod tests {
         assert_eq!(result.unwrap(), expected);
     }
 
-    // 異常系
+    /// キャスト元の型を省略したTypeCastのテスト
+    /// 1 as float;
+    #[test]
+    fn parse_cast_without_from_type() {
+        // 期待される出力
+        let expected = ExpressionNode::TypeCast(Box::new(TypeCastNode {
+            from_type: None,
+            to_type: Type::Float,
+            is_checked: false,
+            expression: Box::new(ExpressionNode::Literal(Box::new(LiteralNode {
+                value: LiteralValue::Integer(1),
+            }))),
+        }));
 
-    /// asがない場合のテスト
-    /// 1 type -> type # error
+        // テストしたい関数の入力
+        let tokens = vec![
+            Token::new(1, 1, TokenType::As),
+            Token::new(1, 1, TokenType::FloatType),
+            Token::new(1, 1, TokenType::Semicolon),
+        ];
+        let mut parser: Parser = create_parser_with_tokens(tokens);
+
+        // テストしたい関数の出力 (エラーが出ていないことを確認)
+        let result: Result<ExpressionNode, ParserError> = parse_type_cast(
+            &mut parser,
+            ExpressionNode::Literal(Box::new(LiteralNode {
+                value: LiteralValue::Integer(1),
+            })),
+        );
+
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), expected);
+    }
+
+    /// 失敗しうるTypeCastのテスト
+    /// "a" as? int;
     #[test]
-    fn fail_as_is_missing() {
+    fn parse_checked_cast() {
+        // 期待される出力
+        let expected = ExpressionNode::TypeCast(Box::new(TypeCastNode {
+            from_type: None,
+            to_type: Type::Integer,
+            is_checked: true,
+            expression: Box::new(ExpressionNode::Literal(Box::new(LiteralNode {
+                value: LiteralValue::String("a".to_string()),
+            }))),
+        }));
+
+        // テストしたい関数の入力
+        let tokens = vec![
+            Token::new(1, 1, TokenType::As),
+            Token::new(1, 1, TokenType::Question),
+            Token::new(1, 1, TokenType::IntType),
+            Token::new(1, 1, TokenType::Semicolon),
+        ];
+        let mut parser: Parser = create_parser_with_tokens(tokens);
+
+        // テストしたい関数の出力 (エラーが出ていないことを確認)
+        let result: Result<ExpressionNode, ParserError> = parse_type_cast(
+            &mut parser,
+            ExpressionNode::Literal(Box::new(LiteralNode {
+                value: LiteralValue::String("a".to_string()),
+            })),
+        );
+
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), expected);
+    }
+
+    /// キャスト元の型を省略した場合、-> の後に型が続かないことを確認するテスト
+    /// 1 as int string;  # `as int` までが型キャスト
+    #[test]
+    fn parse_cast_stops_before_second_type_without_arrow() {
         // テストしたい関数の入力
         let tokens: Vec<Token> = vec![
+            Token::new(1, 1, TokenType::As),
             Token::new(1, 1, TokenType::IntType),
-            Token::new(1, 1, TokenType::TypeCastArrow),
             Token::new(1, 1, TokenType::StringType),
             Token::new(1, 1, TokenType::Semicolon),
         ];
         let mut parser: Parser = create_parser_with_tokens(tokens);
 
-        // テストしたい関数の出力 (エラーが出ていることを確認)
+        // テストしたい関数の出力 (エラーが出ていないことを確認)
         let result: Result<ExpressionNode, ParserError> = parse_type_cast(
             &mut parser,
             ExpressionNode::Literal(Box::new(LiteralNode {
@@ -109,16 +189,20 @@ mod tests {
             })),
         );
 
-        assert!(result.is_err());
+        // string は型キャストの一部として読まれない
+        assert!(result.is_ok());
+        assert_eq!(parser.peek().token_type, TokenType::StringType);
     }
 
-    /// from_typeがない場合のテスト
-    /// 1 as -> type # error
+    // 異常系
+
+    /// asがない場合のテスト
+    /// 1 type -> type # error
     #[test]
-    fn fail_from_type_is_missing() {
+    fn fail_as_is_missing() {
         // テストしたい関数の入力
         let tokens: Vec<Token> = vec![
-            Token::new(1, 1, TokenType::As),
+            Token::new(1, 1, TokenType::IntType),
             Token::new(1, 1, TokenType::TypeCastArrow),
             Token::new(1, 1, TokenType::StringType),
             Token::new(1, 1, TokenType::Semicolon),
@@ -136,14 +220,14 @@ mod tests {
         assert!(result.is_err());
     }
 
-    /// -> がない場合のテスト
-    /// 1 as int type # error
+    /// from_typeがない場合のテスト
+    /// 1 as -> type # error
     #[test]
-    fn fail_arrow_is_missing() {
+    fn fail_from_type_is_missing() {
         // テストしたい関数の入力
         let tokens: Vec<Token> = vec![
             Token::new(1, 1, TokenType::As),
-            Token::new(1, 1, TokenType::IntType),
+            Token::new(1, 1, TokenType::TypeCastArrow),
             Token::new(1, 1, TokenType::StringType),
             Token::new(1, 1, TokenType::Semicolon),
         ];
@@ -0,0 +1,63 @@
+use crate::virtual_machine::ast::{ExpressionNode, Type, TypeTestNode};
+use crate::virtual_machine::parser::core::parse_type;
+use crate::virtual_machine::parser::parser_error::ParserError;
+use crate::virtual_machine::parser::Parser;
+use crate::virtual_machine::token::token_type::TokenType;
+
+/// 型検査表現をパースする
+///
+/// ## Syntax
+/// 型検査表現は、表現の一種で、以下の構文を持ちます。
+/// - `expr is type`
+///
+/// ## Example
+/// - 変数の型を検査する `x is int;`
+/// - Union型で検査する `x is int | float;`
+pub fn parse_type_test(
+    parser: &mut Parser,
+    expr: ExpressionNode,
+) -> Result<ExpressionNode, ParserError> {
+    // isを確認して読み飛ばす
+    parser.check_advance(TokenType::Is)?;
+
+    // 型を読み取る
+    let tested_type: Type = parse_type(parser)?;
+
+    Ok(ExpressionNode::TypeTest(Box::new(TypeTestNode {
+        expression: Box::new(expr),
+        tested_type,
+    })))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::virtual_machine::ast::{ExpressionNode, Type, TypeTestNode, VariableCallNode};
+    use crate::virtual_machine::parser::core::create_parser_with_tokens;
+    use crate::virtual_machine::parser::expression_parser::parse_type_test::parse_type_test;
+    use crate::virtual_machine::parser::{Parser, TokenType};
+    use crate::virtual_machine::token::Token;
+
+    /// Union型による型検査のテスト
+    /// x is int | string?;
+    #[test]
+    fn parse_type_test_with_union_type() {
+        let variable = ExpressionNode::CallOfVariable(Box::new(VariableCallNode {
+            name: "x".to_string(),
+        }));
+        let expected = ExpressionNode::TypeTest(Box::new(TypeTestNode {
+            expression: Box::new(variable.clone()),
+            tested_type: Type::Union(vec![Type::Integer, Type::Optional(Box::new(Type::String))]),
+        }));
+
+        let tokens: Vec<Token> = vec![
+            Token::new(1, 3, TokenType::Is),
+            Token::new(1, 6, TokenType::IntType),
+            Token::new(1, 10, TokenType::Pipe),
+            Token::new(1, 12, TokenType::StringType),
+            Token::new(1, 18, TokenType::Question),
+        ];
+        let mut parser: Parser = create_parser_with_tokens(tokens);
+
+        assert_eq!(parse_type_test(&mut parser, variable), Ok(expected));
+    }
+}
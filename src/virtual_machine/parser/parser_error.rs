@@ -16,6 +16,32 @@ pub enum ParserError {
         line: usize,
         char_pos: usize,
     },
+    #[error("Type {name} is already declared at line {line}, position {char_pos}")]
+    DuplicateType {
+        name: String,
+        line: usize,
+        char_pos: usize,
+    },
+    #[error(
+        "Method {method} must take self as its first parameter at line {line}, position {char_pos}"
+    )]
+    MissingSelfParameter {
+        method: String,
+        line: usize,
+        char_pos: usize,
+    },
+    #[error("Parameter type is required for {name} at line {line}, position {char_pos}")]
+    MissingParameterType {
+        name: String,
+        line: usize,
+        char_pos: usize,
+    },
+    #[error("{found:?} is allowed only at the top level at line {line}, position {char_pos}")]
+    NotAtTopLevel {
+        found: TokenType,
+        line: usize,
+        char_pos: usize,
+    },
     #[error("could not found {found:?} type in Shot at line {line}, position {char_pos}")]
     TypeNotFound {
         found: TokenType,
@@ -28,4 +54,14 @@ pub enum ParserError {
         line: usize,
         char_pos: usize,
     },
+    #[error(
+        "Positional argument cannot follow named arguments at line {line}, position {char_pos}"
+    )]
+    PositionalArgumentAfterNamedArgument { line: usize, char_pos: usize },
+    #[error("Positional argument is not allowed in named-only mode (function {function_name}) at line {line}, position {char_pos}")]
+    PositionalArgumentNotAllowed {
+        function_name: String,
+        line: usize,
+        char_pos: usize,
+    },
 }
@@ -1,5 +1,11 @@
-use crate::virtual_machine::ast::{ExpressionNode, Statement};
+use crate::virtual_machine::ast::{
+    BlockNode, ExpressionNode, ImportNode, NewtypeDeclarationNode, Statement, Type,
+};
+use crate::virtual_machine::parser::core::parse_type;
 use crate::virtual_machine::parser::declaration_parser::parse_declaration;
+use crate::virtual_machine::parser::declaration_parser::parse_declaration_of_constant::parse_declaration_of_constant;
+use crate::virtual_machine::parser::declaration_parser::parse_declaration_of_interface::parse_declaration_of_interface;
+use crate::virtual_machine::parser::declaration_parser::parse_impl::parse_impl;
 use crate::virtual_machine::parser::expression_parser::parse_expression;
 use crate::virtual_machine::parser::Parser;
 use crate::virtual_machine::parser::ParserError;
@@ -13,18 +19,42 @@ use crate::virtual_machine::token::token_type::TokenType;
 ///    - 失敗: ParserError
 ///
 /// # Syntax
-/// 文は、一単位です。let文やreturn文、式文などが含まれます。
+/// 文は、一単位です。let文やconst文、return文、式文などが含まれます。
 ///
 /// ## Example
 /// - let文: `let x: int = 0;`
+/// - const文: `const X: int = 0;`
+/// - ブロック: `{ let x: int = 0; }`
 /// - return文: `return 0;`
 /// - expression文: `0;`
+///
+/// import文とexport文、newtype文、interface文とimpl文は、ファイルのトップレベルにのみ書けます。
+/// 関数の本体やブロックの中では `ParserError::NotAtTopLevel` となります。
+/// トップレベルの文は `parse_top_level_statement` でパースします。
 pub fn parse_statement(parser: &mut Parser) -> Result<Statement, ParserError> {
     match parser.peek().token_type.clone() {
         TokenType::Let => {
             // let文のパース
             parse_let_statement(parser)
         }
+        TokenType::Const => {
+            // const文のパース
+            parse_const_statement(parser)
+        }
+        // let f: fn = (): int { impl int { ... } return 0; };  # Error
+        TokenType::Import
+        | TokenType::Export
+        | TokenType::Newtype
+        | TokenType::Interface
+        | TokenType::Impl => Err(ParserError::NotAtTopLevel {
+            found: parser.peek().token_type.clone(),
+            line: parser.peek().line,
+            char_pos: parser.peek().char_pos,
+        }),
+        TokenType::LeftBrace => {
+            // ブロックのパース
+            parse_block_statement(parser)
+        }
         TokenType::Return => {
             // return文のパース
             parse_return_statement(parser)
@@ -36,6 +66,48 @@ pub fn parse_statement(parser: &mut Parser) -> Result<Statement, ParserError> {
     }
 }
 
+/// トップレベルの文のパース
+///
+/// # Returns
+/// - `Result<Statement, ParserError>`:
+///    - 成功: Statement (ASTの一単位)
+///    - 失敗: ParserError
+///
+/// # Syntax
+/// トップレベルには、`parse_statement` でパースできる文に加えて、次の文を書けます。
+///
+/// ## Example
+/// - import文: `import "lib/strings.shot" as strings;`
+/// - export文: `export let x: int = 0;`
+/// - newtype文: `newtype UserId = int;`
+/// - interface文: `interface Named { let name: fn = (self): string; }`
+/// - impl文: `impl UserId { let name: fn = (self): string { return "user"; }; }`
+pub fn parse_top_level_statement(parser: &mut Parser) -> Result<Statement, ParserError> {
+    match parser.peek().token_type.clone() {
+        TokenType::Import => {
+            // import文のパース
+            parse_import_statement(parser)
+        }
+        TokenType::Export => {
+            // export文のパース
+            parse_export_statement(parser)
+        }
+        TokenType::Newtype => {
+            // newtype文のパース
+            parse_newtype_statement(parser)
+        }
+        TokenType::Interface => {
+            // interface文のパース
+            parse_declaration_of_interface(parser)
+        }
+        TokenType::Impl => {
+            // impl文のパース
+            parse_impl(parser)
+        }
+        _ => parse_statement(parser),
+    }
+}
+
 /// 宣言文のパース
 ///
 /// # Returns
@@ -62,6 +134,229 @@ fn parse_let_statement(parser: &mut Parser) -> Result<Statement, ParserError> {
     Ok(statement)
 }
 
+/// 定数宣言文のパース
+///
+/// # Returns
+/// - `Result<Statement, ParserError>`:
+///    - 成功: Statement (ASTの一単位)
+///    - 失敗: ParserError
+///
+/// # Syntax
+/// 定数宣言文は文の一種で、定数を宣言する際に用いる文です。
+///
+/// ## Example
+/// - 定数宣言: `const MAX_RETRIES: int = *Expression Node*;`
+fn parse_const_statement(parser: &mut Parser) -> Result<Statement, ParserError> {
+    // constキーワードを読み飛ばす
+    parser.advance();
+
+    // 定数宣言文をパース
+    let statement: Statement = parse_declaration_of_constant(parser)?;
+
+    // 終端にセミコロンがあることを確認
+    parser.check_advance(TokenType::Semicolon)?;
+
+    Ok(statement)
+}
+
+/// import文のパース
+///
+/// # Returns
+/// - `Result<Statement, ParserError>`:
+///    - 成功: Statement (ASTの一単位)
+///    - 失敗: ParserError
+///
+/// # Syntax
+/// import文は文の一種で、他のファイルのモジュールを別名を付けて読み込む際に用います。
+///
+/// ## Example
+/// - `import "lib/strings.shot" as strings;`
+fn parse_import_statement(parser: &mut Parser) -> Result<Statement, ParserError> {
+    // importキーワードを読み飛ばす
+    parser.advance();
+
+    // モジュールのパスを読み取る
+    let path: String = match parser.peek().token_type.clone() {
+        TokenType::StringLiteral(path) => path,
+        token_type => {
+            return Err(ParserError::MismatchedToken {
+                expected: TokenType::StringLiteral("module path".to_string()),
+                found: token_type,
+                line: parser.peek().line,
+                char_pos: parser.peek().char_pos,
+            })
+        }
+    };
+    parser.advance();
+
+    // asキーワードを読み飛ばす
+    parser.check_advance(TokenType::As)?;
+
+    // 別名を読み取る
+    let alias: String = match parser.peek().token_type.clone() {
+        TokenType::Identifier(alias) => alias,
+        token_type => {
+            return Err(ParserError::MismatchedToken {
+                expected: TokenType::Identifier("module alias".to_string()),
+                found: token_type,
+                line: parser.peek().line,
+                char_pos: parser.peek().char_pos,
+            })
+        }
+    };
+    parser.advance();
+
+    // 終端にセミコロンがあることを確認
+    parser.check_advance(TokenType::Semicolon)?;
+
+    // 別名で修飾された名前は、メソッド呼び出しではなくモジュールの参照としてパースする
+    parser.module_aliases.insert(alias.clone());
+    Ok(Statement::Import(Box::new(ImportNode { path, alias })))
+}
+
+/// export文のパース
+///
+/// # Returns
+/// - `Result<Statement, ParserError>`:
+///    - 成功: Statement (ASTの一単位)
+///    - 失敗: ParserError
+///
+/// # Syntax
+/// export文は、let文またはconst文の前にexportを付けたものです。
+/// exportが付いた宣言は、モジュールをimportしたファイルから参照できます。
+///
+/// ## Example
+/// - `export let pad: fn = (value: string): string { return value; };`
+/// - `export const WIDTH: int = 80;`
+fn parse_export_statement(parser: &mut Parser) -> Result<Statement, ParserError> {
+    // exportキーワードを読み飛ばす
+    parser.advance();
+
+    // exportできるのは宣言のみ
+    let statement: Statement = match parser.peek().token_type.clone() {
+        TokenType::Let => parse_let_statement(parser)?,
+        TokenType::Const => parse_const_statement(parser)?,
+        token_type => {
+            return Err(ParserError::MismatchedToken {
+                expected: TokenType::Let,
+                found: token_type,
+                line: parser.peek().line,
+                char_pos: parser.peek().char_pos,
+            })
+        }
+    };
+
+    Ok(Statement::Export(Box::new(statement)))
+}
+
+/// newtype文のパース
+///
+/// # Returns
+/// - `Result<Statement, ParserError>`:
+///    - 成功: Statement (ASTの一単位)
+///    - 失敗: ParserError
+///
+/// # Syntax
+/// newtype文は文の一種で、既存の型をもとに元の型と区別される新しい型を宣言します。
+/// 宣言した型名は、以降の型注釈で使うことができます。
+///
+/// ## Example
+/// - `newtype UserId = int;`
+fn parse_newtype_statement(parser: &mut Parser) -> Result<Statement, ParserError> {
+    // newtypeキーワードを読み飛ばす
+    parser.advance();
+
+    // 型名を読み取る
+    let name_token = parser.peek().clone();
+    let name: String = match name_token.token_type.clone() {
+        TokenType::Identifier(name) => name,
+        token_type => {
+            return Err(ParserError::MismatchedToken {
+                expected: TokenType::Identifier("type name".to_string()),
+                found: token_type,
+                line: name_token.line,
+                char_pos: name_token.char_pos,
+            })
+        }
+    };
+    if parser.is_declared_type(&name) {
+        return Err(ParserError::DuplicateType {
+            name,
+            line: name_token.line,
+            char_pos: name_token.char_pos,
+        });
+    }
+    parser.advance();
+
+    // = の後ろの元の型を読み取る
+    parser.check_advance(TokenType::Equal)?;
+    let underlying_type: Type = parse_type(parser)?;
+
+    // 終端にセミコロンがあることを確認
+    parser.check_advance(TokenType::Semicolon)?;
+
+    parser
+        .newtypes
+        .insert(name.clone(), underlying_type.clone());
+    Ok(Statement::DeclarationOfNewtype(Box::new(
+        NewtypeDeclarationNode {
+            name,
+            underlying_type,
+        },
+    )))
+}
+
+/// ブロックのパース
+///
+/// # Returns
+/// - `Result<Statement, ParserError>`:
+///    - 成功: Statement (ASTの一単位)
+///    - 失敗: ParserError
+///
+/// # Syntax
+/// ブロックは `{` と `}` で囲まれた文の並びです。閉じ括弧の後にセミコロンは不要です。
+/// return文は関数の本体を終わらせる文なので、ブロックの中には書けません。
+///
+/// ## Example
+/// - `{ let x: int = 0; }`
+/// - `{ let x: int = 0; { let x: string = "inner"; } }`
+fn parse_block_statement(parser: &mut Parser) -> Result<Statement, ParserError> {
+    // 開き括弧を読み飛ばす
+    parser.advance();
+
+    let mut statements: Vec<(usize, Statement)> = vec![];
+    loop {
+        match parser.peek().token_type.clone() {
+            TokenType::RightBrace => break,
+            TokenType::Eof => {
+                return Err(ParserError::UnexpectedEof {
+                    expected: TokenType::RightBrace,
+                    line: parser.peek().line,
+                    char_pos: parser.peek().char_pos,
+                })
+            }
+            // { return 0; }  # Error
+            TokenType::Return => {
+                return Err(ParserError::MismatchedToken {
+                    expected: TokenType::RightBrace,
+                    found: TokenType::Return,
+                    line: parser.peek().line,
+                    char_pos: parser.peek().char_pos,
+                })
+            }
+            _ => {
+                let line: usize = parser.peek().line;
+                statements.push((line, parse_statement(parser)?));
+            }
+        }
+    }
+
+    // 閉じ括弧を読み飛ばす
+    parser.check_advance(TokenType::RightBrace)?;
+
+    Ok(Statement::Block(Box::new(BlockNode { statements })))
+}
+
 /// リターン文のパース
 ///
 /// # Returns
@@ -108,3 +403,331 @@ fn parse_expression_statement(parser: &mut Parser) -> Result<Statement, ParserEr
 
     Ok(Statement::Expression(expr))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::virtual_machine::ast::{LiteralNode, LiteralValue, Type, VariableDeclarationNode};
+    use crate::virtual_machine::parser::core::create_parser_with_tokens;
+    use crate::virtual_machine::token::Token;
+
+    /// import文のパースが可能か確認するテスト
+    /// import "lib/strings.shot" as strings;
+    #[test]
+    fn parse_import() {
+        let tokens: Vec<Token> = vec![
+            Token::new(1, 1, TokenType::Import),
+            Token::new(
+                1,
+                8,
+                TokenType::StringLiteral("lib/strings.shot".to_string()),
+            ),
+            Token::new(1, 27, TokenType::As),
+            Token::new(1, 30, TokenType::Identifier("strings".to_string())),
+            Token::new(1, 37, TokenType::Semicolon),
+        ];
+        let mut parser: Parser = create_parser_with_tokens(tokens);
+
+        assert_eq!(
+            parse_top_level_statement(&mut parser),
+            Ok(Statement::Import(Box::new(ImportNode {
+                path: "lib/strings.shot".to_string(),
+                alias: "strings".to_string(),
+            })))
+        );
+    }
+
+    /// newtype文で宣言した型名を、型注釈で使えるか確認するテスト
+    /// newtype UserId = int;
+    /// let id: UserId = 1;
+    #[test]
+    fn parse_newtype_and_use_it_as_type() {
+        let tokens: Vec<Token> = vec![
+            Token::new(1, 1, TokenType::Newtype),
+            Token::new(1, 9, TokenType::Identifier("UserId".to_string())),
+            Token::new(1, 16, TokenType::Equal),
+            Token::new(1, 18, TokenType::IntType),
+            Token::new(1, 21, TokenType::Semicolon),
+            Token::new(2, 1, TokenType::Let),
+            Token::new(2, 5, TokenType::Identifier("id".to_string())),
+            Token::new(2, 7, TokenType::Colon),
+            Token::new(2, 9, TokenType::Identifier("UserId".to_string())),
+            Token::new(2, 16, TokenType::Equal),
+            Token::new(2, 18, TokenType::IntegerLiteral(1)),
+            Token::new(2, 19, TokenType::Semicolon),
+        ];
+        let mut parser: Parser = create_parser_with_tokens(tokens);
+
+        assert_eq!(
+            parse_top_level_statement(&mut parser),
+            Ok(Statement::DeclarationOfNewtype(Box::new(
+                NewtypeDeclarationNode {
+                    name: "UserId".to_string(),
+                    underlying_type: Type::Integer,
+                }
+            )))
+        );
+        assert_eq!(
+            parse_top_level_statement(&mut parser),
+            Ok(Statement::DeclarationOfVariable(Box::new(
+                VariableDeclarationNode {
+                    name: "id".to_string(),
+                    var_type: Type::Newtype("UserId".to_string(), Box::new(Type::Integer)),
+                    value: Box::new(ExpressionNode::Literal(Box::new(LiteralNode {
+                        value: LiteralValue::Integer(1),
+                    }))),
+                }
+            )))
+        );
+    }
+
+    /// 同じ型名のnewtype文がエラーになるか確認するテスト
+    /// newtype UserId = int;
+    /// newtype UserId = string;
+    #[test]
+    fn fail_duplicate_newtype() {
+        let tokens: Vec<Token> = vec![
+            Token::new(1, 1, TokenType::Newtype),
+            Token::new(1, 9, TokenType::Identifier("UserId".to_string())),
+            Token::new(1, 16, TokenType::Equal),
+            Token::new(1, 18, TokenType::IntType),
+            Token::new(1, 21, TokenType::Semicolon),
+            Token::new(2, 1, TokenType::Newtype),
+            Token::new(2, 9, TokenType::Identifier("UserId".to_string())),
+            Token::new(2, 16, TokenType::Equal),
+            Token::new(2, 18, TokenType::StringType),
+            Token::new(2, 24, TokenType::Semicolon),
+        ];
+        let mut parser: Parser = create_parser_with_tokens(tokens);
+
+        assert!(parse_top_level_statement(&mut parser).is_ok());
+        assert_eq!(
+            parse_top_level_statement(&mut parser),
+            Err(ParserError::DuplicateType {
+                name: "UserId".to_string(),
+                line: 2,
+                char_pos: 9,
+            })
+        );
+    }
+
+    /// export文のパースが可能か確認するテスト
+    /// export const WIDTH: int = 80;
+    #[test]
+    fn parse_export() {
+        let tokens: Vec<Token> = vec![
+            Token::new(1, 1, TokenType::Export),
+            Token::new(1, 8, TokenType::Const),
+            Token::new(1, 14, TokenType::Identifier("WIDTH".to_string())),
+            Token::new(1, 19, TokenType::Colon),
+            Token::new(1, 21, TokenType::IntType),
+            Token::new(1, 25, TokenType::Equal),
+            Token::new(1, 27, TokenType::IntegerLiteral(80)),
+            Token::new(1, 29, TokenType::Semicolon),
+        ];
+        let mut parser: Parser = create_parser_with_tokens(tokens);
+
+        assert_eq!(
+            parse_top_level_statement(&mut parser),
+            Ok(Statement::Export(Box::new(
+                Statement::DeclarationOfConstant(Box::new(VariableDeclarationNode {
+                    name: "WIDTH".to_string(),
+                    var_type: Type::Integer,
+                    value: Box::new(ExpressionNode::Literal(Box::new(LiteralNode {
+                        value: LiteralValue::Integer(80),
+                    }))),
+                }))
+            )))
+        );
+    }
+
+    /// 宣言以外にexportを付けた場合にエラーになるか確認するテスト
+    /// export 0;
+    #[test]
+    fn fail_export_expression() {
+        let tokens: Vec<Token> = vec![
+            Token::new(1, 1, TokenType::Export),
+            Token::new(1, 8, TokenType::IntegerLiteral(0)),
+            Token::new(1, 9, TokenType::Semicolon),
+        ];
+        let mut parser: Parser = create_parser_with_tokens(tokens);
+
+        assert_eq!(
+            parse_top_level_statement(&mut parser),
+            Err(ParserError::MismatchedToken {
+                expected: TokenType::Let,
+                found: TokenType::IntegerLiteral(0),
+                line: 1,
+                char_pos: 8,
+            })
+        );
+    }
+
+    /// ブロックのパースが可能か確認するテスト
+    /// { let x: int = 0; }
+    #[test]
+    fn parse_block() {
+        let tokens: Vec<Token> = vec![
+            Token::new(1, 1, TokenType::LeftBrace),
+            Token::new(2, 5, TokenType::Let),
+            Token::new(2, 9, TokenType::Identifier("x".to_string())),
+            Token::new(2, 10, TokenType::Colon),
+            Token::new(2, 12, TokenType::IntType),
+            Token::new(2, 16, TokenType::Equal),
+            Token::new(2, 18, TokenType::IntegerLiteral(0)),
+            Token::new(2, 19, TokenType::Semicolon),
+            Token::new(3, 1, TokenType::RightBrace),
+        ];
+        let mut parser: Parser = create_parser_with_tokens(tokens);
+
+        assert_eq!(
+            parse_statement(&mut parser),
+            Ok(Statement::Block(Box::new(BlockNode {
+                statements: vec![(
+                    2,
+                    Statement::DeclarationOfVariable(Box::new(VariableDeclarationNode {
+                        name: "x".to_string(),
+                        var_type: Type::Integer,
+                        value: Box::new(ExpressionNode::Literal(Box::new(LiteralNode {
+                            value: LiteralValue::Integer(0),
+                        }))),
+                    }))
+                )],
+            })))
+        );
+    }
+
+    /// ブロックの中にreturn文を書いた場合にエラーになるか確認するテスト
+    /// { return 0; }
+    #[test]
+    fn fail_return_in_block() {
+        let tokens: Vec<Token> = vec![
+            Token::new(1, 1, TokenType::LeftBrace),
+            Token::new(1, 3, TokenType::Return),
+            Token::new(1, 10, TokenType::IntegerLiteral(0)),
+            Token::new(1, 11, TokenType::Semicolon),
+            Token::new(1, 13, TokenType::RightBrace),
+        ];
+        let mut parser: Parser = create_parser_with_tokens(tokens);
+
+        assert_eq!(
+            parse_statement(&mut parser),
+            Err(ParserError::MismatchedToken {
+                expected: TokenType::RightBrace,
+                found: TokenType::Return,
+                line: 1,
+                char_pos: 3,
+            })
+        );
+    }
+
+    /// 関数の本体とブロックの中に、impl文とinterface文を書いた場合にエラーになるか確認するテスト
+    /// let f: fn = (): int { impl int { ... } ... };
+    /// { interface Named { ... } }
+    #[test]
+    fn fail_impl_and_interface_outside_top_level() {
+        let tokens: Vec<Token> = vec![
+            Token::new(1, 1, TokenType::Let),
+            Token::new(1, 5, TokenType::Identifier("f".to_string())),
+            Token::new(1, 6, TokenType::Colon),
+            Token::new(1, 8, TokenType::Fn),
+            Token::new(1, 11, TokenType::Equal),
+            Token::new(1, 13, TokenType::LeftParen),
+            Token::new(1, 14, TokenType::RightParen),
+            Token::new(1, 15, TokenType::Colon),
+            Token::new(1, 17, TokenType::IntType),
+            Token::new(1, 21, TokenType::LeftBrace),
+            Token::new(1, 23, TokenType::Impl),
+        ];
+        let mut parser: Parser = create_parser_with_tokens(tokens);
+        assert_eq!(
+            parse_statement(&mut parser),
+            Err(ParserError::NotAtTopLevel {
+                found: TokenType::Impl,
+                line: 1,
+                char_pos: 23,
+            })
+        );
+
+        let tokens: Vec<Token> = vec![
+            Token::new(1, 1, TokenType::LeftBrace),
+            Token::new(1, 3, TokenType::Interface),
+        ];
+        let mut parser: Parser = create_parser_with_tokens(tokens);
+        assert_eq!(
+            parse_statement(&mut parser),
+            Err(ParserError::NotAtTopLevel {
+                found: TokenType::Interface,
+                line: 1,
+                char_pos: 3,
+            })
+        );
+    }
+
+    /// 関数の本体とブロックの中に、import文とexport文を書いた場合にエラーになるか確認するテスト
+    /// let f: fn = (): int { import "lib/strings.shot" as s; ... };
+    /// { export let x: int = 0; }
+    #[test]
+    fn fail_import_and_export_outside_top_level() {
+        let tokens: Vec<Token> = vec![
+            Token::new(1, 1, TokenType::Let),
+            Token::new(1, 5, TokenType::Identifier("f".to_string())),
+            Token::new(1, 6, TokenType::Colon),
+            Token::new(1, 8, TokenType::Fn),
+            Token::new(1, 11, TokenType::Equal),
+            Token::new(1, 13, TokenType::LeftParen),
+            Token::new(1, 14, TokenType::RightParen),
+            Token::new(1, 15, TokenType::Colon),
+            Token::new(1, 17, TokenType::IntType),
+            Token::new(1, 21, TokenType::LeftBrace),
+            Token::new(1, 23, TokenType::Import),
+        ];
+        let mut parser: Parser = create_parser_with_tokens(tokens);
+        assert_eq!(
+            parse_statement(&mut parser),
+            Err(ParserError::NotAtTopLevel {
+                found: TokenType::Import,
+                line: 1,
+                char_pos: 23,
+            })
+        );
+
+        let tokens: Vec<Token> = vec![
+            Token::new(1, 1, TokenType::LeftBrace),
+            Token::new(1, 3, TokenType::Export),
+        ];
+        let mut parser: Parser = create_parser_with_tokens(tokens);
+        assert_eq!(
+            parse_statement(&mut parser),
+            Err(ParserError::NotAtTopLevel {
+                found: TokenType::Export,
+                line: 1,
+                char_pos: 3,
+            })
+        );
+    }
+
+    /// ブロックの中にnewtype文を書いた場合にエラーになるか確認するテスト
+    /// { newtype Q = int; }
+    #[test]
+    fn fail_newtype_outside_top_level() {
+        let tokens: Vec<Token> = vec![
+            Token::new(1, 1, TokenType::LeftBrace),
+            Token::new(1, 3, TokenType::Newtype),
+            Token::new(1, 11, TokenType::Identifier("Q".to_string())),
+            Token::new(1, 13, TokenType::Equal),
+            Token::new(1, 15, TokenType::IntType),
+            Token::new(1, 18, TokenType::Semicolon),
+            Token::new(1, 20, TokenType::RightBrace),
+        ];
+        let mut parser: Parser = create_parser_with_tokens(tokens);
+        assert_eq!(
+            parse_statement(&mut parser),
+            Err(ParserError::NotAtTopLevel {
+                found: TokenType::Newtype,
+                line: 1,
+                char_pos: 3,
+            })
+        );
+    }
+}
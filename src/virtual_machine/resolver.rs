@@ -0,0 +1,364 @@
+mod resolve_error;
+
+use crate::virtual_machine::ast::{
+    ArgumentNode, ExpressionNode, FunctionDeclarationNode, Statement, AST,
+};
+use crate::virtual_machine::evaluator::mapper::function_mapper::has_same_signature;
+pub use resolve_error::ResolveError;
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+
+/// 評価の前に、識別子が宣言を参照しているかを検査する
+///
+/// 変数と関数の呼び出しが、スコープの中の宣言 (グローバル、パラメータ、ローカル) を参照しているかを確かめます。
+/// 宣言されていない名前の呼び出しと、同じスコープでの重複した宣言を、全てまとめてエラーとして返します。
+/// このパスは検査のみを行い、ASTを変更しません。変数と関数のスロットは、バイトコードへのコンパイルで割り当てます。
+///
+/// ```shot
+/// let a: int = 1;
+/// f(x: not_a);  # UndefinedFunction, UndefinedVariable
+/// let a: int = 2;  # DuplicateDeclaration
+/// ```
+///
+/// スコープの規則は評価と同じです。
+///
+/// * 宣言より前の行からは、その宣言を参照できません。
+/// * グローバルに宣言された関数とメソッドの本体では、パラメータと本体で宣言された変数と関数のみ参照できます。
+/// * 関数の中で宣言された関数の本体では、外側の関数の変数と関数も参照できます。
+/// * パラメータのデフォルト値は、関数が宣言された位置のスコープで解決します。
+/// * 同じ名前の関数は、パラメータの名前と型が異なればオーバーロードとして宣言できます。
+///
+/// ## Raises
+///
+/// * `ResolveError` - 解決できない名前と重複した宣言ごとのエラー
+pub(crate) fn resolve(ast: &AST) -> Result<(), Vec<ResolveError>> {
+    let mut resolver: Resolver = Resolver {
+        scopes: vec![Scope::default()],
+        is_function_body: false,
+        errors: vec![],
+    };
+    for (line, statement) in ast.statements.iter() {
+        resolver.resolve_statement(statement, *line);
+    }
+    match resolver.errors.is_empty() {
+        true => Ok(()),
+        false => Err(resolver.errors),
+    }
+}
+
+/// スコープで宣言された変数の名前と関数のオーバーロード
+///
+/// オーバーロードは、ASTの関数宣言を共有して保持する
+#[derive(Default)]
+struct Scope {
+    variables: HashSet<String>,
+    functions: HashMap<String, Vec<Rc<FunctionDeclarationNode>>>,
+}
+
+struct Resolver {
+    scopes: Vec<Scope>,
+    is_function_body: bool,
+    errors: Vec<ResolveError>,
+}
+
+impl Resolver {
+    fn resolve_statement(&mut self, statement: &Statement, line: usize) {
+        match statement {
+            Statement::Expression(expression) => self.resolve_expression(expression, line),
+            Statement::Return(expression) => self.resolve_expression(expression, line),
+            Statement::DeclarationOfVariable(node) | Statement::DeclarationOfConstant(node) => {
+                // 初期化式は、宣言する変数を含まないスコープで解決する
+                self.resolve_expression(&node.value, line);
+                self.declare_variable(&node.name, line);
+            }
+            Statement::DeclarationOfFunction(node) => {
+                self.declare_function(node, line);
+                self.resolve_default_values(node, line);
+                self.resolve_function_body(node);
+            }
+            Statement::Block(block) => {
+                self.scopes.push(Scope::default());
+                for (line, statement) in block.statements.iter() {
+                    self.resolve_statement(statement, *line);
+                }
+                self.scopes.pop();
+            }
+            Statement::Export(declaration) => self.resolve_statement(declaration, line),
+            Statement::Impl(node) => {
+                // メソッドのデフォルト値と本体は、外側のスコープを参照せずに解決する
+                let is_function_body: bool = std::mem::replace(&mut self.is_function_body, false);
+                let outer_scopes: Vec<Scope> =
+                    std::mem::replace(&mut self.scopes, vec![Scope::default()]);
+                for method in node.methods.iter() {
+                    self.resolve_default_values(method, line);
+                    self.resolve_function_body(method);
+                }
+                self.scopes = outer_scopes;
+                self.is_function_body = is_function_body;
+            }
+            Statement::Import(_)
+            | Statement::DeclarationOfNewtype(_)
+            | Statement::DeclarationOfInterface(_) => {}
+        }
+    }
+
+    /// パラメータのデフォルト値を、関数が宣言された位置のスコープで解決する
+    fn resolve_default_values(&mut self, node: &FunctionDeclarationNode, line: usize) {
+        for param in node.params.iter() {
+            if let Some(default_value) = &param.default_value {
+                self.resolve_expression(default_value, line);
+            }
+        }
+    }
+
+    /// 関数の本体を、パラメータを宣言した新しいスコープで解決する
+    ///
+    /// 関数の中で宣言された関数は、外側の関数のスコープを引き継ぐ
+    fn resolve_function_body(&mut self, node: &FunctionDeclarationNode) {
+        let outer_scopes: Option<Vec<Scope>> = match self.is_function_body {
+            true => None,
+            false => Some(std::mem::take(&mut self.scopes)),
+        };
+        // パラメータのスコープの内側に、本体のスコープを作る
+        self.scopes.push(Scope {
+            variables: node.params.iter().map(|param| param.name.clone()).collect(),
+            functions: HashMap::new(),
+        });
+        self.scopes.push(Scope::default());
+        let is_function_body: bool = std::mem::replace(&mut self.is_function_body, true);
+
+        for (line, statement) in node.body.iter() {
+            self.resolve_statement(statement, *line);
+        }
+
+        self.is_function_body = is_function_body;
+        self.scopes.pop();
+        self.scopes.pop();
+        if let Some(outer_scopes) = outer_scopes {
+            self.scopes = outer_scopes;
+        }
+    }
+
+    fn resolve_expression(&mut self, expression: &ExpressionNode, line: usize) {
+        match expression {
+            ExpressionNode::Literal(_) => {}
+            ExpressionNode::BinaryOperation(node) => {
+                self.resolve_expression(&node.left, line);
+                self.resolve_expression(&node.right, line);
+            }
+            // print(not_a);  # UndefinedVariable
+            ExpressionNode::CallOfVariable(node) => {
+                if !self.is_variable_declared(&node.name) {
+                    self.errors.push(ResolveError::UndefinedVariable {
+                        name: node.name.clone(),
+                        line,
+                    });
+                }
+            }
+            // 関数を値に持つ変数 (部分適用で作られた関数など) も呼び出せる
+            ExpressionNode::CallOfFunction(node) | ExpressionNode::PartialApplication(node) => {
+                if !self.is_function_declared(&node.name) && !self.is_variable_declared(&node.name)
+                {
+                    self.errors.push(ResolveError::UndefinedFunction {
+                        name: node.name.clone(),
+                        line,
+                    });
+                }
+                self.resolve_arguments(&node.arguments, line);
+            }
+            ExpressionNode::TypeCast(node) => self.resolve_expression(&node.expression, line),
+            ExpressionNode::TypeTest(node) => self.resolve_expression(&node.expression, line),
+            ExpressionNode::If(node) => {
+                self.resolve_expression(&node.condition, line);
+                self.resolve_expression(&node.then_branch, line);
+                self.resolve_expression(&node.else_branch, line);
+            }
+            // メソッドは呼び出し元の値の型から探すので、評価するまで決まらない
+            ExpressionNode::CallOfMethod(node) => {
+                self.resolve_expression(&node.receiver, line);
+                self.resolve_arguments(&node.arguments, line);
+            }
+        }
+    }
+
+    fn resolve_arguments(&mut self, arguments: &[ArgumentNode], line: usize) {
+        for argument in arguments.iter() {
+            self.resolve_expression(&argument.value, line);
+        }
+    }
+
+    /// 変数を宣言する
+    ///
+    /// let x: int = 1; let x: int = 2;  # DuplicateDeclaration
+    fn declare_variable(&mut self, name: &str, line: usize) {
+        let scope: &mut Scope = self.scopes.last_mut().expect("scope must exist");
+        if !scope.variables.insert(name.to_string()) {
+            self.errors.push(ResolveError::DuplicateDeclaration {
+                name: name.to_string(),
+                line,
+            });
+        }
+    }
+
+    /// 関数を宣言する
+    ///
+    /// パラメータの名前と型が全て同じオーバーロードは区別できないので、重複した宣言とする
+    fn declare_function(&mut self, node: &Rc<FunctionDeclarationNode>, line: usize) {
+        let scope: &mut Scope = self.scopes.last_mut().expect("scope must exist");
+        let overloads: &mut Vec<Rc<FunctionDeclarationNode>> =
+            scope.functions.entry(node.name.clone()).or_default();
+        if overloads
+            .iter()
+            .any(|overload| has_same_signature(overload, node))
+        {
+            self.errors.push(ResolveError::DuplicateDeclaration {
+                name: node.name.clone(),
+                line,
+            });
+            return;
+        }
+        overloads.push(Rc::clone(node));
+    }
+
+    fn is_variable_declared(&self, name: &str) -> bool {
+        self.scopes
+            .iter()
+            .any(|scope| scope.variables.contains(name))
+    }
+
+    fn is_function_declared(&self, name: &str) -> bool {
+        self.scopes
+            .iter()
+            .any(|scope| scope.functions.contains_key(name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::virtual_machine::evaluator::core::parse_source;
+    use crate::virtual_machine::resolver::{resolve, ResolveError};
+
+    fn resolve_source(source_code: &str) -> Result<(), Vec<ResolveError>> {
+        resolve(&parse_source(source_code))
+    }
+
+    /// パラメータ、ローカル変数、外側の関数のスコープの名前を解決できるか確認するテスト
+    #[test]
+    fn test_resolve_declared_names() {
+        let source_code: &str = r#"
+let a: int = 1;
+let f: fn = (x: int): int {
+    let y: int = x;
+    let g: fn = (): int { return y; };
+    return g();
+};
+let b: int = f(x: a);
+let inc: fn = f(...);
+{ let c: int = inc(x: b); }
+"#;
+        assert_eq!(resolve_source(source_code), Ok(()));
+    }
+
+    /// 宣言されていない名前と重複した宣言が、まとめて報告されるか確認するテスト
+    #[test]
+    fn test_resolve_reports_all_errors() {
+        let source_code: &str = r#"
+let a: int = 1;
+let b: int = not_a;
+let c: int = undefined_function(x: a);
+let a: int = 2;
+let f: fn = (x: int): int { return a; };
+{ let d: int = 1; }
+let e: int = d;
+"#;
+        assert_eq!(
+            resolve_source(source_code),
+            Err(vec![
+                ResolveError::UndefinedVariable {
+                    name: "not_a".to_string(),
+                    line: 3,
+                },
+                ResolveError::UndefinedFunction {
+                    name: "undefined_function".to_string(),
+                    line: 4,
+                },
+                ResolveError::DuplicateDeclaration {
+                    name: "a".to_string(),
+                    line: 5,
+                },
+                // グローバルに宣言された関数の本体からは、グローバルな変数を参照できない
+                ResolveError::UndefinedVariable {
+                    name: "a".to_string(),
+                    line: 6,
+                },
+                ResolveError::UndefinedVariable {
+                    name: "d".to_string(),
+                    line: 8,
+                },
+            ])
+        );
+    }
+
+    /// パラメータの異なるオーバーロードは重複した宣言にならないことを確認するテスト
+    #[test]
+    fn test_resolve_overloads() {
+        let source_code: &str = r#"
+let open: fn = (path: string): int { return 0; };
+let open: fn = (fd: int): int { return fd; };
+let open: fn = (path: string): int { return 1; };
+"#;
+        assert_eq!(
+            resolve_source(source_code),
+            Err(vec![ResolveError::DuplicateDeclaration {
+                name: "open".to_string(),
+                line: 4,
+            }])
+        );
+    }
+
+    /// デフォルト値が、関数が宣言された位置のスコープで解決されることを確認するテスト
+    #[test]
+    fn test_resolve_default_values() {
+        let source_code: &str = r#"
+let a: int = 1;
+let f: fn = (x: int = a): int { return x; };
+let g: fn = (x: int = b): int { return x; };
+newtype Host = string;
+impl Host {
+    let url: fn = (self, port: int = a): string { return self as string; };
+}
+"#;
+        assert_eq!(
+            resolve_source(source_code),
+            Err(vec![
+                ResolveError::UndefinedVariable {
+                    name: "b".to_string(),
+                    line: 4,
+                },
+                // メソッドのデフォルト値からは、グローバルな変数を参照できない (implの行で報告される)
+                ResolveError::UndefinedVariable {
+                    name: "a".to_string(),
+                    line: 6,
+                },
+            ])
+        );
+    }
+
+    /// 関数の本体の文で見つかったエラーが、その文の行番号で報告されるか確認するテスト
+    #[test]
+    fn test_resolve_reports_line_in_function_body() {
+        let source_code: &str = r#"let f: fn = (x: int): int {
+    let y: int = x;
+    let w: int = z;
+    return y;
+};
+"#;
+        assert_eq!(
+            resolve_source(source_code),
+            Err(vec![ResolveError::UndefinedVariable {
+                name: "z".to_string(),
+                line: 3,
+            }])
+        );
+    }
+}
@@ -0,0 +1,11 @@
+use thiserror::Error;
+
+#[derive(Debug, Clone, Error, PartialEq)]
+pub enum ResolveError {
+    #[error("Variable {name} is not declared at line {line}")]
+    UndefinedVariable { name: String, line: usize },
+    #[error("Function {name} is not declared at line {line}")]
+    UndefinedFunction { name: String, line: usize },
+    #[error("{name} is already declared in the same scope at line {line}")]
+    DuplicateDeclaration { name: String, line: usize },
+}
@@ -93,6 +93,9 @@ impl Scanner {
                 }
             }
 
+            // 可変長パラメータと引数の展開、またはモジュールの名前の修飾
+            '.' => self.dot(),
+
             // 文字列リテラル
             '"' => self.string(),
 
@@ -108,6 +111,8 @@ impl Scanner {
             '(' => Ok(Token::new(self.line, self.char_pos, TokenType::LeftParen)),
             ')' => Ok(Token::new(self.line, self.char_pos, TokenType::RightParen)),
             ';' => Ok(Token::new(self.line, self.char_pos, TokenType::Semicolon)),
+            '?' => Ok(Token::new(self.line, self.char_pos, TokenType::Question)),
+            '|' => Ok(Token::new(self.line, self.char_pos, TokenType::Pipe)),
             _ => Err(ScannerError::UnexpectedToken {
                 token: c.to_string(),
                 line: self.line,
@@ -152,6 +157,13 @@ impl Scanner {
             .unwrap_or('\0')
     }
 
+    // 次の次の文字を覗き見る
+    fn peek_next(&self) -> char {
+        let mut chars = self.source_code[self.current_pos..].chars();
+        chars.next();
+        chars.next().unwrap_or('\0')
+    }
+
     // コメントをスキップする関数
     fn skip_comment(&mut self) {
         // シングルラインコメントをスキップ (改行まで)
@@ -174,14 +186,27 @@ impl Scanner {
         // キーワードの判定
         let token_type = match text {
             "let" => TokenType::Let,
+            "const" => TokenType::Const,
             "as" => TokenType::As,
             "fn" => TokenType::Fn,
             "return" => TokenType::Return,
+            "import" => TokenType::Import,
+            "export" => TokenType::Export,
+            "is" => TokenType::Is,
+            "if" => TokenType::If,
+            "else" => TokenType::Else,
+            "newtype" => TokenType::Newtype,
+            "impl" => TokenType::Impl,
+            "interface" => TokenType::Interface,
             "none" => TokenType::NoneLiteral,
             "void" => TokenType::VoidType,
             "int" => TokenType::IntType,
             "float" => TokenType::FloatType,
             "string" => TokenType::StringType,
+            "bool" => TokenType::BoolType,
+            "any" => TokenType::AnyType,
+            "true" => TokenType::BooleanLiteral(true),
+            "false" => TokenType::BooleanLiteral(false),
             _ => TokenType::Identifier(text.to_string()),
         };
 
@@ -192,20 +217,57 @@ impl Scanner {
     ///
     /// 整数リテラルと浮動小数点リテラルを判定し、トークンを生成する
     ///
-    /// 整数リテラル: 1, 42, 100
-    /// 浮動小数点リテラル: 3.14, 42.0, 100.0
+    /// 整数リテラル: 1, 42, 1_000_000
+    /// 基数付き整数リテラル: 0xff (16進数), 0o755 (8進数), 0b1010 (2進数)
+    /// 浮動小数点リテラル: 3.14, 42.0, 1.5e-3, 1e6
+    ///
+    /// 数字の区切りには `_` を用いることができるが、`_` の前後は数字でなければならない。
+    /// 小数点の前後にも数字が必要であり、`.5` や `1.` はエラーとなる。
     fn number(&mut self) -> Result<Token, ScannerError> {
         let start = self.current_pos - 1;
-        let mut has_dot = false;
 
-        while self.peek().is_ascii_digit() || (self.peek() == '.' && !has_dot) {
-            if self.peek() == '.' {
-                has_dot = true;
+        // 基数付き整数リテラル
+        if self.source_code[start..].starts_with('0') {
+            let radix: Option<u32> = match self.peek() {
+                'x' => Some(16),
+                'o' => Some(8),
+                'b' => Some(2),
+                _ => None,
+            };
+            if let Some(radix) = radix {
+                self.advance();
+                return self.radix_number(start, radix);
             }
+        }
+
+        // 整数部
+        self.consume_decimal_digits();
+        let mut is_float = false;
+
+        // 小数部 (`...` が続く場合は小数点として扱わない)
+        if self.peek() == '.' && self.peek_next() != '.' {
             self.advance();
+            if !self.peek().is_ascii_digit() {
+                // 1. のように小数点の後に数字がない
+                return Err(self.invalid_float_literal(start));
+            }
+            self.consume_decimal_digits();
+            is_float = true;
         }
 
-        let text = &self.source_code[start..self.current_pos];
+        // 指数部
+        if self.peek() == 'e' || self.peek() == 'E' {
+            self.advance();
+            if self.peek() == '+' || self.peek() == '-' {
+                self.advance();
+            }
+            if !self.peek().is_ascii_digit() {
+                // 1e や 1e+ のように指数の数字がない
+                return Err(self.invalid_float_literal(start));
+            }
+            self.consume_decimal_digits();
+            is_float = true;
+        }
 
         // Check if the number is immediately followed by alphabetic characters
         if self.peek().is_alphabetic() {
@@ -223,30 +285,30 @@ impl Scanner {
             });
         }
 
-        if has_dot {
-            let parsed_text = text.parse::<f64>();
-            return match parsed_text {
+        let text: String = self.source_code[start..self.current_pos].to_string();
+        if !has_valid_digit_separators(&text, |c| c.is_ascii_digit()) {
+            return Err(self.invalid_digit_separator(start));
+        }
+        let digits: String = text.replace('_', "");
+
+        if is_float {
+            return match digits.parse::<f64>() {
                 Ok(float_value) => Ok(Token::new(
                     self.line,
                     self.char_pos,
                     TokenType::FloatLiteral(float_value),
                 )),
-                Err(text) => Err(ScannerError::InvalidFloatLiteralFound {
-                    number: text.to_string(),
-                    line: self.line,
-                    char_pos: start,
-                    source_code_line: self.source_code_vector[self.line - 1].clone(),
-                }),
+                Err(_) => Err(self.invalid_float_literal(start)),
             };
         }
-        match text.parse::<i64>() {
+        match digits.parse::<i64>() {
             Ok(int_value) => Ok(Token::new(
                 self.line,
                 self.char_pos,
                 TokenType::IntegerLiteral(int_value),
             )),
-            Err(text) => Err(ScannerError::InvalidIntegerLiteralFound {
-                number: text.to_string(),
+            Err(_) => Err(ScannerError::InvalidIntegerLiteralFound {
+                number: text,
                 line: self.line,
                 char_pos: start,
                 source_code_line: self.source_code_vector[self.line - 1].clone(),
@@ -254,6 +316,105 @@ impl Scanner {
         }
     }
 
+    /// 基数付き整数リテラルのトークン生成
+    ///
+    /// `0x`, `0o`, `0b` の接頭辞は読み込み済みであることを前提とする
+    fn radix_number(&mut self, start: usize, radix: u32) -> Result<Token, ScannerError> {
+        let digits_start = self.current_pos;
+        while self.peek().is_alphanumeric() || self.peek() == '_' {
+            self.advance();
+        }
+
+        let text: String = self.source_code[start..self.current_pos].to_string();
+        let digits: &str = &self.source_code[digits_start..self.current_pos];
+
+        // 0b102 や 0xfg のように基数に合わない文字が含まれている
+        if digits.chars().any(|c| c != '_' && !c.is_digit(radix)) {
+            return Err(ScannerError::InvalidCharacterInNumberLiteral {
+                number_literal: text,
+                line: self.line,
+                char_pos: start,
+                source_code_line: self.source_code_vector[self.line - 1].clone(),
+            });
+        }
+        if !has_valid_digit_separators(digits, |c| c.is_digit(radix)) {
+            return Err(self.invalid_digit_separator(start));
+        }
+
+        match i64::from_str_radix(&digits.replace('_', ""), radix) {
+            Ok(int_value) => Ok(Token::new(
+                self.line,
+                self.char_pos,
+                TokenType::IntegerLiteral(int_value),
+            )),
+            // 0x のように数字がない場合や、値が大きすぎる場合
+            Err(_) => Err(ScannerError::InvalidIntegerLiteralFound {
+                number: text,
+                line: self.line,
+                char_pos: start,
+                source_code_line: self.source_code_vector[self.line - 1].clone(),
+            }),
+        }
+    }
+
+    // 10進数の数字と区切り文字を読み進める
+    fn consume_decimal_digits(&mut self) {
+        while self.peek().is_ascii_digit() || self.peek() == '_' {
+            self.advance();
+        }
+    }
+
+    fn invalid_float_literal(&self, start: usize) -> ScannerError {
+        ScannerError::InvalidFloatLiteralFound {
+            number: self.source_code[start..self.current_pos].to_string(),
+            line: self.line,
+            char_pos: start,
+            source_code_line: self.source_code_vector[self.line - 1].clone(),
+        }
+    }
+
+    fn invalid_digit_separator(&self, start: usize) -> ScannerError {
+        ScannerError::InvalidDigitSeparator {
+            number_literal: self.source_code[start..self.current_pos].to_string(),
+            line: self.line,
+            char_pos: start,
+            source_code_line: self.source_code_vector[self.line - 1].clone(),
+        }
+    }
+
+    /// `.` から始まるトークン生成
+    ///
+    /// - `...`: 可変長パラメータと引数の展開
+    /// - `.`: モジュールの名前の修飾 (`strings.pad`)
+    ///
+    /// `..` のように `.` が2つだけ続く場合はエラーとなる
+    fn dot(&mut self) -> Result<Token, ScannerError> {
+        let start = self.char_pos;
+
+        // .5 のように小数点から始まる数字は許可しない
+        if self.peek().is_ascii_digit() {
+            let number_start = self.current_pos - 1;
+            self.consume_decimal_digits();
+            return Err(self.invalid_float_literal(number_start));
+        }
+
+        if self.peek() != '.' {
+            return Ok(Token::new(self.line, self.char_pos, TokenType::Dot));
+        }
+
+        self.advance();
+        if self.peek() == '.' {
+            self.advance();
+            return Ok(Token::new(self.line, self.char_pos, TokenType::Ellipsis));
+        }
+        Err(ScannerError::UnexpectedToken {
+            token: ".".to_string(),
+            line: self.line,
+            char_pos: start,
+            source_code_line: self.source_code_vector[self.line - 1].clone(),
+        })
+    }
+
     // 文字列リテラルのトークン生成
     fn string(&mut self) -> Result<Token, ScannerError> {
         let mut value = String::new();
@@ -283,6 +444,21 @@ impl Scanner {
     }
 }
 
+/// 数字の区切り文字 `_` が正しい位置にあるか判定する
+///
+/// `_` の前後はどちらも数字でなければならない。
+/// 例えば、`1_000` は正しいが、`1__000`, `1_`, `1_.5` は正しくない。
+fn has_valid_digit_separators(text: &str, is_digit: impl Fn(char) -> bool) -> bool {
+    let chars: Vec<char> = text.chars().collect();
+    chars.iter().enumerate().all(|(index, c)| {
+        *c != '_'
+            || (index > 0
+                && index + 1 < chars.len()
+                && is_digit(chars[index - 1])
+                && is_digit(chars[index + 1]))
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -333,6 +509,105 @@ mod tests {
         assert_eq!(token.token_type, TokenType::Let);
     }
 
+    /// キーワードを認識可能か確認するテスト
+    /// Scannerがキーワード "const" を正しく認識し、`Token::Const` にトークン化するかをテストします。
+    #[test]
+    fn test_keyword_const() {
+        let source = "const".to_string();
+        let source_vector = vec!["const".to_string()];
+
+        let mut scanner = Scanner::new(source, source_vector);
+
+        let token = scanner.next_token().unwrap();
+
+        // トークンが Token::Const であることをアサート
+        assert_eq!(token.token_type, TokenType::Const);
+    }
+
+    /// newtypeキーワードを認識可能か確認するテスト
+    /// Scannerが "newtype UserId = int;" を正しくトークン化するかをテストします。
+    #[test]
+    fn test_keyword_newtype() {
+        let source = "newtype UserId = int;".to_string();
+        let source_vector = vec![source.clone()];
+
+        let scanner = Scanner::new(source, source_vector);
+        let token_types: Vec<TokenType> = scanner
+            .scan()
+            .unwrap()
+            .into_iter()
+            .map(|token| token.token_type)
+            .collect();
+
+        assert_eq!(
+            token_types,
+            vec![
+                TokenType::Newtype,
+                TokenType::Identifier("UserId".to_string()),
+                TokenType::Equal,
+                TokenType::IntType,
+                TokenType::Semicolon,
+                TokenType::Eof,
+            ]
+        );
+    }
+
+    /// implとinterfaceのキーワードを認識可能か確認するテスト
+    /// Scannerが "impl Host interface" を正しくトークン化するかをテストします。
+    #[test]
+    fn test_keyword_impl_and_interface() {
+        let source = "impl Host interface".to_string();
+        let source_vector = vec![source.clone()];
+
+        let scanner = Scanner::new(source, source_vector);
+        let token_types: Vec<TokenType> = scanner
+            .scan()
+            .unwrap()
+            .into_iter()
+            .map(|token| token.token_type)
+            .collect();
+
+        assert_eq!(
+            token_types,
+            vec![
+                TokenType::Impl,
+                TokenType::Identifier("Host".to_string()),
+                TokenType::Interface,
+                TokenType::Eof,
+            ]
+        );
+    }
+
+    /// 型の検査と条件分岐のキーワードを認識可能か確認するテスト
+    /// Scannerが "if x is int | any else" を正しくトークン化するかをテストします。
+    #[test]
+    fn test_keyword_if_is_and_union_type() {
+        let source = "if x is int | any else".to_string();
+        let source_vector = vec![source.clone()];
+
+        let scanner = Scanner::new(source, source_vector);
+        let token_types: Vec<TokenType> = scanner
+            .scan()
+            .unwrap()
+            .into_iter()
+            .map(|token| token.token_type)
+            .collect();
+
+        assert_eq!(
+            token_types,
+            vec![
+                TokenType::If,
+                TokenType::Identifier("x".to_string()),
+                TokenType::Is,
+                TokenType::IntType,
+                TokenType::Pipe,
+                TokenType::AnyType,
+                TokenType::Else,
+                TokenType::Eof,
+            ]
+        );
+    }
+
     /// 数字を含む識別子を認識可能か確認するテスト
     /// Scannerが数字 "variable1" を `Token::Identifier` に正しくトークン化するかをテストします。
     #[test]
@@ -369,6 +644,7 @@ mod tests {
     /// 浮動小数点リテラルを認識可能か確認するテスト
     /// 浮動小数点リテラル "3.14" を `Token::FloatLiteral` に正しくトークン化するかをテストします。
     #[test]
+    #[allow(clippy::approx_constant)]
     fn test_float_literal() {
         let source = "3.14".to_string();
         let source_vector = vec!["3.14".to_string()];
@@ -400,6 +676,116 @@ mod tests {
         ));
     }
 
+    /// 基数付き整数リテラルを認識可能か確認するテスト
+    /// "0xff", "0o755", "0b1010" を `Token::IntegerLiteral` に正しくトークン化するかをテストします。
+    #[test]
+    fn test_radix_integer_literal() {
+        let source = "0xff 0o755 0b1010 0xFF_FF".to_string();
+        let source_vector = vec!["0xff 0o755 0b1010 0xFF_FF".to_string()];
+
+        let mut scanner = Scanner::new(source, source_vector);
+
+        // トークンがそれぞれの基数で解釈された整数リテラルであることをアサート
+        assert_eq!(
+            scanner.next_token().unwrap().token_type,
+            TokenType::IntegerLiteral(255)
+        );
+        assert_eq!(
+            scanner.next_token().unwrap().token_type,
+            TokenType::IntegerLiteral(493)
+        );
+        assert_eq!(
+            scanner.next_token().unwrap().token_type,
+            TokenType::IntegerLiteral(10)
+        );
+        assert_eq!(
+            scanner.next_token().unwrap().token_type,
+            TokenType::IntegerLiteral(65535)
+        );
+    }
+
+    /// 基数に合わない数字を認識可能か確認するテスト
+    /// "0b102" を与えたときに、Scannerが `InvalidCharacterInNumberLiteral` エラーを返すかをテストします。
+    #[test]
+    fn test_invalid_radix_integer_literal() {
+        let source = "0b102".to_string();
+        let source_vector = vec!["0b102".to_string()];
+
+        let mut scanner = Scanner::new(source, source_vector);
+
+        let result = scanner.next_token();
+
+        assert!(matches!(
+            result,
+            Err(ScannerError::InvalidCharacterInNumberLiteral { .. })
+        ));
+    }
+
+    /// 数字の区切り文字を認識可能か確認するテスト
+    /// "1_000_000" を `Token::IntegerLiteral` に正しくトークン化するかをテストします。
+    #[test]
+    fn test_integer_literal_with_digit_separators() {
+        let source = "1_000_000".to_string();
+        let source_vector = vec!["1_000_000".to_string()];
+
+        let mut scanner = Scanner::new(source, source_vector);
+
+        let token = scanner.next_token().unwrap();
+
+        assert_eq!(token.token_type, TokenType::IntegerLiteral(1_000_000));
+    }
+
+    /// 不正な位置の区切り文字を認識可能か確認するテスト
+    /// "1__000", "1_", "1_.5" を与えたときに、Scannerが `InvalidDigitSeparator` エラーを返すかをテストします。
+    #[test]
+    fn test_invalid_digit_separators() {
+        for source in ["1__000", "1_", "1_.5"] {
+            let mut scanner = Scanner::new(source.to_string(), vec![source.to_string()]);
+
+            let result = scanner.next_token();
+
+            assert!(matches!(
+                result,
+                Err(ScannerError::InvalidDigitSeparator { .. })
+            ));
+        }
+    }
+
+    /// 指数表記の浮動小数点リテラルを認識可能か確認するテスト
+    /// "1.5e-3", "2E6" を `Token::FloatLiteral` に正しくトークン化するかをテストします。
+    #[test]
+    fn test_scientific_float_literal() {
+        let source = "1.5e-3 2E6".to_string();
+        let source_vector = vec!["1.5e-3 2E6".to_string()];
+
+        let mut scanner = Scanner::new(source, source_vector);
+
+        assert_eq!(
+            scanner.next_token().unwrap().token_type,
+            TokenType::FloatLiteral(0.0015)
+        );
+        assert_eq!(
+            scanner.next_token().unwrap().token_type,
+            TokenType::FloatLiteral(2000000.0)
+        );
+    }
+
+    /// 小数点の前後に数字がない浮動小数点リテラルを認識可能か確認するテスト
+    /// "1.", ".5", "1e" を与えたときに、Scannerが `InvalidFloatLiteralFound` エラーを返すかをテストします。
+    #[test]
+    fn test_float_literal_without_digits_around_dot() {
+        for source in ["1.", ".5", "1e"] {
+            let mut scanner = Scanner::new(source.to_string(), vec![source.to_string()]);
+
+            let result = scanner.next_token();
+
+            assert!(matches!(
+                result,
+                Err(ScannerError::InvalidFloatLiteralFound { .. })
+            ));
+        }
+    }
+
     /// 文字列リテラルを認識可能か確認するテスト
     /// 文字列リテラル "\"hello world\"" を `Token::StringLiteral` に正しくトークン化するかをテストします。
     #[test]
@@ -567,4 +953,57 @@ mod tests {
         let token = scanner.next_token().unwrap();
         assert_eq!(token.token_type, TokenType::Eof);
     }
+
+    /// 可変長パラメータの記号を認識可能か確認するテスト
+    /// "...parts" を `Token::Ellipsis` と `Token::Identifier` に正しくトークン化するかをテストします。
+    #[test]
+    fn test_ellipsis() {
+        let source = "...parts".to_string();
+        let source_vector = vec!["...parts".to_string()];
+
+        let mut scanner = Scanner::new(source, source_vector);
+
+        // "..." トークンのチェック
+        let token = scanner.next_token().unwrap();
+        assert_eq!(token.token_type, TokenType::Ellipsis);
+
+        // "parts" 識別子のチェック
+        let token = scanner.next_token().unwrap();
+        assert_eq!(token.token_type, TokenType::Identifier("parts".to_string()));
+    }
+
+    /// 3つ続かないドットを認識可能か確認するテスト
+    /// ".." を与えたときに、Scannerが `UnexpectedToken` エラーを返すかをテストします。
+    #[test]
+    fn test_incomplete_ellipsis() {
+        let source = "..".to_string();
+        let source_vector = vec!["..".to_string()];
+
+        let mut scanner = Scanner::new(source, source_vector);
+
+        let result = scanner.next_token();
+
+        // 結果がScannerError::UnexpectedToken型のエラーであることをアサートします。
+        assert!(matches!(result, Err(ScannerError::UnexpectedToken { .. })));
+    }
+
+    /// モジュールの名前の修飾に用いる `.` を認識可能か確認するテスト
+    /// "strings.pad" を `Identifier`, `Dot`, `Identifier` にトークン化するかをテストします。
+    #[test]
+    fn test_dot() {
+        let source = "strings.pad".to_string();
+        let source_vector = vec!["strings.pad".to_string()];
+
+        let mut scanner = Scanner::new(source, source_vector);
+
+        assert_eq!(
+            scanner.next_token().unwrap().token_type,
+            TokenType::Identifier("strings".to_string())
+        );
+        assert_eq!(scanner.next_token().unwrap().token_type, TokenType::Dot);
+        assert_eq!(
+            scanner.next_token().unwrap().token_type,
+            TokenType::Identifier("pad".to_string())
+        );
+    }
 }
@@ -16,6 +16,13 @@ pub enum ScannerError {
         char_pos: usize,
         source_code_line: String,
     },
+    #[error("Invalid Digit Separator was Found: {number_literal} at line {line}, position {char_pos}, source code line: {source_code_line}")]
+    InvalidDigitSeparator {
+        number_literal: String,
+        line: usize,
+        char_pos: usize,
+        source_code_line: String,
+    },
     #[error("Invalid IntegerLiteral was Found: {number} at line {line}, position {char_pos}, source code line: {source_code_line}")]
     InvalidIntegerLiteralFound {
         number: String,
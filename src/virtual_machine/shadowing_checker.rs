@@ -0,0 +1,223 @@
+use crate::virtual_machine::ast::{Statement, AST};
+use std::collections::HashSet;
+use std::fmt;
+
+/// 外側のスコープの宣言をシャドーイングしている宣言
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ShadowingWarning {
+    pub name: String,
+    pub line: usize,
+}
+
+impl fmt::Display for ShadowingWarning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Warning: {} at line {} shadows a declaration in an outer scope",
+            self.name, self.line
+        )
+    }
+}
+
+/// 外側のスコープの宣言をシャドーイングしている宣言を探す
+///
+/// シャドーイングはエラーではありませんが、意図せず外側の変数を隠してしまうことがあります。
+/// `--warn-shadowing` が指定された場合に、この関数で見つかった宣言を警告として表示します。
+///
+/// ```shot
+/// let x: int = 1;
+/// {
+///     let x: int = 2;  # Warning
+/// }
+/// ```
+///
+/// グローバルに宣言された関数の本体は新しいスコープなので、本体の宣言がパラメータをシャドーイングする場合のみ警告します。
+/// 関数の中で宣言された関数の本体では、外側の関数の宣言をシャドーイングする場合も警告します。
+pub(crate) fn find_shadowing(ast: &AST) -> Vec<ShadowingWarning> {
+    let mut checker: ShadowingChecker = ShadowingChecker {
+        scopes: vec![HashSet::new()],
+        warnings: vec![],
+        is_function_body: false,
+    };
+    for (line, statement) in ast.statements.iter() {
+        checker.check_statement(statement, *line);
+    }
+    checker.warnings
+}
+
+struct ShadowingChecker {
+    scopes: Vec<HashSet<String>>,
+    warnings: Vec<ShadowingWarning>,
+    is_function_body: bool,
+}
+
+impl ShadowingChecker {
+    fn check_statement(&mut self, statement: &Statement, line: usize) {
+        match statement {
+            Statement::DeclarationOfVariable(node) | Statement::DeclarationOfConstant(node) => {
+                self.declare(&node.name, line)
+            }
+            Statement::DeclarationOfFunction(node) => {
+                self.declare(&node.name, line);
+
+                // 関数の本体は、パラメータを外側のスコープとする新しいスコープ
+                // 関数の中で宣言された関数は、外側の関数のスコープを引き継ぐ
+                let params: HashSet<String> =
+                    node.params.iter().map(|param| param.name.clone()).collect();
+                let outer_scopes: Vec<HashSet<String>> = match self.is_function_body {
+                    true => {
+                        let outer_scopes: Vec<HashSet<String>> = self.scopes.clone();
+                        self.scopes.push(params);
+                        outer_scopes
+                    }
+                    false => std::mem::replace(&mut self.scopes, vec![params]),
+                };
+                let is_function_body: bool = std::mem::replace(&mut self.is_function_body, true);
+                self.scopes.push(HashSet::new());
+                for (line, statement) in node.body.iter() {
+                    self.check_statement(statement, *line);
+                }
+                self.scopes = outer_scopes;
+                self.is_function_body = is_function_body;
+            }
+            Statement::Block(block) => {
+                self.scopes.push(HashSet::new());
+                for (line, statement) in block.statements.iter() {
+                    self.check_statement(statement, *line);
+                }
+                self.scopes.pop();
+            }
+            Statement::Export(declaration) => self.check_statement(declaration, line),
+            _ => {}
+        }
+    }
+
+    fn declare(&mut self, name: &str, line: usize) {
+        let (scope, outer_scopes) = self
+            .scopes
+            .split_last_mut()
+            .expect("ShadowingChecker always has the global scope");
+        if outer_scopes.iter().any(|outer| outer.contains(name)) {
+            self.warnings.push(ShadowingWarning {
+                name: name.to_string(),
+                line,
+            });
+        }
+        scope.insert(name.to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::virtual_machine::ast::{
+        BlockNode, ExpressionNode, FunctionDeclarationNode, LiteralNode, LiteralValue,
+        ParameterNode, Type, VariableDeclarationNode,
+    };
+    use std::rc::Rc;
+
+    fn declaration(name: &str) -> Statement {
+        Statement::DeclarationOfVariable(Box::new(VariableDeclarationNode {
+            name: name.to_string(),
+            var_type: Type::Integer,
+            value: Box::new(ExpressionNode::Literal(Box::new(LiteralNode {
+                value: LiteralValue::Integer(0),
+            }))),
+        }))
+    }
+
+    /// ブロックの中で外側の変数をシャドーイングした場合に警告されるか確認するテスト
+    /// let x: int = 0;
+    /// { let x: int = 0; let y: int = 0; }
+    #[test]
+    fn test_find_shadowing_in_block() {
+        let mut ast: AST = AST::new();
+        ast.push_statement(1, declaration("x"));
+        ast.push_statement(
+            2,
+            Statement::Block(Box::new(BlockNode {
+                statements: vec![(3, declaration("x")), (4, declaration("y"))],
+            })),
+        );
+
+        assert_eq!(
+            find_shadowing(&ast),
+            vec![ShadowingWarning {
+                name: "x".to_string(),
+                line: 3,
+            }]
+        );
+    }
+
+    /// 関数の本体で、グローバルな変数と同じ名前を宣言しても警告されないことを確認するテスト
+    /// let x: int = 0;
+    /// let f: fn = (a: int): int { { let a: int = 0; } let x: int = 0; return x; };
+    #[test]
+    fn test_find_shadowing_in_function_body() {
+        let mut ast: AST = AST::new();
+        ast.push_statement(1, declaration("x"));
+        ast.push_statement(
+            2,
+            Statement::DeclarationOfFunction(Rc::new(FunctionDeclarationNode {
+                name: "f".to_string(),
+                params: vec![ParameterNode {
+                    name: "a".to_string(),
+                    param_type: Type::Integer,
+                    default_value: None,
+                    is_variadic: false,
+                }],
+                return_type: Type::Integer,
+                body: vec![
+                    (
+                        2,
+                        Statement::Block(Box::new(BlockNode {
+                            statements: vec![(3, declaration("a"))],
+                        })),
+                    ),
+                    (2, declaration("x")),
+                ],
+            })),
+        );
+
+        assert_eq!(
+            find_shadowing(&ast),
+            vec![ShadowingWarning {
+                name: "a".to_string(),
+                line: 3,
+            }]
+        );
+    }
+
+    /// 関数の本体でパラメータをシャドーイングした場合に、その文の行番号で警告されるか確認するテスト
+    /// let f: fn = (a: int): int {
+    ///     let b: int = 0;
+    ///     let a: int = 0;
+    ///     return a;
+    /// };
+    #[test]
+    fn test_find_shadowing_reports_line_in_function_body() {
+        let mut ast: AST = AST::new();
+        ast.push_statement(
+            1,
+            Statement::DeclarationOfFunction(Rc::new(FunctionDeclarationNode {
+                name: "f".to_string(),
+                params: vec![ParameterNode {
+                    name: "a".to_string(),
+                    param_type: Type::Integer,
+                    default_value: None,
+                    is_variadic: false,
+                }],
+                return_type: Type::Integer,
+                body: vec![(2, declaration("b")), (3, declaration("a"))],
+            })),
+        );
+
+        assert_eq!(
+            find_shadowing(&ast),
+            vec![ShadowingWarning {
+                name: "a".to_string(),
+                line: 3,
+            }]
+        );
+    }
+}
@@ -1,16 +1,27 @@
 #[derive(Debug, PartialEq, Clone)]
 pub enum TokenType {
     // キーワード
-    Let,    // let
-    As,     // as
-    Fn,     // fn
-    Return, // return
+    Let,       // let
+    Const,     // const
+    As,        // as
+    Fn,        // fn
+    Return,    // return
+    Import,    // import
+    Export,    // export
+    Is,        // is
+    If,        // if
+    Else,      // else
+    Newtype,   // newtype
+    Impl,      // impl
+    Interface, // interface
 
     // 型
     IntType,    // int
     FloatType,  // float
     StringType, // string
+    BoolType,   // bool
     VoidType,   // void type
+    AnyType,    // any
 
     // 識別子
     Identifier(String),
@@ -19,6 +30,7 @@ pub enum TokenType {
     IntegerLiteral(i64),   // 整数リテラル
     FloatLiteral(f64),     // 浮動小数点リテラル
     StringLiteral(String), // 文字列リテラル
+    BooleanLiteral(bool),  // 真偽値リテラル
     NoneLiteral,           // Noneリテラル
 
     // 記号
@@ -36,10 +48,16 @@ pub enum TokenType {
     LeftBrace,   // {
     RightBrace,  // }
     Semicolon,   // ;
+    Dot,         // .
+    Question,    // ?
+    Pipe,        // |
 
     // 型キャストのための矢印
     TypeCastArrow, // ->
 
+    // 可変長パラメータと引数の展開のための記号
+    Ellipsis, // ...
+
     // 終了トークン
     Eof, // 終端
 }
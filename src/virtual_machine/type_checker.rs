@@ -0,0 +1,569 @@
+mod type_check_error;
+
+use crate::virtual_machine::ast::{
+    ArgumentNode, ExpressionNode, FunctionCallNode, FunctionDeclarationNode, MethodCallNode,
+    ParameterNode, Statement, Type, TypeCastNode, AST,
+};
+use crate::virtual_machine::evaluator::core::is_subtype;
+use crate::virtual_machine::evaluator::mapper::method_mapper::MethodMapper;
+use crate::virtual_machine::expression_typer::{type_of_expression, ExpressionTyper};
+use std::collections::HashMap;
+use std::rc::Rc;
+pub use type_check_error::TypeCheckError;
+
+/// 評価の前に、ASTの型を検査する
+///
+/// 次の型が一致しているかを検査し、見つかった全てのエラーをまとめて返します。
+///
+/// * 変数と定数の初期化式の型と、宣言された型
+/// * 関数呼び出しの引数の型と、パラメータの型
+/// * return文の式の型と、関数の戻り値の型
+/// * キャスト対象の式の型と、キャスト元の型
+///
+/// ```shot
+/// let f: int = "Hello" + " " + "World";  # VariableTypeMismatch
+/// let g: int = 0 as int -> float;        # VariableTypeMismatch
+/// ```
+///
+/// 式の型が求められない場合 (宣言されていない関数の呼び出しなど) は、その式を検査しません。
+/// スコープの規則は評価と同じです。グローバルに宣言された関数の本体では、パラメータと本体で宣言された変数と関数のみ参照できます。
+///
+/// ## Raises
+///
+/// * `TypeCheckError` - 型が一致しない箇所ごとのエラー
+/// * `TypeCheckError::DuplicateMethod` - 同じ型に同じ名前のメソッドが実装された場合
+pub(crate) fn check_types(ast: &AST) -> Result<(), Vec<TypeCheckError>> {
+    let (method_mapper, errors) = collect_methods(ast);
+    let mut checker: TypeChecker = TypeChecker::new(method_mapper);
+    checker.errors = errors;
+    for (line, statement) in ast.statements.iter() {
+        checker.check_statement(statement, *line);
+    }
+    match checker.errors.is_empty() {
+        true => Ok(()),
+        false => Err(checker.errors),
+    }
+}
+
+/// implで実装されたメソッドを集める
+///
+/// メソッドはどこからでも呼び出せるので、検査の前に全て登録しておく
+/// 同じ型に同じ名前のメソッドが実装された場合は、評価の前にエラーとして報告する
+fn collect_methods(ast: &AST) -> (MethodMapper, Vec<TypeCheckError>) {
+    let mut method_mapper: MethodMapper = MethodMapper::new();
+    let mut errors: Vec<TypeCheckError> = vec![];
+    for (line, statement) in ast.statements.iter() {
+        if let Statement::Impl(node) = statement {
+            let type_name: String = node.target.to_string();
+            for method in node.methods.iter() {
+                // impl int { let d: fn = ...; let d: fn = ...; }  # DuplicateMethod
+                if method_mapper
+                    .set(*line, &type_name, Rc::clone(method))
+                    .is_err()
+                {
+                    errors.push(TypeCheckError::DuplicateMethod {
+                        type_name: type_name.clone(),
+                        name: method.name.clone(),
+                        line: *line,
+                    });
+                }
+            }
+        }
+    }
+    (method_mapper, errors)
+}
+
+/// 関数のパラメータと戻り値の型
+#[derive(Clone)]
+struct FunctionSignature {
+    params: Vec<ParameterNode>,
+    return_type: Type,
+}
+
+/// スコープごとの変数の型と関数のシグネチャ、検査中の関数と文の行番号を保持する
+struct TypeChecker {
+    variable_scopes: Vec<HashMap<String, Type>>,
+    function_scopes: Vec<HashMap<String, Vec<FunctionSignature>>>,
+    method_mapper: MethodMapper,
+    current_function: Option<(String, Type)>,
+    is_function_body: bool,
+    line: usize,
+    errors: Vec<TypeCheckError>,
+}
+
+impl TypeChecker {
+    fn new(method_mapper: MethodMapper) -> Self {
+        TypeChecker {
+            variable_scopes: vec![HashMap::new()],
+            function_scopes: vec![HashMap::new()],
+            method_mapper,
+            current_function: None,
+            is_function_body: false,
+            line: 0,
+            errors: vec![],
+        }
+    }
+
+    fn check_statement(&mut self, statement: &Statement, line: usize) {
+        match statement {
+            Statement::Expression(expression) => {
+                self.check_expression(expression, line);
+            }
+            Statement::DeclarationOfVariable(node) | Statement::DeclarationOfConstant(node) => {
+                let actual: Option<Type> = self.check_expression(&node.value, line);
+                if let Some(actual) = actual {
+                    // let f: int = "Hello";  # VariableTypeMismatch
+                    if node.var_type != Type::Infer && !self.is_assignable(&actual, &node.var_type)
+                    {
+                        self.errors.push(TypeCheckError::VariableTypeMismatch {
+                            name: node.name.clone(),
+                            line,
+                            expected: node.var_type.to_string(),
+                            actual: actual.to_string(),
+                        });
+                    }
+                }
+                self.declare_variable(&node.name, node.var_type.clone());
+            }
+            Statement::DeclarationOfFunction(node) => {
+                // 再帰呼び出しのため、本体を検査する前に宣言する
+                self.function_scopes
+                    .last_mut()
+                    .expect("function scope must exist")
+                    .entry(node.name.clone())
+                    .or_default()
+                    .push(FunctionSignature {
+                        params: node.params.clone(),
+                        return_type: node.return_type.clone(),
+                    });
+                self.check_function_body(node);
+            }
+            Statement::Return(expression) => {
+                let actual: Option<Type> = self.check_expression(expression, line);
+                if let (Some(actual), Some((function_name, return_type))) =
+                    (actual, self.current_function.clone())
+                {
+                    // let f: fn = (): int { return "a"; };  # ReturnTypeMismatch
+                    if return_type != Type::Infer && !self.is_assignable(&actual, &return_type) {
+                        self.errors.push(TypeCheckError::ReturnTypeMismatch {
+                            function_name,
+                            line,
+                            expected: return_type.to_string(),
+                            actual: actual.to_string(),
+                        });
+                    }
+                }
+            }
+            Statement::Block(block) => {
+                self.push_scope();
+                for (line, statement) in block.statements.iter() {
+                    self.check_statement(statement, *line);
+                }
+                self.pop_scope();
+            }
+            Statement::Export(declaration) => self.check_statement(declaration, line),
+            Statement::Impl(node) => {
+                // メソッドの本体は、グローバルに宣言された関数と同じく新しいスコープで検査する
+                let is_function_body: bool = std::mem::replace(&mut self.is_function_body, false);
+                for method in node.methods.iter() {
+                    self.check_function_body(method);
+                }
+                self.is_function_body = is_function_body;
+            }
+            Statement::Import(_)
+            | Statement::DeclarationOfNewtype(_)
+            | Statement::DeclarationOfInterface(_) => {}
+        }
+    }
+
+    /// 関数の本体を、パラメータを宣言した新しいスコープで検査する
+    ///
+    /// 関数の中で宣言された関数は、外側の関数のスコープを引き継ぐ
+    fn check_function_body(&mut self, node: &FunctionDeclarationNode) {
+        let outer_scopes = match self.is_function_body {
+            true => None,
+            false => Some((
+                std::mem::take(&mut self.variable_scopes),
+                std::mem::take(&mut self.function_scopes),
+            )),
+        };
+        self.push_scope();
+        for param in node.params.iter() {
+            let param_type: Type = match param.is_variadic {
+                true => Type::List(Box::new(param.param_type.clone())),
+                false => param.param_type.clone(),
+            };
+            self.declare_variable(&param.name, param_type);
+        }
+        let current_function: Option<(String, Type)> = self
+            .current_function
+            .replace((node.name.clone(), node.return_type.clone()));
+        let is_function_body: bool = std::mem::replace(&mut self.is_function_body, true);
+
+        for (line, statement) in node.body.iter() {
+            self.check_statement(statement, *line);
+        }
+
+        self.is_function_body = is_function_body;
+        self.current_function = current_function;
+        self.pop_scope();
+        if let Some((variable_scopes, function_scopes)) = outer_scopes {
+            self.variable_scopes = variable_scopes;
+            self.function_scopes = function_scopes;
+        }
+    }
+
+    /// 式を検査し、式の型を求める
+    ///
+    /// 型が求められない場合は `None` を返す
+    fn check_expression(&mut self, expression: &ExpressionNode, line: usize) -> Option<Type> {
+        self.line = line;
+        type_of_expression(self, expression)
+    }
+
+    /// 引数の式を検査し、関数のシグネチャが一つに決まる場合は引数の型をパラメータの型と比べる
+    ///
+    /// 位置引数は宣言順のパラメータに、名前付き引数は同じ名前のパラメータに対応させます。
+    /// 可変長パラメータに渡す引数は、それぞれを要素の型と比べます。
+    fn check_arguments(
+        &mut self,
+        function_name: &str,
+        arguments: &[ArgumentNode],
+        signatures: Option<&[FunctionSignature]>,
+        line: usize,
+    ) {
+        let argument_types: Vec<Option<Type>> = arguments
+            .iter()
+            .map(|argument| self.check_expression(&argument.value, line))
+            .collect();
+
+        // オーバーロードがある場合は、どの関数が呼ばれるか決まらない
+        let params: &[ParameterNode] = match signatures {
+            Some([signature]) => &signature.params,
+            _ => return,
+        };
+
+        let mut position: usize = 0;
+        for (argument, actual) in arguments.iter().zip(argument_types) {
+            let param: Option<&ParameterNode> = match &argument.name {
+                Some(name) => params.iter().find(|param| &param.name == name),
+                None => {
+                    let param: Option<&ParameterNode> = params.get(position);
+                    if !param.is_some_and(|param| param.is_variadic) {
+                        position += 1;
+                    }
+                    param
+                }
+            };
+            let (param, actual) = match (param, actual) {
+                (Some(param), Some(actual)) => (param, actual),
+                _ => continue,
+            };
+            // 展開される引数は、要素の型が決まらない
+            if argument.is_spread {
+                continue;
+            }
+            if !self.is_assignable(&actual, &param.param_type) {
+                self.errors.push(TypeCheckError::ArgumentTypeMismatch {
+                    function_name: function_name.to_string(),
+                    param_name: param.name.clone(),
+                    line,
+                    expected: param.param_type.to_string(),
+                    actual: actual.to_string(),
+                });
+            }
+        }
+    }
+
+    /// 型 `actual` の値を、型 `expected` として扱えるか判定する
+    ///
+    /// interface型には、interfaceの全てのメソッドを実装した型が当てはまります。
+    fn is_assignable(&self, actual: &Type, expected: &Type) -> bool {
+        if is_subtype(actual.clone(), expected.clone()) {
+            return true;
+        }
+        match (actual, expected) {
+            (Type::Union(member_types), expected) => member_types
+                .iter()
+                .all(|member_type| self.is_assignable(member_type, expected)),
+            (actual, Type::Interface(_, signatures)) => {
+                self.method_mapper.conforms(&actual.to_string(), signatures)
+            }
+            (actual, Type::Optional(inner_type)) => self.is_assignable(actual, inner_type),
+            (actual, Type::Union(member_types)) => member_types
+                .iter()
+                .any(|member_type| self.is_assignable(actual, member_type)),
+            _ => false,
+        }
+    }
+
+    fn push_scope(&mut self) {
+        self.variable_scopes.push(HashMap::new());
+        self.function_scopes.push(HashMap::new());
+    }
+
+    fn pop_scope(&mut self) {
+        self.variable_scopes.pop();
+        self.function_scopes.pop();
+    }
+
+    fn declare_variable(&mut self, name: &str, var_type: Type) {
+        self.variable_scopes
+            .last_mut()
+            .expect("variable scope must exist")
+            .insert(name.to_string(), var_type);
+    }
+
+    fn lookup_variable(&self, name: &str) -> Option<Type> {
+        self.variable_scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.get(name).cloned())
+    }
+
+    fn lookup_function(&self, name: &str) -> Option<Vec<FunctionSignature>> {
+        self.function_scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.get(name).cloned())
+    }
+}
+
+impl ExpressionTyper for TypeChecker {
+    fn variable_type(&self, name: &str) -> Option<Type> {
+        self.lookup_variable(name)
+    }
+
+    fn function_call_type(&mut self, node: &FunctionCallNode) -> Option<Type> {
+        let signatures: Option<Vec<FunctionSignature>> = self.lookup_function(&node.name);
+        self.check_arguments(
+            &node.name,
+            &node.arguments,
+            signatures.as_deref(),
+            self.line,
+        );
+        let signatures: Vec<FunctionSignature> = signatures?;
+        // オーバーロードの戻り値の型が異なる場合は、どの関数が呼ばれるか決まらない
+        let return_type: &Type = &signatures.first()?.return_type;
+        match signatures
+            .iter()
+            .all(|signature| &signature.return_type == return_type)
+        {
+            true => Some(return_type.clone()),
+            false => None,
+        }
+    }
+
+    fn partial_application(&mut self, node: &FunctionCallNode) {
+        let signatures: Option<Vec<FunctionSignature>> = self.lookup_function(&node.name);
+        self.check_arguments(
+            &node.name,
+            &node.arguments,
+            signatures.as_deref(),
+            self.line,
+        );
+    }
+
+    fn method_call_type(
+        &mut self,
+        node: &MethodCallNode,
+        receiver_type: Option<Type>,
+    ) -> Option<Type> {
+        let receiver_type: Type = match receiver_type {
+            Some(receiver_type) => receiver_type,
+            None => {
+                self.check_arguments(&node.name, &node.arguments, None, self.line);
+                return None;
+            }
+        };
+        let method: Option<Rc<FunctionDeclarationNode>> = self
+            .method_mapper
+            .get(&receiver_type.to_string(), &node.name, self.line)
+            .ok();
+        // self を除いたパラメータと引数を比べる
+        let signature: Option<Vec<FunctionSignature>> = method.as_ref().map(|method| {
+            vec![FunctionSignature {
+                params: method.params.iter().skip(1).cloned().collect(),
+                return_type: method.return_type.clone(),
+            }]
+        });
+        let method_name: String = format!("{}.{}", receiver_type, node.name);
+        self.check_arguments(
+            &method_name,
+            &node.arguments,
+            signature.as_deref(),
+            self.line,
+        );
+        method.map(|method| method.return_type.clone())
+    }
+
+    fn type_cast(&mut self, node: &TypeCastNode, actual: Option<Type>) {
+        if let (Some(actual), Some(from_type)) = (actual, &node.from_type) {
+            // 0 as float -> int;  # InvalidCastSource
+            if !self.is_assignable(&actual, from_type) {
+                self.errors.push(TypeCheckError::InvalidCastSource {
+                    line: self.line,
+                    from_type: from_type.to_string(),
+                    actual: actual.to_string(),
+                });
+            }
+        }
+    }
+
+    fn narrowed_type_of(
+        &mut self,
+        narrowed_types: HashMap<String, Type>,
+        expression: &ExpressionNode,
+    ) -> Option<Type> {
+        self.variable_scopes.push(narrowed_types);
+        let expression_type: Option<Type> = type_of_expression(self, expression);
+        self.variable_scopes.pop();
+        expression_type
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::virtual_machine::evaluator::core::parse_source;
+    use crate::virtual_machine::type_checker::{check_types, TypeCheckError};
+
+    fn check_source(source_code: &str) -> Result<(), Vec<TypeCheckError>> {
+        check_types(&parse_source(source_code))
+    }
+
+    /// 型が一致するプログラムはエラーにならないか確認するテスト
+    #[test]
+    fn test_check_well_typed_program() {
+        let source_code: &str = r#"
+let a: int = 1 + 2;
+let b: float = a as int -> float;
+let c: int | string = "a";
+let f: fn = (x: int, ...ys: int): int { return x; };
+let d: int = f(x: 1, ys: 2, ys: 3);
+let e: int = if c is int { c } else { 0 };
+"#;
+        assert_eq!(check_source(source_code), Ok(()));
+    }
+
+    /// 同じ型に同じ名前のメソッドを実装すると、評価の前にエラーになるか確認するテスト
+    #[test]
+    fn test_check_duplicate_method() {
+        let source_code: &str = r#"
+impl int { let d: fn = (self): int { return self; }; }
+impl int { let d: fn = (self): int { return self + 1; }; }
+"#;
+        assert_eq!(
+            check_source(source_code),
+            Err(vec![TypeCheckError::DuplicateMethod {
+                type_name: "int".to_string(),
+                name: "d".to_string(),
+                line: 3,
+            }])
+        );
+    }
+
+    /// 全ての型エラーがまとめて報告されるか確認するテスト
+    #[test]
+    fn test_check_reports_all_errors() {
+        let source_code: &str = r#"
+let f: int = "Hello" + " " + "World";
+let g: int = 0 as int -> float;
+let h: fn = (x: int): int { return "x"; };
+let i: int = h(x: 1.0);
+let j: float = 1 as float -> int;
+"#;
+        assert_eq!(
+            check_source(source_code),
+            Err(vec![
+                TypeCheckError::VariableTypeMismatch {
+                    name: "f".to_string(),
+                    line: 2,
+                    expected: "int".to_string(),
+                    actual: "string".to_string(),
+                },
+                TypeCheckError::VariableTypeMismatch {
+                    name: "g".to_string(),
+                    line: 3,
+                    expected: "int".to_string(),
+                    actual: "float".to_string(),
+                },
+                TypeCheckError::ReturnTypeMismatch {
+                    function_name: "h".to_string(),
+                    line: 4,
+                    expected: "int".to_string(),
+                    actual: "string".to_string(),
+                },
+                TypeCheckError::ArgumentTypeMismatch {
+                    function_name: "h".to_string(),
+                    param_name: "x".to_string(),
+                    line: 5,
+                    expected: "int".to_string(),
+                    actual: "float".to_string(),
+                },
+                TypeCheckError::InvalidCastSource {
+                    line: 6,
+                    from_type: "float".to_string(),
+                    actual: "int".to_string(),
+                },
+                TypeCheckError::VariableTypeMismatch {
+                    name: "j".to_string(),
+                    line: 6,
+                    expected: "float".to_string(),
+                    actual: "int".to_string(),
+                },
+            ])
+        );
+    }
+
+    /// interface型のパラメータに、メソッドを実装した型の値を渡せるか確認するテスト
+    #[test]
+    fn test_check_interface_conformance() {
+        let source_code: &str = r#"
+interface Doubling { let double: fn = (self): int; }
+impl int { let double: fn = (self): int { return self + self; }; }
+let f: fn = (x: Doubling): int { return x.double(); };
+let a: int = f(x: 2);
+let b: int = f(x: "a");
+"#;
+        assert_eq!(
+            check_source(source_code),
+            Err(vec![TypeCheckError::ArgumentTypeMismatch {
+                function_name: "f".to_string(),
+                param_name: "x".to_string(),
+                line: 6,
+                expected: "Doubling".to_string(),
+                actual: "string".to_string(),
+            }])
+        );
+    }
+
+    /// 型が求められない式は検査しないことを確認するテスト
+    #[test]
+    fn test_check_skips_unknown_expressions() {
+        let source_code: &str = r#"
+let k: int = some_function(i: 1) + 1;
+let g: fn = (): int { return a; };
+"#;
+        assert_eq!(check_source(source_code), Ok(()));
+    }
+
+    /// 関数の本体の文で見つかった型エラーが、その文の行番号で報告されるか確認するテスト
+    #[test]
+    fn test_check_reports_line_in_function_body() {
+        let source_code: &str = r#"let f: fn = (x: int): int {
+    let y: int = x;
+    let z: string = "a";
+    return z;
+};
+"#;
+        assert_eq!(
+            check_source(source_code),
+            Err(vec![TypeCheckError::ReturnTypeMismatch {
+                function_name: "f".to_string(),
+                line: 4,
+                expected: "int".to_string(),
+                actual: "string".to_string(),
+            }])
+        );
+    }
+}
@@ -0,0 +1,41 @@
+use thiserror::Error;
+
+#[derive(Debug, Clone, Error, PartialEq)]
+pub enum TypeCheckError {
+    #[error("Type Mismatch Error at line {line}. {name} is declared as {expected}, but the initializer is {actual}")]
+    VariableTypeMismatch {
+        name: String,
+        line: usize,
+        expected: String,
+        actual: String,
+    },
+    #[error("Type Mismatch Error at line {line}. Parameter {param_name} of {function_name} expects {expected}, but got {actual}")]
+    ArgumentTypeMismatch {
+        function_name: String,
+        param_name: String,
+        line: usize,
+        expected: String,
+        actual: String,
+    },
+    #[error("Type Mismatch Error at line {line}. {function_name} returns {expected}, but the return expression is {actual}")]
+    ReturnTypeMismatch {
+        function_name: String,
+        line: usize,
+        expected: String,
+        actual: String,
+    },
+    #[error(
+        "Type Cast Error at line {line}. from_type: {from_type}, but the expression is {actual}"
+    )]
+    InvalidCastSource {
+        line: usize,
+        from_type: String,
+        actual: String,
+    },
+    #[error("Duplicate Method Error at line {line}. Method {name} is already implemented for {type_name}")]
+    DuplicateMethod {
+        type_name: String,
+        name: String,
+        line: usize,
+    },
+}
@@ -0,0 +1,528 @@
+mod type_inference_error;
+
+use crate::virtual_machine::ast::{
+    BlockNode, ExpressionNode, FunctionCallNode, FunctionDeclarationNode, ImplNode, MethodCallNode,
+    Statement, Type, VariableDeclarationNode, AST,
+};
+use crate::virtual_machine::expression_typer::{type_of_expression, ExpressionTyper};
+use std::collections::HashMap;
+use std::rc::Rc;
+pub use type_inference_error::TypeInferenceError;
+
+/// 型注釈が省略された宣言の型を推論する
+///
+/// 型推論が有効な場合、Parserは型注釈が省略された変数宣言と戻り値の型が省略された関数宣言の型を
+/// `Type::Infer` とします。この関数は、それらの型を初期化式やreturn文の式から求めて置き換えます。
+///
+/// ```shot
+/// let a = 1;                              # int
+/// let b = a as float;                     # float
+/// let f: fn = (x: int) { return x + 1; }; # 戻り値の型は int
+/// let c = f(x: 1);                        # int
+/// ```
+///
+/// 関数の本体は新しいスコープとして推論されます。本体では、パラメータと本体で宣言された変数と関数のみ参照できます。
+/// 関数の中で宣言された関数の本体では、外側の関数の変数と関数も参照できます。
+///
+/// 条件分岐の条件が `変数 is 型` の場合、分岐の中ではその変数の型を絞り込みます。
+///
+/// ```shot
+/// let v: int | string = 1;
+/// let a = if v is int { v + 1 } else { v };  # 真の分岐で v は int、偽の分岐で v は string
+/// ```
+///
+/// ## Raises
+///
+/// * `TypeInferenceError::CannotInferType` - 式から型を求められない場合
+pub(crate) fn infer_types(ast: AST) -> Result<AST, TypeInferenceError> {
+    let mut inferrer: TypeInferrer = TypeInferrer::new();
+    let mut inferred_ast: AST = AST::new();
+    for (line, statement) in ast.statements {
+        let inferred_statement: Statement = inferrer.infer_statement(statement, line)?;
+        inferred_ast.push_statement(line, inferred_statement);
+    }
+    Ok(inferred_ast)
+}
+
+/// スコープ内で宣言された変数の型と関数の戻り値の型を保持する
+///
+/// 関数の戻り値の型は、オーバーロードごとに宣言順に保持する
+/// メソッドの戻り値の型は、`Host.url` のように型の名前で修飾して保持する
+struct TypeInferrer {
+    variable_types: HashMap<String, Type>,
+    function_return_types: HashMap<String, Vec<Type>>,
+    method_return_types: HashMap<String, Type>,
+    is_function_body: bool,
+}
+
+impl TypeInferrer {
+    fn new() -> Self {
+        TypeInferrer {
+            variable_types: HashMap::new(),
+            function_return_types: HashMap::new(),
+            method_return_types: HashMap::new(),
+            is_function_body: false,
+        }
+    }
+
+    fn infer_statement(
+        &mut self,
+        statement: Statement,
+        line: usize,
+    ) -> Result<Statement, TypeInferenceError> {
+        match statement {
+            Statement::DeclarationOfVariable(node) => Ok(Statement::DeclarationOfVariable(
+                Box::new(self.infer_variable_declaration(*node, line)?),
+            )),
+            Statement::DeclarationOfConstant(node) => Ok(Statement::DeclarationOfConstant(
+                Box::new(self.infer_variable_declaration(*node, line)?),
+            )),
+            Statement::DeclarationOfFunction(node) => Ok(Statement::DeclarationOfFunction(
+                Rc::new(self.infer_function_declaration(Rc::unwrap_or_clone(node), line)?),
+            )),
+            Statement::Impl(node) => {
+                // メソッドの本体は、グローバルに宣言された関数と同じく新しいスコープで推論する
+                let mut methods: Vec<Rc<FunctionDeclarationNode>> = vec![];
+                for method in node.methods {
+                    let mut method_inferrer: TypeInferrer = TypeInferrer {
+                        method_return_types: self.method_return_types.clone(),
+                        ..TypeInferrer::new()
+                    };
+                    let method: FunctionDeclarationNode = method_inferrer
+                        .infer_function_declaration(Rc::unwrap_or_clone(method), line)?;
+                    self.method_return_types.insert(
+                        format!("{}.{}", node.target, method.name),
+                        method.return_type.clone(),
+                    );
+                    methods.push(Rc::new(method));
+                }
+                Ok(Statement::Impl(Box::new(ImplNode {
+                    target: node.target,
+                    methods,
+                })))
+            }
+            Statement::Export(declaration) => Ok(Statement::Export(Box::new(
+                self.infer_statement(*declaration, line)?,
+            ))),
+            Statement::Block(block) => {
+                // ブロックで宣言された変数と関数は、ブロックを抜けると参照できない
+                let variable_types: HashMap<String, Type> = self.variable_types.clone();
+                let function_return_types: HashMap<String, Vec<Type>> =
+                    self.function_return_types.clone();
+                let mut statements: Vec<(usize, Statement)> = vec![];
+                for (line, statement) in block.statements {
+                    statements.push((line, self.infer_statement(statement, line)?));
+                }
+                self.variable_types = variable_types;
+                self.function_return_types = function_return_types;
+                Ok(Statement::Block(Box::new(BlockNode { statements })))
+            }
+            _ => Ok(statement),
+        }
+    }
+
+    fn infer_variable_declaration(
+        &mut self,
+        node: VariableDeclarationNode,
+        line: usize,
+    ) -> Result<VariableDeclarationNode, TypeInferenceError> {
+        let var_type: Type = match node.var_type {
+            Type::Infer => match self.infer_expression(&node.value) {
+                Some(var_type) => var_type,
+                // let a = undefined_variable;  # CannotInferType
+                None => {
+                    return Err(TypeInferenceError::CannotInferType {
+                        name: node.name,
+                        line,
+                    })
+                }
+            },
+            var_type => var_type,
+        };
+
+        self.variable_types
+            .insert(node.name.clone(), var_type.clone());
+
+        // 部分適用で作られた関数の戻り値の型は、元の関数の戻り値の型
+        // let inc = add(x: 1, ...);
+        // let a = inc(y: 2);  # int
+        if let ExpressionNode::PartialApplication(call) = node.value.as_ref() {
+            if let Some(return_type) = self.infer_return_type(&call.name) {
+                self.function_return_types
+                    .insert(node.name.clone(), vec![return_type]);
+            }
+        }
+        Ok(VariableDeclarationNode { var_type, ..node })
+    }
+
+    fn infer_function_declaration(
+        &mut self,
+        node: FunctionDeclarationNode,
+        line: usize,
+    ) -> Result<FunctionDeclarationNode, TypeInferenceError> {
+        // 関数の本体は新しいスコープとして推論する
+        // 関数の中で宣言された関数は、外側のスコープの変数と関数を引き継ぐ
+        let mut body_inferrer: TypeInferrer = match self.is_function_body {
+            true => TypeInferrer {
+                is_function_body: true,
+                ..self.inherit()
+            },
+            false => TypeInferrer {
+                method_return_types: self.method_return_types.clone(),
+                is_function_body: true,
+                ..TypeInferrer::new()
+            },
+        };
+        for param in node.params.iter() {
+            let param_type: Type = match param.is_variadic {
+                true => Type::List(Box::new(param.param_type.clone())),
+                false => param.param_type.clone(),
+            };
+            body_inferrer
+                .variable_types
+                .insert(param.name.clone(), param_type);
+        }
+
+        let mut body: Vec<(usize, Statement)> = vec![];
+        let mut returned_type: Option<Type> = None;
+        for (statement_line, statement) in node.body {
+            if let Statement::Return(expression) = &statement {
+                returned_type = body_inferrer.infer_expression(expression);
+            }
+            body.push((
+                statement_line,
+                body_inferrer.infer_statement(statement, statement_line)?,
+            ));
+        }
+
+        let return_type: Type = match node.return_type {
+            Type::Infer => match returned_type {
+                Some(return_type) => return_type,
+                // 再帰呼び出しなど、return文の式の型が求められない
+                None => {
+                    return Err(TypeInferenceError::CannotInferType {
+                        name: node.name,
+                        line,
+                    })
+                }
+            },
+            return_type => return_type,
+        };
+
+        self.function_return_types
+            .entry(node.name.clone())
+            .or_default()
+            .push(return_type.clone());
+        Ok(FunctionDeclarationNode {
+            return_type,
+            body,
+            ..node
+        })
+    }
+
+    /// 関数呼び出しの戻り値の型を求める
+    ///
+    /// オーバーロードの戻り値の型が異なる場合は、どの関数が呼ばれるか決まらないので `None` を返す
+    fn infer_return_type(&self, name: &str) -> Option<Type> {
+        let return_types: &Vec<Type> = self.function_return_types.get(name)?;
+        let return_type: &Type = return_types.first()?;
+        match return_types.iter().all(|other| other == return_type) {
+            true => Some(return_type.clone()),
+            false => None,
+        }
+    }
+
+    /// 式の型を求める
+    ///
+    /// 型が求められない場合は `None` を返す
+    fn infer_expression(&mut self, expression: &ExpressionNode) -> Option<Type> {
+        type_of_expression(self, expression)
+    }
+
+    /// 変数の型と関数の戻り値の型を引き継いだTypeInferrerを作る
+    fn inherit(&self) -> TypeInferrer {
+        TypeInferrer {
+            variable_types: self.variable_types.clone(),
+            function_return_types: self.function_return_types.clone(),
+            method_return_types: self.method_return_types.clone(),
+            is_function_body: self.is_function_body,
+        }
+    }
+}
+
+impl ExpressionTyper for TypeInferrer {
+    fn variable_type(&self, name: &str) -> Option<Type> {
+        self.variable_types.get(name).cloned()
+    }
+
+    fn function_call_type(&mut self, node: &FunctionCallNode) -> Option<Type> {
+        self.infer_return_type(&node.name)
+    }
+
+    fn partial_application(&mut self, _node: &FunctionCallNode) {}
+
+    fn method_call_type(
+        &mut self,
+        node: &MethodCallNode,
+        receiver_type: Option<Type>,
+    ) -> Option<Type> {
+        self.method_return_types
+            .get(&format!("{}.{}", receiver_type?, node.name))
+            .cloned()
+    }
+
+    fn narrowed_type_of(
+        &mut self,
+        narrowed_types: HashMap<String, Type>,
+        expression: &ExpressionNode,
+    ) -> Option<Type> {
+        let mut inferrer: TypeInferrer = self.inherit();
+        inferrer.variable_types.extend(narrowed_types);
+        inferrer.infer_expression(expression)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::virtual_machine::ast::{
+        BinaryOperationNode, BinaryOperator, ExpressionNode, FunctionCallNode,
+        FunctionDeclarationNode, IfNode, LiteralNode, LiteralValue, ParameterNode, Statement, Type,
+        TypeCastNode, TypeTestNode, VariableCallNode, VariableDeclarationNode, AST,
+    };
+    use crate::virtual_machine::type_inferrer::{infer_types, TypeInferenceError};
+    use std::rc::Rc;
+
+    fn create_variable_declaration(name: &str, var_type: Type, value: ExpressionNode) -> Statement {
+        Statement::DeclarationOfVariable(Box::new(VariableDeclarationNode {
+            name: name.to_string(),
+            var_type,
+            value: Box::new(value),
+        }))
+    }
+
+    fn variable(name: &str) -> ExpressionNode {
+        ExpressionNode::CallOfVariable(Box::new(VariableCallNode {
+            name: name.to_string(),
+        }))
+    }
+
+    /// 初期化式から変数の型を推論できることを確認します。
+    ///
+    /// let a = 1;
+    /// let b = a + 2;
+    /// let c = "1" as? int;
+    #[test]
+    fn test_infer_variable_types() {
+        let mut ast: AST = AST::new();
+        ast.push_statement(
+            1,
+            create_variable_declaration(
+                "a",
+                Type::Infer,
+                ExpressionNode::Literal(Box::new(LiteralNode {
+                    value: LiteralValue::Integer(1),
+                })),
+            ),
+        );
+        ast.push_statement(
+            2,
+            create_variable_declaration(
+                "b",
+                Type::Infer,
+                ExpressionNode::BinaryOperation(Box::new(BinaryOperationNode {
+                    left: Box::new(variable("a")),
+                    operator: BinaryOperator::Add,
+                    right: Box::new(ExpressionNode::Literal(Box::new(LiteralNode {
+                        value: LiteralValue::Integer(2),
+                    }))),
+                })),
+            ),
+        );
+        ast.push_statement(
+            3,
+            create_variable_declaration(
+                "c",
+                Type::Infer,
+                ExpressionNode::TypeCast(Box::new(TypeCastNode {
+                    from_type: None,
+                    to_type: Type::Integer,
+                    is_checked: true,
+                    expression: Box::new(ExpressionNode::Literal(Box::new(LiteralNode {
+                        value: LiteralValue::String("1".to_string()),
+                    }))),
+                })),
+            ),
+        );
+
+        let inferred_types: Vec<Type> = infer_types(ast)
+            .unwrap()
+            .statements
+            .into_iter()
+            .map(|(_, statement)| match statement {
+                Statement::DeclarationOfVariable(node) => node.var_type,
+                _ => panic!("Expected a DeclarationOfVariable"),
+            })
+            .collect();
+        assert_eq!(
+            inferred_types,
+            vec![
+                Type::Integer,
+                Type::Integer,
+                Type::Optional(Box::new(Type::Integer))
+            ]
+        );
+    }
+
+    /// return文の式から関数の戻り値の型を推論し、呼び出し結果の型に用いることを確認します。
+    ///
+    /// let f: fn = (x: float) { return x; };
+    /// let a = f(x: 1.0);
+    #[test]
+    fn test_infer_function_return_type() {
+        let mut ast: AST = AST::new();
+        ast.push_statement(
+            1,
+            Statement::DeclarationOfFunction(Rc::new(FunctionDeclarationNode {
+                name: "f".to_string(),
+                params: vec![ParameterNode {
+                    name: "x".to_string(),
+                    param_type: Type::Float,
+                    default_value: None,
+                    is_variadic: false,
+                }],
+                return_type: Type::Infer,
+                body: vec![(1, Statement::Return(Box::new(variable("x"))))],
+            })),
+        );
+        ast.push_statement(
+            2,
+            create_variable_declaration(
+                "a",
+                Type::Infer,
+                ExpressionNode::CallOfFunction(Box::new(FunctionCallNode {
+                    name: "f".to_string(),
+                    arguments: vec![],
+                })),
+            ),
+        );
+
+        let statements: Vec<(usize, Statement)> = infer_types(ast).unwrap().statements;
+        match &statements[0].1 {
+            Statement::DeclarationOfFunction(node) => assert_eq!(node.return_type, Type::Float),
+            _ => panic!("Expected a DeclarationOfFunction"),
+        }
+        match &statements[1].1 {
+            Statement::DeclarationOfVariable(node) => assert_eq!(node.var_type, Type::Float),
+            _ => panic!("Expected a DeclarationOfVariable"),
+        }
+    }
+
+    /// 型を求められない初期化式の場合、エラーが返されることを確認します。
+    ///
+    /// let a = b;  # CannotInferType
+    #[test]
+    fn test_infer_unknown_variable() {
+        let mut ast: AST = AST::new();
+        ast.push_statement(
+            1,
+            create_variable_declaration("a", Type::Infer, variable("b")),
+        );
+
+        assert_eq!(
+            infer_types(ast).map(|ast| ast.statements),
+            Err(TypeInferenceError::CannotInferType {
+                name: "a".to_string(),
+                line: 1
+            })
+        );
+    }
+
+    /// 関数の本体で型を求められない場合、その文の行番号でエラーが返されることを確認します。
+    ///
+    /// let f: fn = (x: float): float {
+    ///     let a = b;  # CannotInferType
+    ///     return x;
+    /// };
+    #[test]
+    fn test_infer_unknown_variable_in_function_body() {
+        let mut ast: AST = AST::new();
+        ast.push_statement(
+            1,
+            Statement::DeclarationOfFunction(Rc::new(FunctionDeclarationNode {
+                name: "f".to_string(),
+                params: vec![ParameterNode {
+                    name: "x".to_string(),
+                    param_type: Type::Float,
+                    default_value: None,
+                    is_variadic: false,
+                }],
+                return_type: Type::Float,
+                body: vec![
+                    (
+                        2,
+                        create_variable_declaration("a", Type::Infer, variable("b")),
+                    ),
+                    (3, Statement::Return(Box::new(variable("x")))),
+                ],
+            })),
+        );
+
+        assert_eq!(
+            infer_types(ast).map(|ast| ast.statements),
+            Err(TypeInferenceError::CannotInferType {
+                name: "a".to_string(),
+                line: 2
+            })
+        );
+    }
+
+    /// 条件分岐の中で、型検査した変数の型が絞り込まれることを確認します。
+    ///
+    /// let v: int | string = 1;
+    /// let a = if v is string { v } else { v + 1 };  # 真の分岐は string、偽の分岐は int
+    #[test]
+    fn test_infer_if_with_narrowing() {
+        let mut ast: AST = AST::new();
+        ast.push_statement(
+            1,
+            create_variable_declaration(
+                "v",
+                Type::Union(vec![Type::Integer, Type::String]),
+                ExpressionNode::Literal(Box::new(LiteralNode {
+                    value: LiteralValue::Integer(1),
+                })),
+            ),
+        );
+        ast.push_statement(
+            2,
+            create_variable_declaration(
+                "a",
+                Type::Infer,
+                ExpressionNode::If(Box::new(IfNode {
+                    condition: Box::new(ExpressionNode::TypeTest(Box::new(TypeTestNode {
+                        expression: Box::new(variable("v")),
+                        tested_type: Type::String,
+                    }))),
+                    then_branch: Box::new(variable("v")),
+                    else_branch: Box::new(ExpressionNode::BinaryOperation(Box::new(
+                        BinaryOperationNode {
+                            left: Box::new(variable("v")),
+                            operator: BinaryOperator::Add,
+                            right: Box::new(ExpressionNode::Literal(Box::new(LiteralNode {
+                                value: LiteralValue::Integer(1),
+                            }))),
+                        },
+                    ))),
+                })),
+            ),
+        );
+
+        match &infer_types(ast).unwrap().statements[1].1 {
+            Statement::DeclarationOfVariable(node) => assert_eq!(
+                node.var_type,
+                Type::Union(vec![Type::String, Type::Integer])
+            ),
+            _ => panic!("Expected a DeclarationOfVariable"),
+        }
+    }
+}
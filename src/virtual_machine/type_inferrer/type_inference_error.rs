@@ -0,0 +1,7 @@
+use thiserror::Error;
+
+#[derive(Debug, Clone, Error, PartialEq)]
+pub enum TypeInferenceError {
+    #[error("Cannot infer the type of {name} at line {line}. Please write the type annotation.")]
+    CannotInferType { name: String, line: usize },
+}